@@ -0,0 +1,311 @@
+//! Procedural macros for [pstr](https://crates.io/crates/pstr), use them through the `macros` feature of pstr
+
+use std::collections::HashMap;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    braced,
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+    Ident, LitStr, Token, Visibility,
+};
+
+struct Entry {
+    name: Ident,
+    value: LitStr,
+}
+
+impl Parse for Entry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value = input.parse()?;
+        Ok(Self { name, value })
+    }
+}
+
+struct Symbols {
+    vis: Visibility,
+    name: Ident,
+    entries: Punctuated<Entry, Token![,]>,
+}
+
+impl Parse for Symbols {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis = input.parse()?;
+        input.parse::<Token![mod]>()?;
+        let name = input.parse()?;
+        let content;
+        braced!(content in input);
+        let entries = content.parse_terminated(Entry::parse, Token![,])?;
+        Ok(Self { vis, name, entries })
+    }
+}
+
+/// Declare a table of symbols with stable indexes, collected at compile time
+///
+/// Generates a module with a `Symbol` constant per entry, the `TABLE` of strings,
+/// and a `preseed()` function registering the table, which must be called before any other symbol is created.
+/// Only one table can be preseeded per program.
+/// Duplicate strings are rejected at compile time
+///
+/// # Example
+/// ```
+/// use pstr::{symbols, Symbol};
+///
+/// symbols! {
+///     pub mod kw {
+///         FN = "fn",
+///         LET = "let",
+///     }
+/// }
+///
+/// kw::preseed();
+/// match Symbol::new("let") {
+///     kw::FN => unreachable!(),
+///     kw::LET => {}
+///     _ => unreachable!(),
+/// }
+/// assert_eq!(kw::FN.resolve(), "fn");
+/// ```
+#[proc_macro]
+pub fn symbols(input: TokenStream) -> TokenStream {
+    let Symbols { vis, name, entries } = parse_macro_input!(input as Symbols);
+
+    let mut seen = HashMap::new();
+    for entry in &entries {
+        if let Some(first) = seen.insert(entry.value.value(), &entry.name) {
+            return syn::Error::new(
+                entry.value.span(),
+                format!("duplicate symbol, already declared as `{}`", first),
+            )
+            .to_compile_error()
+            .into();
+        }
+    }
+
+    let len = entries.len();
+    let values = entries.iter().map(|e| &e.value);
+    let consts = entries.iter().enumerate().map(|(i, e)| {
+        let name = &e.name;
+        let doc = format!("Symbol of `{:?}`", e.value.value());
+        let i = i as u32;
+        quote! {
+            #[doc = #doc]
+            pub const #name: ::pstr::Symbol = ::pstr::Symbol::__from_u32(#i);
+        }
+    });
+
+    let expanded = quote! {
+        #vis mod #name {
+            #(#consts)*
+
+            /// The strings of this table, in symbol order
+            pub static TABLE: [&str; #len] = [#(#values),*];
+
+            /// Register this table as the first symbols
+            ///
+            /// # Panics
+            /// Panics if other symbols were already created
+            pub fn preseed() {
+                assert!(
+                    ::pstr::Symbol::preseed(&TABLE),
+                    "symbols were created before preseeding the table"
+                );
+            }
+        }
+    };
+    expanded.into()
+}
+
+/// Options of `#[derive(Interning)]` given in `#[interning(...)]`
+#[derive(Default)]
+struct InterningOptions {
+    name: Option<Ident>,
+    derives: Vec<syn::Path>,
+}
+
+impl InterningOptions {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut options = Self::default();
+        for attr in attrs.iter().filter(|a| a.path().is_ident("interning")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    options.name = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("derive") {
+                    meta.parse_nested_meta(|derive| {
+                        options.derives.push(derive.path);
+                        Ok(())
+                    })
+                } else {
+                    Err(meta.error("expected `name` or `derive`"))
+                }
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+/// How a field is converted by `#[derive(Interning)]`
+enum FieldKind {
+    String,
+    OptionString,
+    VecString,
+    Other,
+}
+
+impl FieldKind {
+    fn of(ty: &syn::Type) -> Self {
+        let segment = match ty {
+            syn::Type::Path(p) if p.qself.is_none() => p.path.segments.last().unwrap(),
+            _ => return Self::Other,
+        };
+        let inner_is_string = || match &segment.arguments {
+            syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+                matches!(&args.args[0], syn::GenericArgument::Type(t) if matches!(Self::of(t), Self::String))
+            }
+            _ => false,
+        };
+        if segment.ident == "String" && segment.arguments.is_empty() {
+            Self::String
+        } else if segment.ident == "Option" && inner_is_string() {
+            Self::OptionString
+        } else if segment.ident == "Vec" && inner_is_string() {
+            Self::VecString
+        } else {
+            Self::Other
+        }
+    }
+
+    fn ty(&self, ty: &syn::Type) -> proc_macro2::TokenStream {
+        match self {
+            Self::String => quote!(::pstr::IStr),
+            Self::OptionString => quote!(::std::option::Option<::pstr::IStr>),
+            Self::VecString => quote!(::std::vec::Vec<::pstr::IStr>),
+            Self::Other => quote!(#ty),
+        }
+    }
+
+    fn convert(&self, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match self {
+            Self::String => quote!(::pstr::IStr::from_string(#value)),
+            Self::OptionString => quote!(#value.map(::pstr::IStr::from_string)),
+            Self::VecString => quote!(::pstr::IStr::new_many(&#value)),
+            Self::Other => value,
+        }
+    }
+}
+
+/// Derive `pstr::Interning` for a struct, converting it into a mirrored struct with interned strings
+///
+/// The mirrored struct is named `Interned` followed by the name of the struct,
+/// `String`, `Option<String>` and `Vec<String>` fields become `IStr`, `Option<IStr>` and `Vec<IStr>`,
+/// other fields are moved as they are.
+/// `#[interning(name = Other)]` renames the mirrored struct and `#[interning(derive(Debug, Clone))]` adds derives to it
+///
+/// # Example
+/// ```
+/// use pstr::{IStr, Interning};
+///
+/// #[derive(Interning)]
+/// #[interning(derive(Debug, PartialEq))]
+/// pub struct Config {
+///     pub name: String,
+///     pub port: u16,
+///     pub tags: Vec<String>,
+/// }
+///
+/// let config = Config {
+///     name: "server".into(),
+///     port: 8080,
+///     tags: vec!["a".into()],
+/// }
+/// .interned();
+/// assert_eq!(config.name, IStr::new("server"));
+/// assert_eq!(config.port, 8080);
+/// assert_eq!(config.tags, [IStr::new("a")]);
+/// ```
+#[proc_macro_derive(Interning, attributes(interning))]
+pub fn derive_interning(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+    match expand_interning(input) {
+        Ok(v) => v.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand_interning(input: syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let options = InterningOptions::parse(&input.attrs)?;
+    let data = match &input.data {
+        syn::Data::Struct(data) => data,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`Interning` can only be derived for structs",
+            ))
+        }
+    };
+    let vis = &input.vis;
+    let name = &input.ident;
+    let outern = options
+        .name
+        .unwrap_or_else(|| Ident::new(&format!("Interned{}", name), name.span()));
+    let derives = &options.derives;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let doc = format!("`{}` with interned strings, see `pstr::Interning`", name);
+
+    let kinds: Vec<FieldKind> = data.fields.iter().map(|f| FieldKind::of(&f.ty)).collect();
+    let fields = data.fields.iter().zip(&kinds).map(|(f, kind)| {
+        let vis = &f.vis;
+        let ty = kind.ty(&f.ty);
+        match &f.ident {
+            Some(ident) => quote!(#vis #ident: #ty),
+            None => quote!(#vis #ty),
+        }
+    });
+    let (body, construct) = match &data.fields {
+        syn::Fields::Named(_) => {
+            let values = data.fields.iter().zip(&kinds).map(|(f, kind)| {
+                let ident = &f.ident;
+                let value = kind.convert(quote!(self.#ident));
+                quote!(#ident: #value)
+            });
+            (
+                quote!(#where_clause { #(#fields),* }),
+                quote!(#outern { #(#values),* }),
+            )
+        }
+        syn::Fields::Unnamed(_) => {
+            let values = kinds.iter().enumerate().map(|(i, kind)| {
+                let i = syn::Index::from(i);
+                kind.convert(quote!(self.#i))
+            });
+            (
+                quote!(( #(#fields),* ) #where_clause;),
+                quote!(#outern( #(#values),* )),
+            )
+        }
+        syn::Fields::Unit => (quote!(;), quote!(#outern)),
+    };
+    let generics = &input.generics;
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(#(#derives),*)]
+        #vis struct #outern #generics #body
+
+        unsafe impl #impl_generics ::pstr::intern::Interned for #outern #ty_generics #where_clause {}
+
+        impl #impl_generics ::pstr::Interning for #name #ty_generics #where_clause {
+            type Outern = #outern #ty_generics;
+
+            #[inline]
+            fn interned(self) -> Self::Outern {
+                #construct
+            }
+        }
+    })
+}
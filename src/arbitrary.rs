@@ -0,0 +1,165 @@
+//! Generating strings for fuzzing with [arbitrary](https://crates.io/crates/arbitrary)
+//! and for property tests with [proptest](https://crates.io/crates/proptest)
+//!
+//! `MowStr` and `MowOsStr` are randomly interned or mutable, so both states get covered
+
+use std::ffi::OsString;
+
+#[cfg(feature = "arbitrary")]
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+#[cfg(feature = "proptest")]
+use ::proptest::{
+    arbitrary::{any, any_with, StrategyFor},
+    strategy::{Map, Strategy},
+};
+
+use crate::{
+    ffi::{IOsStr, MowOsStr},
+    IStr, MowStr,
+};
+
+#[cfg(feature = "arbitrary")]
+impl<'a> Arbitrary<'a> for IStr {
+    #[inline]
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        <&str>::arbitrary(u).map(IStr::new)
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <&str>::size_hint(depth)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> Arbitrary<'a> for MowStr {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let s = <&str>::arbitrary(u)?;
+        Ok(if u.arbitrary()? {
+            MowStr::new_mut(s)
+        } else {
+            MowStr::new(s)
+        })
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        ::arbitrary::size_hint::and(<&str>::size_hint(depth), bool::size_hint(depth))
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> Arbitrary<'a> for IOsStr {
+    #[inline]
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        OsString::arbitrary(u).map(IOsStr::from_os_string)
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        OsString::size_hint(depth)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> Arbitrary<'a> for MowOsStr {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let s = OsString::arbitrary(u)?;
+        Ok(if u.arbitrary()? {
+            MowOsStr::from_os_string_mut(s)
+        } else {
+            MowOsStr::from_os_string(s)
+        })
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        ::arbitrary::size_hint::and(OsString::size_hint(depth), bool::size_hint(depth))
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl ::proptest::arbitrary::Arbitrary for IStr {
+    type Parameters = <String as ::proptest::arbitrary::Arbitrary>::Parameters;
+    type Strategy = Map<StrategyFor<String>, fn(String) -> Self>;
+
+    fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+        any_with::<String>(args).prop_map(IStr::from_string)
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl ::proptest::arbitrary::Arbitrary for MowStr {
+    type Parameters = <String as ::proptest::arbitrary::Arbitrary>::Parameters;
+    type Strategy = Map<(StrategyFor<String>, StrategyFor<bool>), fn((String, bool)) -> Self>;
+
+    fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+        (any_with::<String>(args), any::<bool>()).prop_map(|(s, mutable)| {
+            if mutable {
+                MowStr::from_string_mut(s)
+            } else {
+                MowStr::from_string(s)
+            }
+        })
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl ::proptest::arbitrary::Arbitrary for IOsStr {
+    type Parameters = <OsString as ::proptest::arbitrary::Arbitrary>::Parameters;
+    type Strategy = Map<StrategyFor<OsString>, fn(OsString) -> Self>;
+
+    fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+        any_with::<OsString>(args).prop_map(IOsStr::from_os_string)
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl ::proptest::arbitrary::Arbitrary for MowOsStr {
+    type Parameters = <OsString as ::proptest::arbitrary::Arbitrary>::Parameters;
+    type Strategy = Map<(StrategyFor<OsString>, StrategyFor<bool>), fn((OsString, bool)) -> Self>;
+
+    fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+        (any_with::<OsString>(args), any::<bool>()).prop_map(|(s, mutable)| {
+            if mutable {
+                MowOsStr::from_os_string_mut(s)
+            } else {
+                MowOsStr::from_os_string(s)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "arbitrary")]
+    fn test_arbitrary() {
+        let data: Vec<u8> = (0..=255).cycle().take(4096).collect();
+        let mut u = Unstructured::new(&data);
+        let (mut interned, mut mutable) = (false, false);
+        for _ in 0..64 {
+            let s = IStr::arbitrary(&mut u).unwrap();
+            let m = MowStr::arbitrary(&mut u).unwrap();
+            let o = MowOsStr::arbitrary(&mut u).unwrap();
+            assert_eq!(IStr::new(&*s), s);
+            interned |= m.is_interned() || o.is_interned();
+            mutable |= m.is_mutable() || o.is_mutable();
+            IOsStr::arbitrary(&mut u).unwrap();
+        }
+        assert!(interned && mutable);
+    }
+
+    #[cfg(feature = "proptest")]
+    ::proptest::proptest! {
+        #[test]
+        fn test_proptest(s: IStr, m: MowStr, o: IOsStr, mo: MowOsStr) {
+            ::proptest::prop_assert_eq!(IStr::new(&*s), s);
+            ::proptest::prop_assert!(m.is_interned() != m.is_mutable());
+            ::proptest::prop_assert_eq!(IOsStr::new(&*o), o);
+            ::proptest::prop_assert!(mo.is_interned() != mo.is_mutable());
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! Conversions with the `Atom`s of [string_cache](https://crates.io/crates/string_cache)
+//!
+//! Both are interned, a conversion looks the string up in the other interner
+
+use ::string_cache::{Atom, StaticAtomSet};
+
+use crate::{IStr, MowStr};
+
+impl<S: StaticAtomSet> From<&Atom<S>> for IStr {
+    #[inline]
+    fn from(atom: &Atom<S>) -> Self {
+        IStr::new(atom.as_str())
+    }
+}
+
+impl<S: StaticAtomSet> From<Atom<S>> for IStr {
+    #[inline]
+    fn from(atom: Atom<S>) -> Self {
+        IStr::from(&atom)
+    }
+}
+
+impl<S: StaticAtomSet> From<Atom<S>> for MowStr {
+    #[inline]
+    fn from(atom: Atom<S>) -> Self {
+        MowStr::new(atom.as_str())
+    }
+}
+
+impl<S: StaticAtomSet> From<&IStr> for Atom<S> {
+    #[inline]
+    fn from(s: &IStr) -> Self {
+        Atom::from(s.as_str())
+    }
+}
+
+impl<S: StaticAtomSet> From<IStr> for Atom<S> {
+    #[inline]
+    fn from(s: IStr) -> Self {
+        Atom::from(&s)
+    }
+}
+
+impl<S: StaticAtomSet> From<MowStr> for Atom<S> {
+    #[inline]
+    fn from(s: MowStr) -> Self {
+        Atom::from(s.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::string_cache::DefaultAtom;
+
+    #[test]
+    fn test_string_cache() {
+        let atom = DefaultAtom::from("string_cache atom to intern");
+        let s = IStr::from(&atom);
+        assert_eq!(IStr::from(atom.clone()).as_ptr(), s.as_ptr());
+        assert!(MowStr::from(atom.clone()).is_interned());
+        assert_eq!(DefaultAtom::from(s), atom);
+        assert_eq!(
+            DefaultAtom::from(MowStr::new("div")),
+            DefaultAtom::from("div")
+        );
+    }
+}
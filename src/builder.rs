@@ -0,0 +1,343 @@
+//! Building interned strings in place
+
+use std::{
+    cell::RefCell,
+    fmt,
+    fmt::Write,
+    hash::{BuildHasher, Hasher},
+    mem,
+};
+
+use crate::{
+    pool::{self, blocks::BLOCK, GlobalHasher},
+    IStr, MowStr,
+};
+
+/// Scratch buffers larger than this are released after use instead of being kept for the next call
+pub(crate) const MAX_KEPT_SCRATCH: usize = 1 << 16;
+
+thread_local! {
+    static SCRATCH: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Run `f` on the thread's scratch buffer, emptied, or on a new buffer if the scratch buffer is in use
+pub(crate) fn with_scratch_bytes<R>(f: impl FnOnce(&mut Vec<u8>) -> R) -> R {
+    SCRATCH.with(|scratch| match scratch.try_borrow_mut() {
+        Ok(mut buf) => {
+            buf.clear();
+            let r = f(&mut buf);
+            if buf.capacity() > MAX_KEPT_SCRATCH {
+                *buf = Vec::new();
+            }
+            r
+        }
+        // `f` calls back into something using the scratch buffer
+        Err(_) => f(&mut Vec::new()),
+    })
+}
+
+/// Fill the thread's scratch buffer and intern it, see [`with_scratch_bytes`]
+pub(crate) fn with_scratch(fill: impl FnOnce(&mut String)) -> IStr {
+    with_scratch_bytes(|bytes| {
+        // The buffer is empty, so this only moves it
+        let mut buf = String::from_utf8(mem::take(bytes)).unwrap_or_default();
+        fill(&mut buf);
+        let r = IStr::new(buf.as_str());
+        *bytes = buf.into_bytes();
+        r
+    })
+}
+
+impl IStr {
+    /// Create a `IStr` from format arguments, see [`iformat!`](crate::iformat)  
+    ///
+    /// Formats into a scratch buffer reused by every call on the same thread,
+    /// so a result already in the pool costs no allocation
+    pub fn from_fmt(args: fmt::Arguments<'_>) -> Self {
+        if let Some(s) = args.as_str() {
+            return Self::new(s);
+        }
+        with_scratch(|buf| {
+            buf.write_fmt(args)
+                .expect("a Display implementation returned an error")
+        })
+    }
+
+    /// Concatenate `parts` into one `IStr`, see [`from_fmt`](IStr::from_fmt)
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::IStr;
+    /// assert_eq!(IStr::concat(&["a", "b", "c"]), "abc");
+    /// ```
+    pub fn concat<I>(parts: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        with_scratch(|buf| {
+            for part in parts {
+                buf.push_str(part.as_ref());
+            }
+        })
+    }
+
+    /// Join `parts` with `sep` between each into one `IStr`, see [`from_fmt`](IStr::from_fmt)
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::IStr;
+    /// assert_eq!(IStr::join(", ", &["a", "b", "c"]), "a, b, c");
+    /// ```
+    pub fn join<I>(sep: &str, parts: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        with_scratch(|buf| {
+            for (i, part) in parts.into_iter().enumerate() {
+                if i != 0 {
+                    buf.push_str(sep);
+                }
+                buf.push_str(part.as_ref());
+            }
+        })
+    }
+
+    /// Create a `IStr` from the `Display` output of `v`, see [`from_fmt`](IStr::from_fmt)
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::IStr;
+    /// assert_eq!(IStr::from_display(&42), "42");
+    /// ```
+    #[inline]
+    pub fn from_display(v: &(impl fmt::Display + ?Sized)) -> Self {
+        Self::from_fmt(format_args!("{}", v))
+    }
+}
+
+impl MowStr {
+    /// Create a interned `MowStr` from the `Display` output of `v`, see [`IStr::from_display`]
+    #[inline]
+    pub fn from_display(v: &(impl fmt::Display + ?Sized)) -> Self {
+        IStr::from_display(v).into()
+    }
+}
+
+/// A buffer for building a `IStr` piece by piece, e.g. with `write!`  
+///
+/// The content is hashed as it is written, a block at a time, and looked up with that hash when built,
+/// so it is never hashed again and only copied if it isn't in the pool yet
+///
+/// # Example
+/// ```
+/// # use pstr::{IStr, IStrBuilder};
+/// use std::fmt::Write;
+/// let mut b = IStrBuilder::new();
+/// write!(b, "user:{}", 42).unwrap();
+/// assert_eq!(b.build(), IStr::new("user:42"));
+/// ```
+#[derive(Clone)]
+pub struct IStrBuilder {
+    buf: String,
+    /// Hash of the first `hashed` bytes of `buf`, which are whole blocks
+    hasher: <GlobalHasher as BuildHasher>::Hasher,
+    hashed: usize,
+    /// Id of the pool `hasher` belongs to, a test scope may change the pool before the content is built
+    pool: u64,
+}
+
+impl IStrBuilder {
+    /// New a empty builder
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// New a empty builder with space for at least `capacity` bytes
+    pub fn with_capacity(capacity: usize) -> Self {
+        let (hasher, pool) = pool::with_str_pool(|p| (p.piece_hasher(), p.id()));
+        Self {
+            buf: String::with_capacity(capacity),
+            hasher,
+            hashed: 0,
+            pool,
+        }
+    }
+
+    /// Append a string slice
+    #[inline]
+    pub fn push_str(&mut self, s: &str) {
+        self.buf.push_str(s);
+        self.hash_blocks();
+    }
+
+    /// Append a char
+    #[inline]
+    pub fn push(&mut self, c: char) {
+        self.buf.push(c);
+        self.hash_blocks();
+    }
+
+    /// Feed the whole blocks written since the last call to the hasher
+    #[inline]
+    fn hash_blocks(&mut self) {
+        while self.buf.len() - self.hashed >= BLOCK {
+            self.hasher
+                .write(&self.buf.as_bytes()[self.hashed..self.hashed + BLOCK]);
+            self.hashed += BLOCK;
+        }
+    }
+
+    /// Finish the hash of the content with the rest after the last block
+    fn finish_hash(&self, mut hasher: impl Hasher) -> u64 {
+        let rest = &self.buf.as_bytes()[self.hashed..];
+        // A `str` hashes as its bytes followed by 0xff
+        let mut tail = [0xff; BLOCK];
+        tail[..rest.len()].copy_from_slice(rest);
+        hasher.write(&tail[..=rest.len()]);
+        hasher.finish()
+    }
+
+    /// Get the hash the content is interned with, equal to the [`precomputed_hash`](IStr::precomputed_hash) of the result
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::{IStr, IStrBuilder};
+    /// let mut b = IStrBuilder::new();
+    /// b.push_str("precomputed ");
+    /// b.push_str("hash of the builder");
+    /// assert_eq!(b.precomputed_hash(), IStr::new(b.as_str()).precomputed_hash());
+    /// ```
+    pub fn precomputed_hash(&self) -> u64 {
+        self.finish_hash(self.hasher.clone())
+    }
+
+    /// Get the content built so far
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.buf
+    }
+
+    /// Intern the content
+    #[inline]
+    pub fn build(mut self) -> IStr {
+        self.take()
+    }
+
+    /// Intern the content and clear the builder, keeping its buffer for reuse
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::IStrBuilder;
+    /// use std::fmt::Write;
+    /// let mut b = IStrBuilder::new();
+    /// let keys: Vec<_> = (0..3)
+    ///     .map(|i| {
+    ///         write!(b, "key{}", i).unwrap();
+    ///         b.take()
+    ///     })
+    ///     .collect();
+    /// assert_eq!(keys, ["key0", "key1", "key2"]);
+    /// ```
+    pub fn take(&mut self) -> IStr {
+        let r = pool::with_str_pool(|p| {
+            let hasher = mem::replace(&mut self.hasher, p.piece_hasher());
+            let r = if p.id() == self.pool {
+                IStr::from_prehashed(&self.buf, p, || self.finish_hash(hasher))
+            } else {
+                IStr::new(&self.buf)
+            };
+            self.pool = p.id();
+            r
+        });
+        self.buf.clear();
+        self.hashed = 0;
+        r
+    }
+}
+
+impl Default for IStrBuilder {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for IStrBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IStrBuilder")
+            .field("buf", &self.buf)
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Write for IStrBuilder {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.push(c);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_display() {
+        let a = IStr::from_display(&1.5);
+        assert_eq!(a, "1.5");
+        let b = MowStr::from_display(&'x');
+        assert!(b.is_interned());
+        assert_eq!(b, "x");
+    }
+
+    #[test]
+    fn test_builder_hash() {
+        let s = "the builder hashes its content as it is written, a block at a time";
+        let expected = IStr::new(s);
+        for split in [1, 5, 31, 32, 33, 64, s.len()] {
+            let mut b = IStrBuilder::new();
+            for piece in s.as_bytes().chunks(split) {
+                b.push_str(std::str::from_utf8(piece).unwrap());
+            }
+            assert_eq!(b.precomputed_hash(), expected.precomputed_hash());
+            let a = b.take();
+            assert_eq!(a.as_ptr(), expected.as_ptr());
+            assert_eq!(a.precomputed_hash(), expected.precomputed_hash());
+        }
+        let mut b = IStrBuilder::new();
+        write!(b, "short:{}", 1).unwrap();
+        assert_eq!(
+            b.precomputed_hash(),
+            IStr::new("short:1").precomputed_hash()
+        );
+        assert_eq!(b.take(), "short:1");
+        b.push('x');
+        assert_eq!(b.build(), "x");
+    }
+
+    #[test]
+    fn test_builder_scope_change() {
+        let s = "the pool of the builder changed before it was built";
+        let mut b = {
+            let _scope = pool::test_scope();
+            let mut b = IStrBuilder::new();
+            b.push_str(s);
+            b
+        };
+        // Made right after the first one is freed, likely at the same address
+        let scope = pool::test_scope();
+        let a = b.take();
+        assert_eq!(a.precomputed_hash(), scope.hash(s));
+        assert_eq!(scope.get(s).unwrap().get().as_ptr(), a.as_ptr());
+    }
+}
@@ -0,0 +1,135 @@
+//! C API sharing the global string pool with C and C++
+//!
+//! A handle keeps its string interned until it is released, strings are not NUL terminated,
+//! use [`pstr_len`] to get the length in bytes
+//!
+//! Every handle points into the pool, so handles of equal strings are equal pointers.
+//! A Rust [`IStr`](crate::IStr) stores strings of up to 7 bytes in place instead of in the pool,
+//! only for longer strings it shares the bytes returned by [`pstr_resolve`]
+//!
+//! ```c
+//! typedef struct PstrHandle PstrHandle;
+//!
+//! /* Handles of equal strings are equal, also for short strings.
+//!    A Rust IStr of a string of up to 7 bytes doesn't share its bytes with the handle */
+//! const PstrHandle *pstr_intern(const char *ptr, size_t len);
+//! const PstrHandle *pstr_clone(const PstrHandle *handle);
+//! const char *pstr_resolve(const PstrHandle *handle);
+//! size_t pstr_len(const PstrHandle *handle);
+//! void pstr_release(const PstrHandle *handle);
+//! ```
+
+use std::{mem::ManuallyDrop, os::raw::c_char, slice, str};
+
+use crate::pool::{Intern, STR_POOL};
+
+/// Opaque handle to a string in the global string pool
+#[repr(C)]
+pub struct PstrHandle {
+    _private: [u8; 0],
+}
+
+/// Borrow the intern behind `handle` without consuming it
+#[inline]
+unsafe fn borrow(handle: *const PstrHandle) -> ManuallyDrop<Intern<str>> {
+    ManuallyDrop::new(Intern::from_raw(handle as *const ()))
+}
+
+/// Intern the `len` bytes at `ptr`, return null if they are not valid UTF-8  
+/// The string is always put into the pool, also if a `IStr` would store it inline
+///
+/// # Safety
+/// `ptr` must be valid for reads of `len` bytes
+#[no_mangle]
+pub unsafe extern "C" fn pstr_intern(ptr: *const c_char, len: usize) -> *const PstrHandle {
+    let bytes = if len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(ptr as *const u8, len)
+    };
+    match str::from_utf8(bytes) {
+        Ok(s) => STR_POOL.intern(s).into_raw() as *const PstrHandle,
+        Err(_) => std::ptr::null(),
+    }
+}
+
+/// Get a new handle to the same string, which must be released on its own
+///
+/// # Safety
+/// `handle` must be a live handle returned by this API
+#[no_mangle]
+pub unsafe extern "C" fn pstr_clone(handle: *const PstrHandle) -> *const PstrHandle {
+    Intern::clone(&borrow(handle)).into_raw() as *const PstrHandle
+}
+
+/// Get the bytes of the string, valid until `handle` is released
+///
+/// # Safety
+/// `handle` must be a live handle returned by this API
+#[no_mangle]
+pub unsafe extern "C" fn pstr_resolve(handle: *const PstrHandle) -> *const c_char {
+    borrow(handle).get().as_ptr() as *const c_char
+}
+
+/// Get the length of the string in bytes
+///
+/// # Safety
+/// `handle` must be a live handle returned by this API
+#[no_mangle]
+pub unsafe extern "C" fn pstr_len(handle: *const PstrHandle) -> usize {
+    borrow(handle).get().len()
+}
+
+/// Release a handle, null is ignored
+///
+/// # Safety
+/// `handle` must be null or a live handle returned by this API, and is dead afterwards
+#[no_mangle]
+pub unsafe extern "C" fn pstr_release(handle: *const PstrHandle) {
+    if handle.is_null() {
+        return;
+    }
+    let intern = Intern::<str>::from_raw(handle as *const ());
+    STR_POOL.on_drop(&intern);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IStr;
+
+    #[test]
+    fn test_capi() {
+        unsafe {
+            let s = "shared with c";
+            let a = pstr_intern(s.as_ptr() as *const c_char, s.len());
+            let b = pstr_clone(a);
+            assert_eq!(pstr_resolve(a), pstr_resolve(b));
+            let bytes = slice::from_raw_parts(pstr_resolve(b) as *const u8, pstr_len(b));
+            assert_eq!(bytes, s.as_bytes());
+            pstr_release(a);
+            pstr_release(b);
+            assert!(pstr_intern([0xffu8].as_ptr() as *const c_char, 1).is_null());
+            pstr_release(pstr_intern(std::ptr::null(), 0));
+        }
+    }
+
+    #[test]
+    fn test_capi_identity() {
+        unsafe {
+            let short = "short";
+            let a = pstr_intern(short.as_ptr() as *const c_char, short.len());
+            let b = pstr_intern(short.as_ptr() as *const c_char, short.len());
+            assert_eq!(a, b);
+            assert!(STR_POOL.contains(short));
+            assert_ne!(pstr_resolve(a) as *const u8, IStr::new(short).as_ptr());
+            pstr_release(a);
+            pstr_release(b);
+
+            let long = "shared between c and rust";
+            let c = pstr_intern(long.as_ptr() as *const c_char, long.len());
+            assert_eq!(pstr_resolve(c) as *const u8, IStr::new(long).as_ptr());
+            pstr_release(c);
+        }
+    }
+}
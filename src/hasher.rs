@@ -0,0 +1,171 @@
+//! Identity hashing for interned strings
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    hash::{self, BuildHasherDefault, Hash, Hasher},
+    ops::Deref,
+};
+
+use crate::IStr;
+
+/// A hasher passing through the hash precomputed by the pool
+///
+/// Equal `IStr`s share a pointer and are compared by it, so there is nothing to hash at lookup time,
+/// [`IStrKey`] writes the precomputed hash as a single `u64` and this hasher just returns it
+///
+/// Other keys still work, their bytes are folded into the state, but without any real mixing
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IdentityHasher {
+    hash: u64,
+}
+
+impl Hasher for IdentityHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.hash = self.hash.rotate_left(8) ^ u64::from(*b);
+        }
+    }
+
+    #[inline]
+    fn write_u64(&mut self, n: u64) {
+        self.hash ^= n;
+    }
+}
+
+/// `BuildHasher` for [`IdentityHasher`]
+pub type BuildIdentityHasher = BuildHasherDefault<IdentityHasher>;
+
+/// A `IStr` hashed by its precomputed hash, the key of [`IStrMap`] and [`IStrSet`]
+///
+/// `IStr` itself hashes its contents like `str` so it can borrow as `&str`,
+/// this key hashes in O(1) regardless of length but can only be looked up by another `IStrKey`
+///
+/// # Example
+/// ```
+/// # use pstr::{IStr, IStrKey};
+/// let s = IStr::new("key");
+/// assert_eq!(IStrKey::from_ref(&s), &IStrKey::new(s.clone()));
+/// assert_eq!(IStrKey::from(s).into_inner(), "key");
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct IStrKey(IStr);
+
+impl IStrKey {
+    /// Create a `IStrKey` from `IStr`
+    #[inline]
+    pub fn new(s: IStr) -> Self {
+        Self(s)
+    }
+
+    /// View a `&IStr` as a `&IStrKey` without cloning, for lookups
+    #[inline]
+    pub fn from_ref(s: &IStr) -> &Self {
+        // SAFETY: `IStrKey` is a transparent wrapper of `IStr`
+        unsafe { &*(s as *const IStr as *const Self) }
+    }
+
+    /// Get the `IStr`
+    #[inline]
+    pub fn into_inner(self) -> IStr {
+        self.0
+    }
+}
+
+impl Hash for IStrKey {
+    /// Writes the precomputed hash
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        state.write_u64(self.0.precomputed_hash())
+    }
+}
+
+impl Deref for IStrKey {
+    type Target = IStr;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<IStr> for IStrKey {
+    #[inline]
+    fn from(s: IStr) -> Self {
+        Self(s)
+    }
+}
+
+impl From<IStrKey> for IStr {
+    #[inline]
+    fn from(k: IStrKey) -> Self {
+        k.0
+    }
+}
+
+impl fmt::Debug for IStrKey {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for IStrKey {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// A `HashMap` keyed by [`IStrKey`], using [`IdentityHasher`]
+///
+/// # Example
+/// ```
+/// # use pstr::{IStr, IStrKey, IStrMap};
+/// let mut map = IStrMap::default();
+/// map.insert(IStr::new("x").into(), 1);
+/// assert_eq!(map[IStrKey::from_ref(&IStr::new("x"))], 1);
+/// ```
+pub type IStrMap<V> = HashMap<IStrKey, V, BuildIdentityHasher>;
+
+/// A `HashSet` of [`IStrKey`], using [`IdentityHasher`]
+///
+/// # Example
+/// ```
+/// # use pstr::{IStr, IStrKey, IStrSet};
+/// let mut set = IStrSet::default();
+/// set.insert(IStr::new("x").into());
+/// assert!(set.contains(IStrKey::from_ref(&IStr::new("x"))));
+/// ```
+pub type IStrSet = HashSet<IStrKey, BuildIdentityHasher>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hash::BuildHasher;
+
+    #[test]
+    fn test_identity_hash() {
+        let s = IStr::new("identity_hash");
+        assert_eq!(
+            BuildIdentityHasher::default().hash_one(IStrKey::from_ref(&s)),
+            s.precomputed_hash()
+        );
+
+        let mut map = IStrMap::default();
+        for i in 0..100 {
+            map.insert(IStr::from_string(i.to_string()).into(), i);
+        }
+        for i in 0..100 {
+            let key = IStrKey::new(IStr::from_string(i.to_string()));
+            assert_eq!(map[&key], i);
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//! Heap usage for [get-size](https://crates.io/crates/get-size)
+//!
+//! Interned entries are owned by their pool, so they count towards the pool and never towards a handle
+
+use std::hash::{BuildHasher, Hash};
+
+use ::get_size::GetSize;
+
+use crate::{
+    pool::{Pointee, Pool},
+    IStr, MowStr,
+};
+
+impl GetSize for IStr {
+    /// Always 0, the entry belongs to the pool
+    #[inline]
+    fn get_heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl GetSize for MowStr {
+    /// The capacity of the mutable `String`, a interned or inline string uses no heap of its own
+    #[inline]
+    fn get_heap_size(&self) -> usize {
+        self.try_string().map_or(0, String::capacity)
+    }
+}
+
+impl<T: Eq + Hash + ?Sized + Pointee, S: BuildHasher + Clone> GetSize for Pool<T, S> {
+    /// See [`Pool::heap_bytes`]
+    #[inline]
+    fn get_heap_size(&self) -> usize {
+        self.heap_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_size() {
+        let s = IStr::new("heap size of a handle");
+        assert_eq!(s.get_size(), std::mem::size_of::<IStr>());
+        let mut m = MowStr::new("heap size of a mutable string");
+        assert_eq!(m.get_heap_size(), 0);
+        m.mutdown();
+        assert!(m.get_heap_size() >= m.len());
+        let pool = Pool::<str>::new();
+        pool.intern("x".repeat(100));
+        assert!(pool.get_heap_size() >= 100);
+    }
+}
@@ -0,0 +1,173 @@
+//! Conversions with the header and method types of [http](https://crates.io/crates/http)
+//!
+//! `HeaderName` and `Method` always convert to `IStr`, a `HeaderValue` only if it is visible ASCII.
+//! Proxies seeing the same headers over and over can keep them with [`get_interned`] and [`get_all_interned`]
+
+use std::convert::TryFrom;
+
+use ::http::{
+    header::{AsHeaderName, InvalidHeaderName, InvalidHeaderValue, ToStrError},
+    method::InvalidMethod,
+    HeaderMap, HeaderName, HeaderValue, Method,
+};
+
+use crate::IStr;
+
+/// Get the value of `key` in `headers` as a interned string  
+/// `None` if there is no such header or its value isn't visible ASCII
+///
+/// # Example
+/// ```
+/// # use pstr::IStr;
+/// # use http::{header::CONTENT_TYPE, HeaderMap, HeaderValue};
+/// let mut headers = HeaderMap::new();
+/// headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+/// let v = pstr::http::get_interned(&headers, CONTENT_TYPE);
+/// assert_eq!(v, Some(IStr::new("application/json")));
+/// ```
+#[inline]
+pub fn get_interned<K: AsHeaderName>(headers: &HeaderMap, key: K) -> Option<IStr> {
+    headers.get(key).and_then(|v| IStr::try_from(v).ok())
+}
+
+/// Get all values of `key` in `headers` as interned strings, skipping values that aren't visible ASCII
+#[inline]
+pub fn get_all_interned<K: AsHeaderName>(
+    headers: &HeaderMap,
+    key: K,
+) -> impl Iterator<Item = IStr> + '_ {
+    headers
+        .get_all(key)
+        .into_iter()
+        .filter_map(|v| IStr::try_from(v).ok())
+}
+
+impl From<&HeaderName> for IStr {
+    #[inline]
+    fn from(name: &HeaderName) -> Self {
+        IStr::new(name.as_str())
+    }
+}
+
+impl From<HeaderName> for IStr {
+    #[inline]
+    fn from(name: HeaderName) -> Self {
+        IStr::from(&name)
+    }
+}
+
+impl TryFrom<&HeaderValue> for IStr {
+    type Error = ToStrError;
+
+    /// Fails if the value isn't visible ASCII
+    #[inline]
+    fn try_from(value: &HeaderValue) -> Result<Self, Self::Error> {
+        value.to_str().map(IStr::new)
+    }
+}
+
+impl TryFrom<HeaderValue> for IStr {
+    type Error = ToStrError;
+
+    /// Fails if the value isn't visible ASCII
+    #[inline]
+    fn try_from(value: HeaderValue) -> Result<Self, Self::Error> {
+        IStr::try_from(&value)
+    }
+}
+
+impl From<&Method> for IStr {
+    #[inline]
+    fn from(method: &Method) -> Self {
+        IStr::new(method.as_str())
+    }
+}
+
+impl From<Method> for IStr {
+    #[inline]
+    fn from(method: Method) -> Self {
+        IStr::from(&method)
+    }
+}
+
+impl TryFrom<&IStr> for HeaderName {
+    type Error = InvalidHeaderName;
+
+    #[inline]
+    fn try_from(s: &IStr) -> Result<Self, Self::Error> {
+        HeaderName::from_bytes(s.as_bytes())
+    }
+}
+
+impl TryFrom<IStr> for HeaderName {
+    type Error = InvalidHeaderName;
+
+    #[inline]
+    fn try_from(s: IStr) -> Result<Self, Self::Error> {
+        HeaderName::try_from(&s)
+    }
+}
+
+impl TryFrom<&IStr> for HeaderValue {
+    type Error = InvalidHeaderValue;
+
+    #[inline]
+    fn try_from(s: &IStr) -> Result<Self, Self::Error> {
+        HeaderValue::from_str(s)
+    }
+}
+
+impl TryFrom<IStr> for HeaderValue {
+    type Error = InvalidHeaderValue;
+
+    #[inline]
+    fn try_from(s: IStr) -> Result<Self, Self::Error> {
+        HeaderValue::try_from(&s)
+    }
+}
+
+impl TryFrom<&IStr> for Method {
+    type Error = InvalidMethod;
+
+    #[inline]
+    fn try_from(s: &IStr) -> Result<Self, Self::Error> {
+        Method::from_bytes(s.as_bytes())
+    }
+}
+
+impl TryFrom<IStr> for Method {
+    type Error = InvalidMethod;
+
+    #[inline]
+    fn try_from(s: IStr) -> Result<Self, Self::Error> {
+        Method::try_from(&s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::http::header::{ACCEPT, VARY};
+
+    #[test]
+    fn test_http() {
+        let name = HeaderName::try_from(IStr::new("x-request-id")).unwrap();
+        assert_eq!(IStr::from(&name), "x-request-id");
+        assert!(HeaderName::try_from(IStr::new("bad name")).is_err());
+        assert!(HeaderValue::try_from(IStr::new("a\nb")).is_err());
+        assert_eq!(Method::try_from(IStr::new("GET")).unwrap(), Method::GET);
+        assert_eq!(IStr::from(Method::PATCH), "PATCH");
+
+        let mut headers = HeaderMap::new();
+        headers.append(VARY, HeaderValue::from_static("accept-encoding"));
+        headers.append(VARY, HeaderValue::from_bytes(b"\xff").unwrap());
+        headers.append(VARY, HeaderValue::try_from(IStr::new("origin")).unwrap());
+        let vary: Vec<IStr> = get_all_interned(&headers, VARY).collect();
+        assert_eq!(vary, ["accept-encoding", "origin"]);
+        assert_eq!(
+            get_interned(&headers, VARY),
+            Some(IStr::new("accept-encoding"))
+        );
+        assert_eq!(get_interned(&headers, ACCEPT), None);
+    }
+}
@@ -0,0 +1,296 @@
+use std::{
+    borrow::{Borrow, Cow},
+    hash::{self, Hash},
+    iter::FromIterator,
+    ops::Deref,
+    rc::Rc,
+    sync::Arc,
+};
+
+use crate::{
+    bytes::MowBytes,
+    intern::Interned,
+    pool::{Intern, BYTES_POOL},
+};
+
+/// Immutable Interning Bytes
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct IBytes(Intern<[u8]>);
+
+impl IBytes {
+    /// Create a `IBytes` from byte slice  
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::bytes::IBytes;
+    /// let b = IBytes::new(b"\x00\x01\x02");
+    /// ```
+    #[inline]
+    pub fn new(s: impl AsRef<[u8]>) -> Self {
+        Self(BYTES_POOL.intern(s.as_ref()))
+    }
+
+    /// Create a `IBytes` from `Vec<u8>`  
+    #[inline]
+    pub fn from_vec(s: Vec<u8>) -> Self {
+        Self(BYTES_POOL.intern(s))
+    }
+
+    /// Create a `IBytes` from `Box<[u8]>`  
+    #[inline]
+    pub fn from_boxed(s: Box<[u8]>) -> Self {
+        Self(BYTES_POOL.intern(s))
+    }
+
+    /// Create a `IBytes` from `Arc<[u8]>`  
+    #[inline]
+    pub fn from_arc(s: Arc<[u8]>) -> Self {
+        Self(BYTES_POOL.intern(s))
+    }
+
+    /// Create a `IBytes` from `Rc<[u8]>`  
+    #[inline]
+    pub fn from_rc(s: Rc<[u8]>) -> Self {
+        Self(BYTES_POOL.intern(s))
+    }
+
+    /// Create a `IBytes` from `MowBytes`  
+    #[inline]
+    pub fn from_mow(s: MowBytes) -> Self {
+        s.into()
+    }
+
+    /// Create a `IBytes` from custom fn  
+    /// Entries are copied into the pool, so `to_arc` is never called
+    #[deprecated(note = "`to_arc` is never called, entries are copied into the pool, use `new`")]
+    #[inline]
+    pub fn from_to_arc<S: AsRef<[u8]>>(s: S, _to_arc: impl FnOnce(S) -> Arc<[u8]>) -> Self {
+        Self(BYTES_POOL.intern(s))
+    }
+}
+
+impl IBytes {
+    /// Extracts a byte slice containing the entire `IBytes`
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.deref()
+    }
+
+    /// Clone a boxed byte slice containing the entire `IBytes`
+    #[inline]
+    pub fn into_boxed_bytes(&self) -> Box<[u8]> {
+        self.deref().into()
+    }
+
+    /// Convert to `MowBytes`  
+    #[inline]
+    pub fn into_mut(&self) -> MowBytes {
+        MowBytes::from(self.clone())
+    }
+}
+
+unsafe impl Interned for IBytes {}
+
+impl Drop for IBytes {
+    #[inline]
+    fn drop(&mut self) {
+        BYTES_POOL.on_drop(&self.0)
+    }
+}
+
+impl Deref for IBytes {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_ref()
+    }
+}
+
+impl AsRef<[u8]> for IBytes {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.0.get()
+    }
+}
+
+impl Hash for IBytes {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.deref().hash(state)
+    }
+}
+
+impl Borrow<[u8]> for IBytes {
+    #[inline]
+    fn borrow(&self) -> &[u8] {
+        self.deref()
+    }
+}
+
+impl From<&'_ [u8]> for IBytes {
+    #[inline]
+    fn from(s: &[u8]) -> Self {
+        Self::new(s)
+    }
+}
+
+impl<const N: usize> From<&'_ [u8; N]> for IBytes {
+    #[inline]
+    fn from(s: &[u8; N]) -> Self {
+        Self::new(s)
+    }
+}
+
+impl From<Vec<u8>> for IBytes {
+    #[inline]
+    fn from(s: Vec<u8>) -> Self {
+        Self::from_vec(s)
+    }
+}
+
+impl From<&'_ Vec<u8>> for IBytes {
+    #[inline]
+    fn from(s: &Vec<u8>) -> Self {
+        Self::new(s)
+    }
+}
+
+impl From<&'_ str> for IBytes {
+    #[inline]
+    fn from(s: &str) -> Self {
+        Self::new(s)
+    }
+}
+
+impl From<String> for IBytes {
+    #[inline]
+    fn from(s: String) -> Self {
+        Self::from_vec(s.into_bytes())
+    }
+}
+
+impl From<Box<[u8]>> for IBytes {
+    #[inline]
+    fn from(s: Box<[u8]>) -> Self {
+        Self::from_boxed(s)
+    }
+}
+
+impl From<Arc<[u8]>> for IBytes {
+    #[inline]
+    fn from(s: Arc<[u8]>) -> Self {
+        Self::from_arc(s)
+    }
+}
+
+impl From<Rc<[u8]>> for IBytes {
+    #[inline]
+    fn from(s: Rc<[u8]>) -> Self {
+        Self::from_rc(s)
+    }
+}
+
+impl<'a> From<Cow<'a, [u8]>> for IBytes {
+    #[inline]
+    fn from(s: Cow<'a, [u8]>) -> Self {
+        match s {
+            Cow::Borrowed(v) => Self::new(v),
+            Cow::Owned(v) => Self::from_vec(v),
+        }
+    }
+}
+
+impl FromIterator<u8> for IBytes {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = u8>>(iter: T) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
+
+impl<'a> FromIterator<&'a u8> for IBytes {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = &'a u8>>(iter: T) -> Self {
+        Self::from_vec(iter.into_iter().copied().collect())
+    }
+}
+
+impl From<IBytes> for Vec<u8> {
+    #[inline]
+    fn from(v: IBytes) -> Self {
+        v.to_vec()
+    }
+}
+
+impl From<IBytes> for Box<[u8]> {
+    #[inline]
+    fn from(v: IBytes) -> Self {
+        v.deref().into()
+    }
+}
+
+impl From<IBytes> for Arc<[u8]> {
+    #[inline]
+    fn from(v: IBytes) -> Self {
+        v.0.clone().into()
+    }
+}
+
+impl From<IBytes> for Rc<[u8]> {
+    #[inline]
+    fn from(v: IBytes) -> Self {
+        v.deref().into()
+    }
+}
+
+impl<'a> From<&'a IBytes> for Cow<'a, [u8]> {
+    #[inline]
+    fn from(v: &'a IBytes) -> Self {
+        Cow::Borrowed(v.deref())
+    }
+}
+
+impl PartialEq<[u8]> for IBytes {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.deref() == other
+    }
+}
+
+impl PartialEq<&[u8]> for IBytes {
+    fn eq(&self, other: &&[u8]) -> bool {
+        self.deref() == *other
+    }
+}
+
+impl<const N: usize> PartialEq<[u8; N]> for IBytes {
+    fn eq(&self, other: &[u8; N]) -> bool {
+        self.deref() == other
+    }
+}
+
+impl<const N: usize> PartialEq<&[u8; N]> for IBytes {
+    fn eq(&self, other: &&[u8; N]) -> bool {
+        self.deref() == *other
+    }
+}
+
+impl PartialEq<Vec<u8>> for IBytes {
+    fn eq(&self, other: &Vec<u8>) -> bool {
+        self.deref() == other.as_slice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytes() {
+        let a = IBytes::new(b"\xff\x00");
+        let b = IBytes::from_vec(vec![0xff, 0]);
+        let c = IBytes::new(b"\x00");
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(a, b"\xff\x00");
+    }
+}
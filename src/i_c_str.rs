@@ -0,0 +1,253 @@
+use std::{
+    borrow::{Borrow, Cow},
+    ffi::{CStr, CString},
+    hash::{self, Hash},
+    ops::Deref,
+    os::raw::c_char,
+    rc::Rc,
+    sync::Arc,
+};
+
+use crate::{
+    intern::Interned,
+    mow_c_str::MowCStr,
+    pool::{Intern, C_STR_POOL},
+};
+
+/// Immutable Interning CString  
+///
+/// The pointer returned by [`as_ptr`](ICStr::as_ptr) stays valid and stable as long as any handle to the entry is alive
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ICStr(Intern<CStr>);
+
+impl ICStr {
+    /// Create a `ICStr` from CStr slice  
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::ffi::ICStr;
+    /// # use std::ffi::CString;
+    /// let s = ICStr::new(CString::new("hello world").unwrap());
+    /// ```
+    #[inline]
+    pub fn new(s: impl AsRef<CStr>) -> Self {
+        Self(C_STR_POOL.intern(s.as_ref()))
+    }
+
+    /// Create a `ICStr` from `CString`  
+    #[inline]
+    pub fn from_c_string(s: CString) -> Self {
+        Self(C_STR_POOL.intern(s))
+    }
+
+    /// Create a `ICStr` from `Box<CStr>`  
+    #[inline]
+    pub fn from_boxed(s: Box<CStr>) -> Self {
+        Self(C_STR_POOL.intern(s))
+    }
+
+    /// Create a `ICStr` from `Arc<CStr>`  
+    #[inline]
+    pub fn from_arc(s: Arc<CStr>) -> Self {
+        Self(C_STR_POOL.intern(s))
+    }
+
+    /// Create a `ICStr` from `Rc<CStr>`  
+    #[inline]
+    pub fn from_rc(s: Rc<CStr>) -> Self {
+        Self(C_STR_POOL.intern(s))
+    }
+
+    /// Create a `ICStr` from `MowCStr`
+    #[inline]
+    pub fn from_mow(s: MowCStr) -> Self {
+        s.into()
+    }
+
+    /// Create a `ICStr` from custom fn  
+    /// Entries are copied into the pool, so `to_arc` is never called
+    #[deprecated(note = "`to_arc` is never called, entries are copied into the pool, use `new`")]
+    #[inline]
+    pub fn from_to_arc<S: AsRef<CStr>>(s: S, _to_arc: impl FnOnce(S) -> Arc<CStr>) -> Self {
+        Self(C_STR_POOL.intern(s))
+    }
+}
+
+impl ICStr {
+    /// Converts to a `CStr` slice.
+    #[inline]
+    pub fn as_c_str(&self) -> &CStr {
+        self.deref()
+    }
+
+    /// Returns the inner pointer to this C string  
+    ///
+    /// Equal strings share the same pointer, it is valid as long as any handle to the entry is alive
+    #[inline]
+    pub fn as_ptr(&self) -> *const c_char {
+        self.deref().as_ptr()
+    }
+
+    /// Converts to an `Box<CStr>`.
+    #[inline]
+    pub fn into_boxed_c_str(&self) -> Box<CStr> {
+        self.deref().into()
+    }
+
+    /// Convert to `MowCStr`
+    #[inline]
+    pub fn into_mut(&self) -> MowCStr {
+        MowCStr::from(self.clone())
+    }
+}
+
+unsafe impl Interned for ICStr {}
+
+impl Drop for ICStr {
+    #[inline]
+    fn drop(&mut self) {
+        C_STR_POOL.on_drop(&self.0)
+    }
+}
+
+impl Deref for ICStr {
+    type Target = CStr;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_ref()
+    }
+}
+
+impl AsRef<CStr> for ICStr {
+    #[inline]
+    fn as_ref(&self) -> &CStr {
+        self.0.get()
+    }
+}
+
+impl Hash for ICStr {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.deref().hash(state)
+    }
+}
+
+impl Borrow<CStr> for ICStr {
+    #[inline]
+    fn borrow(&self) -> &CStr {
+        self.deref()
+    }
+}
+
+impl From<&'_ CStr> for ICStr {
+    #[inline]
+    fn from(s: &CStr) -> Self {
+        Self::new(s)
+    }
+}
+
+impl From<CString> for ICStr {
+    #[inline]
+    fn from(s: CString) -> Self {
+        Self::from_c_string(s)
+    }
+}
+
+impl From<&'_ CString> for ICStr {
+    #[inline]
+    fn from(s: &CString) -> Self {
+        Self::new(s)
+    }
+}
+
+impl From<Box<CStr>> for ICStr {
+    #[inline]
+    fn from(s: Box<CStr>) -> Self {
+        Self::from_boxed(s)
+    }
+}
+
+impl From<Arc<CStr>> for ICStr {
+    #[inline]
+    fn from(s: Arc<CStr>) -> Self {
+        Self::from_arc(s)
+    }
+}
+
+impl From<Rc<CStr>> for ICStr {
+    #[inline]
+    fn from(s: Rc<CStr>) -> Self {
+        Self::from_rc(s)
+    }
+}
+
+impl<'a> From<Cow<'a, CStr>> for ICStr {
+    #[inline]
+    fn from(s: Cow<'a, CStr>) -> Self {
+        match s {
+            Cow::Borrowed(v) => Self::new(v),
+            Cow::Owned(v) => Self::from_c_string(v),
+        }
+    }
+}
+
+impl From<ICStr> for CString {
+    #[inline]
+    fn from(v: ICStr) -> Self {
+        v.deref().into()
+    }
+}
+
+impl From<ICStr> for Box<CStr> {
+    #[inline]
+    fn from(v: ICStr) -> Self {
+        v.deref().into()
+    }
+}
+
+impl From<ICStr> for Arc<CStr> {
+    #[inline]
+    fn from(v: ICStr) -> Self {
+        v.0.clone().into()
+    }
+}
+
+impl From<ICStr> for Rc<CStr> {
+    #[inline]
+    fn from(v: ICStr) -> Self {
+        v.deref().into()
+    }
+}
+
+impl PartialEq<CStr> for ICStr {
+    fn eq(&self, other: &CStr) -> bool {
+        self.deref() == other
+    }
+}
+
+impl PartialEq<&CStr> for ICStr {
+    fn eq(&self, other: &&CStr) -> bool {
+        self.deref() == *other
+    }
+}
+
+impl PartialEq<CString> for ICStr {
+    fn eq(&self, other: &CString) -> bool {
+        self.deref() == other.as_c_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ptr() {
+        let a = ICStr::new(CString::new("asd").unwrap());
+        let b = ICStr::from_c_string(CString::new("asd").unwrap());
+        assert_eq!(a, b);
+        assert_eq!(a.as_ptr(), b.as_ptr());
+        assert_eq!(unsafe { CStr::from_ptr(a.as_ptr()) }.to_bytes(), b"asd");
+    }
+}
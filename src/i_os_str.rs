@@ -1,14 +1,34 @@
 use std::{
-    borrow::Borrow, convert::identity, convert::Infallible, ffi::OsStr, ffi::OsString, hash,
-    hash::Hash, ops::Deref, path::Path, path::PathBuf, rc::Rc, str::FromStr, sync::Arc,
+    borrow::Borrow, borrow::Cow, convert::Infallible, ffi::OsStr, ffi::OsString, fmt, hash,
+    hash::Hash, iter::FromIterator, ops::Deref, path::Path, path::PathBuf, rc::Rc, str::FromStr,
+    sync::Arc,
 };
 
+#[cfg(unix)]
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+#[cfg(windows)]
+use std::os::windows::ffi::{EncodeWide, OsStrExt, OsStringExt};
+
+#[cfg(not(feature = "single-thread"))]
+use once_cell::sync::Lazy;
+
 use crate::{
     intern::Interned,
     mow_os_str::MowOsStr,
     pool::{Intern, OS_STR_POOL},
+    IStr,
 };
 
+/// The shared empty entry, the static keeps it alive so it is never collected
+#[cfg(not(feature = "single-thread"))]
+static EMPTY: Lazy<IOsStr> = Lazy::new(|| IOsStr::new(""));
+
+#[cfg(feature = "single-thread")]
+thread_local! {
+    /// The shared empty entry of the pool of this thread, leaked so it is never collected
+    static EMPTY: &'static IOsStr = Box::leak(Box::new(IOsStr::new("")));
+}
+
 /// Immutable Interning OsString
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub struct IOsStr(Intern<OsStr>);
@@ -23,31 +43,31 @@ impl IOsStr {
     /// ```
     #[inline]
     pub fn new(s: impl AsRef<OsStr>) -> Self {
-        Self(OS_STR_POOL.intern(s.as_ref(), Arc::from))
+        Self(OS_STR_POOL.intern(s.as_ref()))
     }
 
     /// Create a `IOsStr` from `OsString`  
     #[inline]
     pub fn from_os_string(s: OsString) -> Self {
-        Self(OS_STR_POOL.intern(s, Arc::from))
+        Self(OS_STR_POOL.intern(s))
     }
 
     /// Create a `IOsStr` from `Box<OsStr>`  
     #[inline]
     pub fn from_boxed(s: Box<OsStr>) -> Self {
-        Self(OS_STR_POOL.intern(s, Arc::from))
+        Self(OS_STR_POOL.intern(s))
     }
 
     /// Create a `IOsStr` from `Arc<OsStr>`  
     #[inline]
     pub fn from_arc(s: Arc<OsStr>) -> Self {
-        Self(OS_STR_POOL.intern(s, identity))
+        Self(OS_STR_POOL.intern(s))
     }
 
     /// Create a `IOsStr` from `Rc<OsStr>`  
     #[inline]
     pub fn from_rc(s: Rc<OsStr>) -> Self {
-        Self(OS_STR_POOL.intern(s, |s| Arc::from(s.to_os_string())))
+        Self(OS_STR_POOL.intern(s))
     }
 
     /// Create a `IOsStr` from `MowOsStr`
@@ -57,9 +77,11 @@ impl IOsStr {
     }
 
     /// Create a `IOsStr` from custom fn  
+    /// Entries are copied into the pool, so `to_arc` is never called
+    #[deprecated(note = "`to_arc` is never called, entries are copied into the pool, use `new`")]
     #[inline]
-    pub fn from_to_arc<S: AsRef<OsStr>>(s: S, to_arc: impl FnOnce(S) -> Arc<OsStr>) -> Self {
-        Self(OS_STR_POOL.intern(s, to_arc))
+    pub fn from_to_arc<S: AsRef<OsStr>>(s: S, _to_arc: impl FnOnce(S) -> Arc<OsStr>) -> Self {
+        Self(OS_STR_POOL.intern(s))
     }
 }
 
@@ -81,10 +103,100 @@ impl IOsStr {
     pub fn into_mut(&self) -> MowOsStr {
         MowOsStr::from(self.clone())
     }
+
+    /// Convert to `IStr` if it is valid unicode, interning it into the string pool  
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::{ffi::IOsStr, IStr};
+    /// let s = IOsStr::new("hello world");
+    /// assert_eq!(s.to_istr(), Some(IStr::new("hello world")));
+    /// ```
+    #[inline]
+    pub fn to_istr(&self) -> Option<IStr> {
+        self.to_str().map(IStr::new)
+    }
+
+    /// Convert to `IStr`, invalid unicode is replaced with `U+FFFD`  
+    #[inline]
+    pub fn to_istr_lossy(&self) -> IStr {
+        IStr::from(self.to_string_lossy())
+    }
+}
+
+#[cfg(unix)]
+impl IOsStr {
+    /// Create a `IOsStr` from raw bytes, without checking for UTF-8  
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::ffi::IOsStr;
+    /// let s = IOsStr::from_bytes(b"caf\xe9");
+    /// assert_eq!(s.as_bytes(), b"caf\xe9");
+    /// assert!(s.to_str().is_none());
+    /// ```
+    #[inline]
+    pub fn from_bytes(s: &[u8]) -> Self {
+        Self::new(OsStr::from_bytes(s))
+    }
+
+    /// Create a `IOsStr` from `Vec<u8>`, without checking for UTF-8  
+    #[inline]
+    pub fn from_vec(s: Vec<u8>) -> Self {
+        Self::from_os_string(OsString::from_vec(s))
+    }
+
+    /// Get the raw bytes  
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.deref().as_bytes()
+    }
+}
+
+#[cfg(windows)]
+impl IOsStr {
+    /// Create a `IOsStr` from potentially ill-formed UTF-16, like the strings returned by Win32 APIs  
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::ffi::IOsStr;
+    /// let wide: Vec<u16> = "C:\\Windows".encode_utf16().collect();
+    /// let s = IOsStr::from_wide(&wide);
+    /// assert_eq!(s, "C:\\Windows");
+    /// assert_eq!(s.encode_wide().collect::<Vec<_>>(), wide);
+    /// ```
+    #[inline]
+    pub fn from_wide(wide: &[u16]) -> Self {
+        Self::from_os_string(OsString::from_wide(wide))
+    }
+
+    /// Re-encode to potentially ill-formed UTF-16  
+    #[inline]
+    pub fn encode_wide(&self) -> EncodeWide<'_> {
+        self.deref().encode_wide()
+    }
 }
 
 unsafe impl Interned for IOsStr {}
 
+impl Default for IOsStr {
+    /// Clone the shared empty entry instead of looking it up in the pool
+    #[inline]
+    fn default() -> Self {
+        #[cfg(not(feature = "single-thread"))]
+        return EMPTY.clone();
+        #[cfg(feature = "single-thread")]
+        return EMPTY.with(|empty| (*empty).clone());
+    }
+}
+
+impl Drop for IOsStr {
+    #[inline]
+    fn drop(&mut self) {
+        OS_STR_POOL.on_drop(&self.0)
+    }
+}
+
 impl Deref for IOsStr {
     type Target = OsStr;
 
@@ -115,6 +227,14 @@ impl Hash for IOsStr {
     }
 }
 
+/// Displays lossily, invalid unicode is replaced with `U+FFFD`
+impl fmt::Display for IOsStr {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_string_lossy(), f)
+    }
+}
+
 impl Borrow<OsStr> for IOsStr {
     #[inline]
     fn borrow(&self) -> &OsStr {
@@ -172,6 +292,13 @@ impl From<Rc<OsStr>> for IOsStr {
     }
 }
 
+impl From<&'_ Path> for IOsStr {
+    #[inline]
+    fn from(s: &'_ Path) -> Self {
+        Self::new(s)
+    }
+}
+
 impl From<PathBuf> for IOsStr {
     #[inline]
     fn from(s: PathBuf) -> Self {
@@ -207,6 +334,41 @@ impl From<String> for IOsStr {
     }
 }
 
+impl From<IStr> for IOsStr {
+    #[inline]
+    fn from(s: IStr) -> Self {
+        Self::new(s.as_str())
+    }
+}
+
+impl From<&'_ IStr> for IOsStr {
+    #[inline]
+    fn from(s: &'_ IStr) -> Self {
+        Self::new(s.as_str())
+    }
+}
+
+impl<'a> FromIterator<&'a OsStr> for IOsStr {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = &'a OsStr>>(iter: T) -> Self {
+        Self::from_os_string(OsString::from_iter(iter))
+    }
+}
+
+impl FromIterator<OsString> for IOsStr {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = OsString>>(iter: T) -> Self {
+        Self::from_os_string(OsString::from_iter(iter))
+    }
+}
+
+impl<'a> FromIterator<Cow<'a, OsStr>> for IOsStr {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = Cow<'a, OsStr>>>(iter: T) -> Self {
+        Self::from_os_string(OsString::from_iter(iter))
+    }
+}
+
 impl FromStr for IOsStr {
     type Err = Infallible;
 
@@ -225,7 +387,7 @@ impl From<IOsStr> for Box<OsStr> {
 impl From<IOsStr> for Arc<OsStr> {
     #[inline]
     fn from(v: IOsStr) -> Self {
-        v.0.into()
+        v.0.clone().into()
     }
 }
 
@@ -285,3 +447,21 @@ impl PartialEq<String> for IOsStr {
         self.deref() == other.as_str()
     }
 }
+
+impl PartialEq<Path> for IOsStr {
+    fn eq(&self, other: &Path) -> bool {
+        self.deref() == other.as_os_str()
+    }
+}
+
+impl PartialEq<&Path> for IOsStr {
+    fn eq(&self, other: &&Path) -> bool {
+        self.deref() == other.as_os_str()
+    }
+}
+
+impl PartialEq<PathBuf> for IOsStr {
+    fn eq(&self, other: &PathBuf) -> bool {
+        self.deref() == other.as_os_str()
+    }
+}
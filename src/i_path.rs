@@ -0,0 +1,347 @@
+use std::{
+    borrow::{Borrow, Cow},
+    convert::Infallible,
+    ffi::{OsStr, OsString},
+    hash::{self, Hash},
+    ops::Deref,
+    path::{Path, PathBuf},
+    rc::Rc,
+    str::FromStr,
+    sync::Arc,
+};
+
+use crate::{
+    ffi::IOsStr,
+    intern::Interned,
+    mow_path::MowPath,
+    pool::{Intern, PATH_POOL},
+};
+
+/// Immutable Interning Path
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct IPath(Intern<Path>);
+
+impl IPath {
+    /// Create a `IPath` from path slice  
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::path::IPath;
+    /// let p = IPath::new("/usr/lib");
+    /// ```
+    #[inline]
+    pub fn new(s: impl AsRef<Path>) -> Self {
+        Self(PATH_POOL.intern(s.as_ref()))
+    }
+
+    /// Create a `IPath` from `PathBuf`  
+    #[inline]
+    pub fn from_path_buf(s: PathBuf) -> Self {
+        Self(PATH_POOL.intern(s))
+    }
+
+    /// Create a `IPath` from `Box<Path>`  
+    #[inline]
+    pub fn from_boxed(s: Box<Path>) -> Self {
+        Self(PATH_POOL.intern(s))
+    }
+
+    /// Create a `IPath` from `Arc<Path>`  
+    #[inline]
+    pub fn from_arc(s: Arc<Path>) -> Self {
+        Self(PATH_POOL.intern(s))
+    }
+
+    /// Create a `IPath` from `Rc<Path>`  
+    #[inline]
+    pub fn from_rc(s: Rc<Path>) -> Self {
+        Self(PATH_POOL.intern(s))
+    }
+
+    /// Create a `IPath` from `MowPath`  
+    #[inline]
+    pub fn from_mow(s: MowPath) -> Self {
+        s.into()
+    }
+
+    /// Create a `IPath` from custom fn  
+    /// Entries are copied into the pool, so `to_arc` is never called
+    #[deprecated(note = "`to_arc` is never called, entries are copied into the pool, use `new`")]
+    #[inline]
+    pub fn from_to_arc<S: AsRef<Path>>(s: S, _to_arc: impl FnOnce(S) -> Arc<Path>) -> Self {
+        Self(PATH_POOL.intern(s))
+    }
+}
+
+impl IPath {
+    /// Converts to a `Path` slice.
+    #[inline]
+    pub fn as_path(&self) -> &Path {
+        self.deref()
+    }
+
+    /// Converts to an `Box<Path>`.
+    #[inline]
+    pub fn into_boxed_path(&self) -> Box<Path> {
+        self.deref().into()
+    }
+
+    /// Convert to `MowPath`
+    #[inline]
+    pub fn into_mut(&self) -> MowPath {
+        MowPath::from(self.clone())
+    }
+}
+
+impl IPath {
+    /// Creates an interned path with `path` adjoined to `self`, see [`Path::join`]
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::path::IPath;
+    /// let p = IPath::new("/etc").join("passwd");
+    /// assert_eq!(p, IPath::new("/etc/passwd"));
+    /// ```
+    #[inline]
+    pub fn join(&self, path: impl AsRef<Path>) -> IPath {
+        Self::from_path_buf(self.deref().join(path))
+    }
+
+    /// Returns the interned parent path, see [`Path::parent`]
+    #[inline]
+    pub fn parent(&self) -> Option<IPath> {
+        self.deref().parent().map(Self::new)
+    }
+
+    /// Returns the interned final component, see [`Path::file_name`]
+    #[inline]
+    pub fn file_name(&self) -> Option<IOsStr> {
+        self.deref().file_name().map(IOsStr::new)
+    }
+
+    /// Produces an iterator over the interned components, see [`Path::components`]
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::path::IPath;
+    /// let p = IPath::new("tmp/foo.txt");
+    /// let c: Vec<_> = p.components().collect();
+    /// assert_eq!(c, ["tmp", "foo.txt"]);
+    /// ```
+    #[inline]
+    pub fn components(&self) -> impl Iterator<Item = IOsStr> + '_ {
+        self.deref()
+            .components()
+            .map(|c| IOsStr::new(c.as_os_str()))
+    }
+}
+
+unsafe impl Interned for IPath {}
+
+impl Drop for IPath {
+    #[inline]
+    fn drop(&mut self) {
+        PATH_POOL.on_drop(&self.0)
+    }
+}
+
+impl Deref for IPath {
+    type Target = Path;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_ref()
+    }
+}
+
+impl AsRef<Path> for IPath {
+    #[inline]
+    fn as_ref(&self) -> &Path {
+        self.0.get()
+    }
+}
+
+impl AsRef<OsStr> for IPath {
+    #[inline]
+    fn as_ref(&self) -> &OsStr {
+        self.deref().as_ref()
+    }
+}
+
+impl Hash for IPath {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.deref().hash(state)
+    }
+}
+
+impl Borrow<Path> for IPath {
+    #[inline]
+    fn borrow(&self) -> &Path {
+        self.deref()
+    }
+}
+
+impl From<&'_ Path> for IPath {
+    #[inline]
+    fn from(s: &Path) -> Self {
+        Self::new(s)
+    }
+}
+
+impl From<PathBuf> for IPath {
+    #[inline]
+    fn from(s: PathBuf) -> Self {
+        Self::from_path_buf(s)
+    }
+}
+
+impl From<&'_ PathBuf> for IPath {
+    #[inline]
+    fn from(s: &PathBuf) -> Self {
+        Self::new(s)
+    }
+}
+
+impl From<&'_ str> for IPath {
+    #[inline]
+    fn from(s: &str) -> Self {
+        Self::new(s)
+    }
+}
+
+impl From<String> for IPath {
+    #[inline]
+    fn from(s: String) -> Self {
+        Self::from_path_buf(s.into())
+    }
+}
+
+impl From<&'_ OsStr> for IPath {
+    #[inline]
+    fn from(s: &OsStr) -> Self {
+        Self::new(s)
+    }
+}
+
+impl From<OsString> for IPath {
+    #[inline]
+    fn from(s: OsString) -> Self {
+        Self::from_path_buf(s.into())
+    }
+}
+
+impl From<Box<Path>> for IPath {
+    #[inline]
+    fn from(s: Box<Path>) -> Self {
+        Self::from_boxed(s)
+    }
+}
+
+impl From<Arc<Path>> for IPath {
+    #[inline]
+    fn from(s: Arc<Path>) -> Self {
+        Self::from_arc(s)
+    }
+}
+
+impl From<Rc<Path>> for IPath {
+    #[inline]
+    fn from(s: Rc<Path>) -> Self {
+        Self::from_rc(s)
+    }
+}
+
+impl<'a> From<Cow<'a, Path>> for IPath {
+    #[inline]
+    fn from(s: Cow<'a, Path>) -> Self {
+        match s {
+            Cow::Borrowed(v) => Self::new(v),
+            Cow::Owned(v) => Self::from_path_buf(v),
+        }
+    }
+}
+
+impl FromStr for IPath {
+    type Err = Infallible;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(s))
+    }
+}
+
+impl From<IPath> for PathBuf {
+    #[inline]
+    fn from(v: IPath) -> Self {
+        v.to_path_buf()
+    }
+}
+
+impl From<IPath> for Box<Path> {
+    #[inline]
+    fn from(v: IPath) -> Self {
+        Self::from(v.deref())
+    }
+}
+
+impl From<IPath> for Arc<Path> {
+    #[inline]
+    fn from(v: IPath) -> Self {
+        v.0.clone().into()
+    }
+}
+
+impl From<IPath> for Rc<Path> {
+    #[inline]
+    fn from(v: IPath) -> Self {
+        Self::from(v.deref())
+    }
+}
+
+impl From<IPath> for OsString {
+    #[inline]
+    fn from(v: IPath) -> Self {
+        v.as_os_str().to_os_string()
+    }
+}
+
+impl<'a> From<&'a IPath> for Cow<'a, Path> {
+    #[inline]
+    fn from(v: &'a IPath) -> Self {
+        Cow::Borrowed(v.deref())
+    }
+}
+
+impl PartialEq<Path> for IPath {
+    fn eq(&self, other: &Path) -> bool {
+        self.deref() == other
+    }
+}
+
+impl PartialEq<&Path> for IPath {
+    fn eq(&self, other: &&Path) -> bool {
+        self.deref() == *other
+    }
+}
+
+impl PartialEq<PathBuf> for IPath {
+    fn eq(&self, other: &PathBuf) -> bool {
+        self.deref() == other.as_path()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path() {
+        let a = IPath::new("/a/b");
+        let b = IPath::new("/a").join("b");
+        assert_eq!(a, b);
+        assert_eq!(a.parent(), Some(IPath::new("/a")));
+        assert_eq!(a.file_name(), Some(IOsStr::new("b")));
+        assert_eq!(a.components().count(), 3);
+    }
+}
@@ -0,0 +1,102 @@
+//! Short strings stored without a heap allocation
+
+use std::{cmp::Ordering, fmt, str};
+
+/// A string of at most `N` bytes stored inline, `N` must be less than 256
+#[derive(Clone, Copy)]
+pub(crate) struct InlineStr<const N: usize> {
+    len: u8,
+    buf: [u8; N],
+}
+
+impl<const N: usize> InlineStr<N> {
+    /// New a empty inline string
+    #[inline]
+    pub const fn empty() -> Self {
+        Self {
+            len: 0,
+            buf: [0; N],
+        }
+    }
+
+    /// New a inline string, return `None` if `s` is longer than `N` bytes
+    #[inline]
+    pub fn new(s: &str) -> Option<Self> {
+        let mut r = Self::empty();
+        if r.push_str(s) {
+            Some(r)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        // Only ever filled from whole `str`s and cut at char boundaries
+        unsafe { str::from_utf8_unchecked(&self.buf[..self.len as usize]) }
+    }
+
+    #[inline]
+    pub fn as_mut_str(&mut self) -> &mut str {
+        unsafe { str::from_utf8_unchecked_mut(&mut self.buf[..self.len as usize]) }
+    }
+
+    /// Append `s`, return `false` and leave the string unchanged if it doesn't fit
+    #[inline]
+    pub fn push_str(&mut self, s: &str) -> bool {
+        let len = self.len as usize;
+        if s.len() > N - len {
+            return false;
+        }
+        self.buf[len..len + s.len()].copy_from_slice(s.as_bytes());
+        self.len = (len + s.len()) as u8;
+        true
+    }
+
+    /// See [`String::truncate`]
+    #[inline]
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len <= self.len as usize {
+            assert!(self.as_str().is_char_boundary(new_len));
+            self.len = new_len as u8;
+        }
+    }
+
+    /// See [`String::pop`]
+    #[inline]
+    pub fn pop(&mut self) -> Option<char> {
+        let ch = self.as_str().chars().next_back()?;
+        self.len -= ch.len_utf8() as u8;
+        Some(ch)
+    }
+}
+
+impl<const N: usize> fmt::Debug for InlineStr<N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> PartialEq for InlineStr<N> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<const N: usize> Eq for InlineStr<N> {}
+
+impl<const N: usize> PartialOrd for InlineStr<N> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for InlineStr<N> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
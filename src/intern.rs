@@ -1,12 +1,19 @@
 //! Provides some type conversion utils
 
 use std::{
-    ffi::{OsStr, OsString},
+    ffi::{CStr, CString, OsStr, OsString},
+    path::{Path, PathBuf},
     rc::Rc,
     sync::Arc,
 };
 
-use crate::{ffi::IOsStr, mow_os_str::MowOsStr, IStr, MowStr};
+use crate::{
+    bytes::{IBytes, MowBytes},
+    ffi::{ICStr, IOsStr, MowCStr},
+    mow_os_str::MowOsStr,
+    path::{IPath, MowPath},
+    IStr, MowStr,
+};
 
 /// Type annotation
 #[doc(hidden)]
@@ -31,6 +38,78 @@ pub trait Muterning {
     fn muterned(self) -> Self::Outern;
 }
 
+/// Iterator adapters converting every item, see [`Interning`] and [`Muterning`]
+///
+/// # Example
+/// ```
+/// # use pstr::{IStr, InterningIteratorExt};
+/// let words: Vec<IStr> = "a b a".split(' ').interned().collect();
+/// assert_eq!(words[0], words[2]);
+/// ```
+pub trait InterningIteratorExt: Iterator + Sized {
+    /// Convert every item to Interning String
+    #[inline]
+    fn interned(self) -> InternedIter<Self>
+    where
+        Self::Item: Interning,
+    {
+        InternedIter(self)
+    }
+
+    /// Convert every item to Mutable on Write Interning String
+    #[inline]
+    fn muterned(self) -> MuternedIter<Self>
+    where
+        Self::Item: Muterning,
+    {
+        MuternedIter(self)
+    }
+}
+
+impl<I: Iterator> InterningIteratorExt for I {}
+
+/// Iterator returned by [`InterningIteratorExt::interned`]
+#[derive(Debug, Clone)]
+pub struct InternedIter<I>(I);
+
+impl<I: Iterator> Iterator for InternedIter<I>
+where
+    I::Item: Interning,
+{
+    type Item = <I::Item as Interning>::Outern;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(Interning::interned)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+/// Iterator returned by [`InterningIteratorExt::muterned`]
+#[derive(Debug, Clone)]
+pub struct MuternedIter<I>(I);
+
+impl<I: Iterator> Iterator for MuternedIter<I>
+where
+    I::Item: Muterning,
+{
+    type Item = <I::Item as Muterning>::Outern;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(Muterning::muterned)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
 impl Interning for char {
     type Outern = IStr;
 
@@ -153,6 +232,105 @@ impl Interning for MowOsStr {
     }
 }
 
+impl Interning for &[u8] {
+    type Outern = IBytes;
+
+    fn interned(self) -> Self::Outern {
+        IBytes::new(self)
+    }
+}
+
+impl Interning for Vec<u8> {
+    type Outern = IBytes;
+
+    fn interned(self) -> Self::Outern {
+        IBytes::from_vec(self)
+    }
+}
+
+impl Interning for IBytes {
+    type Outern = IBytes;
+
+    fn interned(self) -> Self::Outern {
+        self
+    }
+}
+
+impl Interning for MowBytes {
+    type Outern = MowBytes;
+
+    fn interned(mut self) -> Self::Outern {
+        self.intern();
+        self
+    }
+}
+
+impl Interning for &CStr {
+    type Outern = ICStr;
+
+    fn interned(self) -> Self::Outern {
+        ICStr::new(self)
+    }
+}
+
+impl Interning for CString {
+    type Outern = ICStr;
+
+    fn interned(self) -> Self::Outern {
+        ICStr::from_c_string(self)
+    }
+}
+
+impl Interning for ICStr {
+    type Outern = ICStr;
+
+    fn interned(self) -> Self::Outern {
+        self
+    }
+}
+
+impl Interning for MowCStr {
+    type Outern = MowCStr;
+
+    fn interned(mut self) -> Self::Outern {
+        self.intern();
+        self
+    }
+}
+
+impl Interning for &Path {
+    type Outern = IPath;
+
+    fn interned(self) -> Self::Outern {
+        IPath::new(self)
+    }
+}
+
+impl Interning for PathBuf {
+    type Outern = IPath;
+
+    fn interned(self) -> Self::Outern {
+        IPath::from_path_buf(self)
+    }
+}
+
+impl Interning for IPath {
+    type Outern = IPath;
+
+    fn interned(self) -> Self::Outern {
+        self
+    }
+}
+
+impl Interning for MowPath {
+    type Outern = MowPath;
+
+    fn interned(mut self) -> Self::Outern {
+        self.intern();
+        self
+    }
+}
+
 impl Muterning for char {
     type Outern = MowStr;
 
@@ -273,3 +451,120 @@ impl Muterning for MowOsStr {
         self
     }
 }
+
+impl Muterning for &[u8] {
+    type Outern = MowBytes;
+
+    fn muterned(self) -> Self::Outern {
+        MowBytes::new_mut(self)
+    }
+}
+
+impl Muterning for Vec<u8> {
+    type Outern = MowBytes;
+
+    fn muterned(self) -> Self::Outern {
+        MowBytes::from_vec_mut(self)
+    }
+}
+
+impl Muterning for IBytes {
+    type Outern = MowBytes;
+
+    fn muterned(self) -> Self::Outern {
+        MowBytes::from_vec_mut(self.to_vec())
+    }
+}
+
+impl Muterning for MowBytes {
+    type Outern = MowBytes;
+
+    fn muterned(mut self) -> Self::Outern {
+        self.to_mut();
+        self
+    }
+}
+
+impl Muterning for &CStr {
+    type Outern = MowCStr;
+
+    fn muterned(self) -> Self::Outern {
+        MowCStr::new_mut(self)
+    }
+}
+
+impl Muterning for CString {
+    type Outern = MowCStr;
+
+    fn muterned(self) -> Self::Outern {
+        MowCStr::from_c_string_mut(self)
+    }
+}
+
+impl Muterning for ICStr {
+    type Outern = MowCStr;
+
+    fn muterned(self) -> Self::Outern {
+        MowCStr::from_c_string_mut(self.as_c_str().into())
+    }
+}
+
+impl Muterning for MowCStr {
+    type Outern = MowCStr;
+
+    fn muterned(mut self) -> Self::Outern {
+        self.to_mut();
+        self
+    }
+}
+
+impl Muterning for &Path {
+    type Outern = MowPath;
+
+    fn muterned(self) -> Self::Outern {
+        MowPath::new_mut(self)
+    }
+}
+
+impl Muterning for PathBuf {
+    type Outern = MowPath;
+
+    fn muterned(self) -> Self::Outern {
+        MowPath::from_path_buf_mut(self)
+    }
+}
+
+impl Muterning for IPath {
+    type Outern = MowPath;
+
+    fn muterned(self) -> Self::Outern {
+        MowPath::from_path_buf_mut(self.to_path_buf())
+    }
+}
+
+impl Muterning for MowPath {
+    type Outern = MowPath;
+
+    fn muterned(mut self) -> Self::Outern {
+        self.to_mut();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iterator_ext() {
+        let v: Vec<MowStr> = vec![String::from("a"), String::from("b")]
+            .into_iter()
+            .muterned()
+            .collect();
+        assert!(v.iter().all(MowStr::is_mutable));
+        let v: Vec<MowStr> = v.into_iter().interned().collect();
+        assert!(v.iter().all(MowStr::is_interned));
+        let v: Vec<IStr> = ["a", "b"].iter().copied().interned().collect();
+        assert_eq!(v, [IStr::new("a"), IStr::new("b")]);
+    }
+}
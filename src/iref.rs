@@ -0,0 +1,272 @@
+//! Interning of arbitrary values
+
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{self, Hash},
+    ops::Deref,
+};
+
+use crate::pool::{registry, GlobalHasher, Intern, Pool};
+
+/// Immutable Interning Reference to any value
+///
+/// Like [`IStr`](crate::IStr) for any `T`, each type has its own global pool created on first use.
+/// A pointer to the entry and one to the pool, cloning, comparing and dropping are O(1)
+///
+/// # Example
+/// ```
+/// # use pstr::IRef;
+/// #[derive(Debug, PartialEq, Eq, Hash)]
+/// struct Mime {
+///     kind: String,
+///     sub: String,
+/// }
+///
+/// let a = IRef::new(Mime { kind: "text".into(), sub: "plain".into() });
+/// let b = IRef::new(Mime { kind: "text".into(), sub: "plain".into() });
+/// assert_eq!(a, b);
+/// assert_eq!(a.sub, "plain");
+/// ```
+pub struct IRef<T: Eq + Hash + Send + Sync + 'static> {
+    intern: Intern<T>,
+    /// Dropping goes straight to the pool instead of looking it up in the registry
+    pool: &'static Pool<T, GlobalHasher>,
+}
+
+impl<T: Eq + Hash + Send + Sync + 'static> IRef<T> {
+    /// Create a `IRef` from a value, the value is moved into the pool if it is new and dropped otherwise
+    #[inline]
+    pub fn new(value: T) -> Self {
+        of().intern(value)
+    }
+
+    /// Get the `IRef` of `value` if it is in the pool, without inserting it
+    #[inline]
+    pub fn get_existing(value: &T) -> Option<Self> {
+        of().get(value)
+    }
+
+    /// Get the global pool of `T`, see [`of`]
+    #[inline]
+    pub fn pool() -> &'static Pool<T, GlobalHasher> {
+        of().pool()
+    }
+
+    /// Get target ref
+    #[inline]
+    pub fn get(&self) -> &T {
+        self.intern.get()
+    }
+
+    /// Get the hash precomputed when the value was interned
+    #[inline]
+    pub fn precomputed_hash(&self) -> u64 {
+        self.intern.precomputed_hash()
+    }
+}
+
+impl<T: Eq + Hash + Send + Sync + 'static> Drop for IRef<T> {
+    #[inline]
+    fn drop(&mut self) {
+        if self.pool.is_release_on_drop() {
+            self.pool.remove_unused(&self.intern);
+        }
+    }
+}
+
+impl<T: Eq + Hash + Send + Sync + 'static> Clone for IRef<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            intern: self.intern.clone(),
+            pool: self.pool,
+        }
+    }
+}
+
+impl<T: Eq + Hash + Send + Sync + 'static> Deref for IRef<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.get()
+    }
+}
+
+impl<T: Eq + Hash + Send + Sync + 'static> AsRef<T> for IRef<T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self.get()
+    }
+}
+
+impl<T: Eq + Hash + Send + Sync + 'static> From<T> for IRef<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: Eq + Hash + Send + Sync + 'static> PartialEq for IRef<T> {
+    /// Equal values share a entry, so this compares pointers
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.intern == other.intern
+    }
+}
+
+impl<T: Eq + Hash + Send + Sync + 'static> Eq for IRef<T> {}
+
+impl<T: Eq + Hash + Send + Sync + 'static> Hash for IRef<T> {
+    /// Writes the precomputed hash, hashing is O(1) regardless of the value
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        state.write_u64(self.precomputed_hash())
+    }
+}
+
+impl<T: Eq + Hash + Send + Sync + Ord + 'static> PartialOrd for IRef<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Eq + Hash + Send + Sync + Ord + 'static> Ord for IRef<T> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.get().cmp(other.get())
+    }
+}
+
+impl<T: Eq + Hash + Send + Sync + fmt::Debug + 'static> fmt::Debug for IRef<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IRef").field(self.get()).finish()
+    }
+}
+
+impl<T: Eq + Hash + Send + Sync + fmt::Display + 'static> fmt::Display for IRef<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.get(), f)
+    }
+}
+
+/// Get the interner of `T`, which lazily creates one global pool per type  
+///
+/// A single entry point for interning values of any type without declaring a static pool for each,
+/// the interned values are [`IRef`]s
+///
+/// # Example
+/// ```
+/// # use pstr::IRef;
+/// let a = pstr::of::<(u16, char)>().intern((1, 'a'));
+/// assert_eq!(a, IRef::new((1, 'a')));
+/// assert_eq!(pstr::of::<(u16, char)>().len(), 1);
+/// ```
+#[inline]
+pub fn of<T: Eq + Hash + Send + Sync + 'static>() -> Interner<T> {
+    Interner(registry().global())
+}
+
+/// The global interner of a type, see [`of`]
+pub struct Interner<T: Eq + Hash + Send + Sync + 'static>(&'static Pool<T, GlobalHasher>);
+
+impl<T: Eq + Hash + Send + Sync + 'static> Interner<T> {
+    /// Intern `value`, see [`IRef::new`]
+    #[inline]
+    pub fn intern(&self, value: T) -> IRef<T> {
+        IRef {
+            intern: self.0.intern_value(value),
+            pool: self.0,
+        }
+    }
+
+    /// Get the `IRef` of `value` if it is in the pool, without inserting it
+    #[inline]
+    pub fn get(&self, value: &T) -> Option<IRef<T>> {
+        self.0.get(value).map(|intern| IRef {
+            intern,
+            pool: self.0,
+        })
+    }
+
+    /// Get the number of values in the pool
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Check if the pool is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Get the global pool of `T`
+    #[inline]
+    pub fn pool(&self) -> &'static Pool<T, GlobalHasher> {
+        self.0
+    }
+}
+
+impl<T: Eq + Hash + Send + Sync + 'static> Clone for Interner<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Eq + Hash + Send + Sync + 'static> Copy for Interner<T> {}
+
+impl<T: Eq + Hash + Send + Sync + fmt::Debug + 'static> fmt::Debug for Interner<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Interner").field(self.0).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iref() {
+        let a = IRef::new(vec!["iref".to_string(), "segments".to_string()]);
+        let b = IRef::from(vec!["iref".to_string(), "segments".to_string()]);
+        assert_eq!(a, b);
+        assert_eq!(a[1], "segments");
+        assert_eq!(IRef::get_existing(a.get()), Some(a.clone()));
+        assert!(IRef::<Vec<String>>::get_existing(&vec![]).is_none());
+        assert!(IRef::new(1u64) < IRef::new(2u64));
+        assert_eq!(format!("{:?}", IRef::new(7u8)), "IRef(7)");
+    }
+
+    #[test]
+    fn test_of() {
+        #[derive(Debug, PartialEq, Eq, Hash)]
+        struct Token(&'static str);
+        let a = of::<Token>().intern(Token("of"));
+        assert!(std::ptr::eq(of::<Token>().pool(), IRef::<Token>::pool()));
+        assert_eq!(of::<Token>().get(&Token("of")), Some(a));
+        assert!(of::<Token>().get(&Token("other")).is_none());
+        assert!(!std::ptr::eq(
+            of::<u32>().pool() as *const _ as *const u8,
+            of::<u64>().pool() as *const _ as *const u8
+        ));
+    }
+
+    #[test]
+    fn test_release_on_drop() {
+        #[derive(Debug, PartialEq, Eq, Hash)]
+        struct Released(u8);
+        IRef::<Released>::pool().set_release_on_drop(true);
+        let a = IRef::new(Released(1));
+        let b = a.clone();
+        drop(a);
+        assert_eq!(of::<Released>().len(), 1);
+        drop(b);
+        assert!(of::<Released>().is_empty());
+    }
+}
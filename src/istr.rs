@@ -1,31 +1,155 @@
 use std::{
     borrow::{Borrow, Cow},
-    convert::identity,
+    cmp::Ordering,
     error::Error,
     ffi::{OsStr, OsString},
+    fmt,
     hash::{self, Hash},
+    io::Cursor,
     iter::FromIterator,
+    mem::{self, ManuallyDrop},
     net::ToSocketAddrs,
     ops::{Deref, Index},
     path::{Path, PathBuf},
+    ptr::NonNull,
     rc::Rc,
     slice::SliceIndex,
     str::{self, FromStr},
-    string::ParseError,
+    string::{FromUtf16Error, ParseError},
     sync::Arc,
 };
 
 use crate::{
-    intern::Interned,
-    pool::{Intern, STR_POOL},
+    intern::{Interned, InternedIter, InterningIteratorExt},
+    pool::{self, GlobalHasher, Intern, Pool, STR_POOL},
     MowStr,
 };
 
-/// Immutable Interning String
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
-pub struct IStr(Intern<str>);
+/// A `std::io::Read`/`BufRead` cursor over the bytes of a `IStr`, see [`IStr::reader`]
+pub type IStrReader = Cursor<IStr>;
+
+/// Strings up to this many bytes are stored in the handle instead of in the pool
+const INLINE_CAP: usize = mem::size_of::<usize>() - 1;
+
+/// Index of the tag byte in a inline handle, which is the lowest byte of the word
+const TAG: usize = if cfg!(target_endian = "little") {
+    0
+} else {
+    INLINE_CAP
+};
+
+/// Index of the first byte of the string in a inline handle
+const TEXT: usize = if cfg!(target_endian = "little") { 1 } else { 0 };
+
+/// The empty `IStr`, see [`IStr::empty`]
+pub const EMPTY: IStr = IStr::empty();
+
+/// What a handle holds
+enum Repr<'a> {
+    /// Borrowed from the handle, must not be dropped
+    Pooled(ManuallyDrop<Intern<str>>),
+    /// Short enough to be stored in the handle, never in the pool
+    Inline(&'a str),
+}
+
+/// Immutable Interning String  
+///
+/// A single word, and so is `Option<IStr>`:
+/// either a thin pointer to the pool entry, which stores the length and the precomputed hash,
+/// or a string of at most 7 bytes (3 on 32-bit targets) stored inline in the handle.  
+/// Inline strings never touch the pool, they compare by content instead of by pointer
+pub struct IStr(NonNull<u8>);
+
+// SAFETY: a handle is either a `Intern<str>` or plain bytes,
+// with the `single-thread` feature a `Intern<str>` is neither `Send` nor `Sync`
+#[cfg(not(feature = "single-thread"))]
+unsafe impl Send for IStr {}
+#[cfg(not(feature = "single-thread"))]
+unsafe impl Sync for IStr {}
 
 impl IStr {
+    /// Store `s` inline if it is short enough, otherwise intern it, through the thread cache if it is enabled
+    #[inline]
+    fn make<S: AsRef<str>>(s: S) -> Self {
+        match Self::inline(s.as_ref()) {
+            Some(v) => v,
+            None => Self::pooled(pool::intern_str(s.as_ref())),
+        }
+    }
+
+    #[inline]
+    fn pooled(intern: Intern<str>) -> Self {
+        let ptr = intern.into_raw() as *mut u8;
+        // Entries are aligned to at least 2 bytes, so the low bit is clear
+        debug_assert_eq!(ptr as usize & 1, 0);
+        Self(unsafe { NonNull::new_unchecked(ptr) })
+    }
+
+    /// Create a `IStr` from a string whose hash `hash` computes in pieces, see [`IStrBuilder`](crate::IStrBuilder)  
+    /// `hash` is only called if `s` isn't stored inline
+    pub(crate) fn from_prehashed(
+        s: &str,
+        pool: &Pool<str, GlobalHasher>,
+        hash: impl FnOnce() -> u64,
+    ) -> Self {
+        match Self::inline(s) {
+            Some(v) => v,
+            None => Self::pooled(pool.intern_prehashed(s, hash())),
+        }
+    }
+
+    /// Store `s` in the handle, return `None` if it is longer than `INLINE_CAP` bytes  
+    ///
+    /// The lowest byte is `len << 1 | 1`, the unused bytes are zero so equal strings are equal words
+    #[inline]
+    fn inline(s: &str) -> Option<Self> {
+        if s.len() > INLINE_CAP {
+            return None;
+        }
+        let mut bytes = [0; mem::size_of::<usize>()];
+        bytes[TAG] = (s.len() as u8) << 1 | 1;
+        bytes[TEXT..TEXT + s.len()].copy_from_slice(s.as_bytes());
+        let word = usize::from_ne_bytes(bytes) as *mut u8;
+        // The low bit is set, so the word is never null
+        Some(Self(unsafe { NonNull::new_unchecked(word) }))
+    }
+
+    #[inline]
+    fn is_inline(&self) -> bool {
+        self.0.as_ptr() as usize & 1 == 1
+    }
+
+    #[inline]
+    fn repr(&self) -> Repr<'_> {
+        if self.is_inline() {
+            let bytes = unsafe { &*(self as *const Self as *const [u8; mem::size_of::<usize>()]) };
+            let len = (bytes[TAG] >> 1) as usize;
+            // Only ever filled from a whole `str`
+            Repr::Inline(unsafe { str::from_utf8_unchecked(&bytes[TEXT..TEXT + len]) })
+        } else {
+            Repr::Pooled(ManuallyDrop::new(unsafe {
+                Intern::from_raw(self.0.as_ptr() as *const ())
+            }))
+        }
+    }
+
+    /// The empty string, built at compile time without hashing or touching the pool  
+    /// Cheap enough to use as a "no value" sentinel, see also [`EMPTY`]
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::IStr;
+    /// assert_eq!(IStr::empty(), IStr::new(""));
+    /// assert_eq!(pstr::EMPTY, "");
+    /// ```
+    #[inline]
+    pub const fn empty() -> Self {
+        let mut bytes = [0; mem::size_of::<usize>()];
+        bytes[TAG] = 1;
+        let word = usize::from_ne_bytes(bytes) as *mut u8;
+        Self(unsafe { NonNull::new_unchecked(word) })
+    }
+
     /// Create a `IStr` from str slice  
     ///
     /// # Example
@@ -35,31 +159,131 @@ impl IStr {
     /// ```
     #[inline]
     pub fn new(s: impl AsRef<str>) -> Self {
-        Self(STR_POOL.intern(s.as_ref(), Arc::from))
+        Self::make(s.as_ref())
+    }
+
+    /// Create a `IStr` and report whether the string was newly put into the pool, see [`Pool::intern_report`](crate::pool::Pool::intern_report)  
+    /// Strings short enough to be stored inline never touch the pool and report `false`
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::IStr;
+    /// let (a, inserted) = IStr::new_reporting("new_reporting example");
+    /// assert!(inserted);
+    /// let (b, inserted) = IStr::new_reporting("new_reporting example");
+    /// assert!(!inserted);
+    /// assert_eq!(a, b);
+    /// ```
+    #[inline]
+    pub fn new_reporting(s: impl AsRef<str>) -> (Self, bool) {
+        let s = s.as_ref();
+        match Self::inline(s) {
+            Some(v) => (v, false),
+            None => {
+                let (v, inserted) = pool::with_str_pool(|p| p.intern_report(s));
+                (Self::pooled(v), inserted)
+            }
+        }
+    }
+
+    /// Create a `IStr` without blocking, return `None` if the pool is busy, see [`Pool::try_intern`](crate::pool::Pool::try_intern)  
+    /// Strings short enough to be stored inline always succeed
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::IStr;
+    /// let s = IStr::try_new("not waiting for the pool").unwrap();
+    /// assert_eq!(s, "not waiting for the pool");
+    /// ```
+    #[inline]
+    pub fn try_new(s: impl AsRef<str>) -> Option<Self> {
+        let s = s.as_ref();
+        match Self::inline(s) {
+            Some(v) => Some(v),
+            None => pool::with_str_pool(|p| p.try_intern(s)).map(Self::pooled),
+        }
+    }
+
+    /// Create a `IStr` from a string literal  
+    ///
+    /// A new entry points at the literal instead of copying it, only its header is allocated,
+    /// if the string is already in the pool the existing entry is used. See [`istr!`](crate::istr!) to skip the lookup too
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::IStr;
+    /// let a = IStr::from_static("a static string literal");
+    /// let b = IStr::from_static("a static string literal");
+    /// assert_eq!(a.as_ptr(), b.as_ptr());
+    /// ```
+    #[inline]
+    pub fn from_static(s: &'static str) -> Self {
+        match Self::inline(s) {
+            Some(v) => v,
+            None => Self::pooled(pool::with_str_pool(|p| p.intern_static(s))),
+        }
     }
 
     /// Create a `IStr` from `String`  
     #[inline]
     pub fn from_string(s: String) -> Self {
-        Self(STR_POOL.intern(s, Arc::from))
+        Self::make(s)
+    }
+
+    /// Create a `IStr` from UTF-16 encoded code units, returning an error if they contain invalid data  
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::IStr;
+    /// let wide: Vec<u16> = "hello world".encode_utf16().collect();
+    /// assert_eq!(IStr::from_utf16(&wide).unwrap(), "hello world");
+    /// assert!(IStr::from_utf16(&[0xD800]).is_err());
+    /// ```
+    #[inline]
+    pub fn from_utf16(v: &[u16]) -> Result<Self, FromUtf16Error> {
+        String::from_utf16(v).map(Self::from_string)
+    }
+
+    /// Create a `IStr` from UTF-16 encoded code units, replacing invalid data with the replacement character  
+    #[inline]
+    pub fn from_utf16_lossy(v: &[u16]) -> Self {
+        Self::from_string(String::from_utf16_lossy(v))
+    }
+
+    /// Create a `IStr` for every string of `values`, in the same order, see [`Pool::intern_many`](crate::pool::Pool::intern_many)
+    #[inline]
+    pub fn new_many<S: AsRef<str>>(values: &[S]) -> Vec<Self> {
+        let long: Vec<&str> = values
+            .iter()
+            .map(AsRef::as_ref)
+            .filter(|s| s.len() > INLINE_CAP)
+            .collect();
+        let mut pooled = pool::with_str_pool(|p| p.intern_many(&long)).into_iter();
+        values
+            .iter()
+            .map(|s| match Self::inline(s.as_ref()) {
+                Some(v) => v,
+                None => Self::pooled(pooled.next().unwrap()),
+            })
+            .collect()
     }
 
     /// Create a `IStr` from `Box<str>`  
     #[inline]
     pub fn from_boxed(s: Box<str>) -> Self {
-        Self(STR_POOL.intern(s, Arc::from))
+        Self::make(s)
     }
 
     /// Create a `IStr` from `Arc<str>`  
     #[inline]
     pub fn from_arc(s: Arc<str>) -> Self {
-        Self(STR_POOL.intern(s, identity))
+        Self::make(s)
     }
 
     /// Create a `IStr` from `Rc<str>`  
     #[inline]
     pub fn from_rc(s: Rc<str>) -> Self {
-        Self(STR_POOL.intern(s, |s| Arc::from(s.to_string())))
+        Self::make(s)
     }
 
     /// Create a `IStr` from `MowStr`  
@@ -69,9 +293,11 @@ impl IStr {
     }
 
     /// Create a `IStr` from custom fn  
+    /// Entries are copied into the pool, so `to_arc` is never called
+    #[deprecated(note = "`to_arc` is never called, entries are copied into the pool, use `new`")]
     #[inline]
-    pub fn from_to_arc<S: AsRef<str>>(s: S, to_arc: impl FnOnce(S) -> Arc<str>) -> Self {
-        Self(STR_POOL.intern(s, to_arc))
+    pub fn from_to_arc<S: AsRef<str>>(s: S, _to_arc: impl FnOnce(S) -> Arc<str>) -> Self {
+        Self::make(s)
     }
 }
 
@@ -88,15 +314,228 @@ impl IStr {
         self.deref().into()
     }
 
+    /// Get the `IStr` of `s` if it is already interned, without interning it  
+    /// Strings short enough to be stored inline are always returned
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::IStr;
+    /// assert!(IStr::get_existing("never interned before").is_none());
+    /// let s = IStr::new("get_existing example");
+    /// assert_eq!(IStr::get_existing("get_existing example"), Some(s));
+    /// ```
+    #[inline]
+    pub fn get_existing(s: impl AsRef<str>) -> Option<Self> {
+        let s = s.as_ref();
+        Self::inline(s).or_else(|| pool::with_str_pool(|p| p.get(s)).map(Self::pooled))
+    }
+
+    /// Get a `IStr` of a substring, the slice is looked up in the pool without copying it first  
+    ///
+    /// # Panics
+    /// Panics like `str` indexing if `range` is out of bounds or not on char boundaries
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::IStr;
+    /// let src = IStr::new("let x = y;");
+    /// assert_eq!(src.substr(4..5), "x");
+    /// assert_eq!(src.substr(8..), IStr::new("y;"));
+    /// ```
+    #[inline]
+    pub fn substr<R: SliceIndex<str, Output = str>>(&self, range: R) -> Self {
+        Self::new(&self[range])
+    }
+
+    /// Split by `pat` like [`str::split`], interning every piece
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::IStr;
+    /// let row = IStr::new("a,b,a");
+    /// let cols: Vec<IStr> = row.split_interned(",").collect();
+    /// assert_eq!(cols, ["a", "b", "a"]);
+    /// assert_eq!(cols[0], cols[2]);
+    /// ```
+    #[inline]
+    pub fn split_interned<'a>(&'a self, pat: &'a str) -> InternedIter<str::Split<'a, &'a str>> {
+        self.split(pat).interned()
+    }
+
+    /// Split by `pat` into at most `n` pieces like [`str::splitn`], interning every piece
+    #[inline]
+    pub fn splitn_interned<'a>(
+        &'a self,
+        n: usize,
+        pat: &'a str,
+    ) -> InternedIter<str::SplitN<'a, &'a str>> {
+        self.splitn(n, pat).interned()
+    }
+
+    /// Split by `pat` from the end like [`str::rsplit`], interning every piece
+    #[inline]
+    pub fn rsplit_interned<'a>(&'a self, pat: &'a str) -> InternedIter<str::RSplit<'a, &'a str>> {
+        self.rsplit(pat).interned()
+    }
+
     /// Convert to `MowStr`  
     #[inline]
     pub fn into_mut(&self) -> MowStr {
         MowStr::from(self.clone())
     }
+
+    /// Create a reader over the bytes without copying  
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::IStr;
+    /// use std::io::BufRead;
+    /// let s = IStr::new("a\nb");
+    /// let lines: Vec<_> = s.reader().lines().map(Result::unwrap).collect();
+    /// assert_eq!(lines, ["a", "b"]);
+    /// ```
+    #[inline]
+    pub fn reader(&self) -> IStrReader {
+        Cursor::new(self.clone())
+    }
+
+    /// Get the hash precomputed when the string was interned  
+    ///
+    /// Equal strings always have the same precomputed hash, a inline string is hashed on every call
+    #[inline]
+    pub fn precomputed_hash(&self) -> u64 {
+        match self.repr() {
+            Repr::Pooled(v) => v.precomputed_hash(),
+            Repr::Inline(v) => STR_POOL.hash(v),
+        }
+    }
+
+    /// Pin the entry in the pool forever and get a `'static` reference to it  
+    ///
+    /// Leaking the same string twice doesn't duplicate it, every call returns the pooled string,
+    /// a inline string is put into the pool first
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::IStr;
+    /// let a: &'static str = IStr::new("leaked").leak();
+    /// let b: &'static str = IStr::new("leaked").leak();
+    /// assert_eq!(a, "leaked");
+    /// assert!(std::ptr::eq(a, b));
+    /// ```
+    #[inline]
+    pub fn leak(self) -> &'static str {
+        let intern = match self.repr() {
+            Repr::Pooled(v) => (*v).clone(),
+            Repr::Inline(v) => pool::intern_str(v),
+        };
+        let s: *const str = intern.get();
+        // SAFETY: the forgotten handle is never dropped, so the entry lives forever,
+        // and garbage collection never removes a entry that is referenced outside the pool
+        mem::forget(intern);
+        unsafe { &*s }
+    }
+
+    /// Turn into a opaque pointer sized handle, keeping the string interned until [`from_raw`](IStr::from_raw) takes it back  
+    ///
+    /// The pointer is never null, but it doesn't point to the string and must not be dereferenced,
+    /// short strings are stored in the pointer itself
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::IStr;
+    /// let raw = IStr::new("stashed in c userdata").into_raw();
+    /// let s = unsafe { IStr::from_raw(raw) };
+    /// assert_eq!(s, "stashed in c userdata");
+    /// ```
+    #[inline]
+    pub fn into_raw(self) -> *const () {
+        let ptr = self.0.as_ptr() as *const ();
+        mem::forget(self);
+        ptr
+    }
+
+    /// Take back a handle from [`into_raw`](IStr::into_raw)
+    ///
+    /// # Safety
+    /// `ptr` must come from `into_raw` and must not be used again afterwards
+    #[inline]
+    pub unsafe fn from_raw(ptr: *const ()) -> Self {
+        Self(NonNull::new_unchecked(ptr as *mut u8))
+    }
+
+    /// Drop this handle, removing the entry from the pool if it was the last one
+    #[inline]
+    pub(crate) fn release(self) -> bool {
+        match self.repr() {
+            Repr::Pooled(v) => pool::with_owning_str_pool(&v, |p| p.remove_unused(&v)),
+            Repr::Inline(_) => false,
+        }
+    }
 }
 
 unsafe impl Interned for IStr {}
 
+impl Clone for IStr {
+    #[inline]
+    fn clone(&self) -> Self {
+        match self.repr() {
+            Repr::Pooled(v) => Self::pooled((*v).clone()),
+            Repr::Inline(_) => Self(self.0),
+        }
+    }
+}
+
+impl Default for IStr {
+    /// See [`IStr::empty`]
+    #[inline]
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl Drop for IStr {
+    #[inline]
+    fn drop(&mut self) {
+        if !self.is_inline() {
+            let intern = unsafe { Intern::<str>::from_raw(self.0.as_ptr() as *const ()) };
+            pool::with_owning_str_pool(&intern, |p| p.on_drop(&intern))
+        }
+    }
+}
+
+impl fmt::Debug for IStr {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IStr").field(&self.as_str()).finish()
+    }
+}
+
+impl PartialEq for IStr {
+    /// Compares the words, the same entry or the same inline string,
+    /// whether a string is inline only depends on its length
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for IStr {}
+
+impl PartialOrd for IStr {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for IStr {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
 impl Deref for IStr {
     type Target = str;
 
@@ -125,7 +564,11 @@ impl AsRef<[u8]> for IStr {
 impl AsRef<str> for IStr {
     #[inline]
     fn as_ref(&self) -> &str {
-        self.0.get()
+        match self.repr() {
+            // SAFETY: the entry is kept alive by `self`
+            Repr::Pooled(v) => unsafe { &*(v.get() as *const str) },
+            Repr::Inline(v) => v,
+        }
     }
 }
 
@@ -153,6 +596,8 @@ impl<I: SliceIndex<str>> Index<I> for IStr {
 }
 
 impl Hash for IStr {
+    /// Hashes the contents like `str`, so maps keyed by `IStr` can be looked up by `&str`  
+    /// Use [`IStrMap`](crate::IStrMap) to hash with the precomputed hash instead
     #[inline]
     fn hash<H: hash::Hasher>(&self, state: &mut H) {
         self.deref().hash(state)
@@ -226,6 +671,13 @@ impl<'a> From<Cow<'a, str>> for IStr {
     }
 }
 
+impl<'a> From<&Cow<'a, str>> for IStr {
+    #[inline]
+    fn from(s: &Cow<'a, str>) -> Self {
+        Self::new(s)
+    }
+}
+
 impl<'a> FromIterator<&'a char> for IStr {
     #[inline]
     fn from_iter<T: IntoIterator<Item = &'a char>>(iter: T) -> Self {
@@ -319,9 +771,10 @@ impl<'a> From<&'a IStr> for Cow<'a, str> {
     }
 }
 
-impl ToString for IStr {
-    fn to_string(&self) -> String {
-        self.deref().to_string()
+impl fmt::Display for IStr {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.deref(), f)
     }
 }
 
@@ -402,6 +855,12 @@ impl PartialEq<OsString> for IStr {
     }
 }
 
+impl<'a> PartialEq<Cow<'a, str>> for IStr {
+    fn eq(&self, other: &Cow<'a, str>) -> bool {
+        self.deref() == other.as_ref()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -425,4 +884,104 @@ mod tests {
         let b = IStr::new("123");
         assert_ne!(a, b);
     }
+
+    #[test]
+    fn test_from_utf16() {
+        let wide: Vec<u16> = "from utf16 units".encode_utf16().collect();
+        assert_eq!(
+            IStr::from_utf16(&wide).unwrap(),
+            IStr::new("from utf16 units")
+        );
+        assert!(IStr::from_utf16(&[0x61, 0xD800]).is_err());
+        assert_eq!(IStr::from_utf16_lossy(&[0x61, 0xD800]), "a\u{FFFD}");
+    }
+
+    #[test]
+    fn test_cow() {
+        let c: Cow<str> = Cow::Borrowed("from a cow");
+        let s = IStr::from(&c);
+        assert_eq!(s, c);
+        assert_eq!(MowStr::from(&c), c);
+        assert_ne!(s, Cow::<str>::Owned("other".to_string()));
+    }
+
+    #[test]
+    fn test_default() {
+        let s = IStr::default();
+        assert_eq!(s, "");
+        assert_eq!(s, IStr::new(""));
+        assert!(MowStr::default().is_interned());
+        assert_eq!(MowStr::default(), "");
+        assert_eq!(IStr::empty(), EMPTY);
+        assert!(MowStr::interned_empty().is_interned());
+    }
+
+    #[test]
+    fn test_precomputed_hash() {
+        let a = IStr::new("hash_me");
+        let b = IStr::from_string("hash_me".to_string());
+        assert_eq!(a.precomputed_hash(), b.precomputed_hash());
+        let mut set = std::collections::HashSet::new();
+        set.insert(a);
+        assert!(set.contains(&b));
+        assert!(set.contains("hash_me"));
+    }
+
+    #[test]
+    fn test_from_static() {
+        static LITERAL: &str = "test_from_static borrows the literal";
+        let a = IStr::from_static(LITERAL);
+        assert_eq!(a.as_ptr(), LITERAL.as_ptr());
+        assert_eq!(IStr::new(String::from(LITERAL)).as_ptr(), LITERAL.as_ptr());
+        assert_eq!(IStr::from_static("short"), "short");
+    }
+
+    #[test]
+    fn test_leak() {
+        let s = IStr::new("test_leak_pooled").leak();
+        STR_POOL.collect_garbage();
+        assert!(STR_POOL.contains("test_leak_pooled"));
+        assert_eq!(IStr::new("test_leak_pooled").as_ptr(), s.as_ptr());
+        let s = IStr::new("test_leak").leak();
+        assert!(STR_POOL.contains("test_leak"));
+        assert!(std::ptr::eq(IStr::new("test_leak").leak(), s));
+    }
+
+    #[test]
+    fn test_inline() {
+        let a = IStr::new("inline");
+        let b = IStr::from_string("inline".to_string());
+        assert_eq!(a, b);
+        assert_ne!(a.as_ptr(), b.as_ptr());
+        assert_eq!(a.precomputed_hash(), b.precomputed_hash());
+        assert!(!STR_POOL.contains("inline"));
+        let c = IStr::new("inline but longer");
+        assert_ne!(a, c);
+        assert!(a < c);
+        assert_eq!(IStr::new_many(&["inline", "inline but longer"]), [a, c]);
+    }
+
+    #[test]
+    fn test_raw() {
+        let raw = IStr::new("test_raw_pooled_string").into_raw();
+        STR_POOL.collect_garbage();
+        assert!(STR_POOL.contains("test_raw_pooled_string"));
+        assert_eq!(unsafe { IStr::from_raw(raw) }, "test_raw_pooled_string");
+        let raw = IStr::new("raw").into_raw();
+        assert_eq!(unsafe { IStr::from_raw(raw) }, IStr::new("raw"));
+    }
+
+    #[test]
+    fn test_size() {
+        assert_eq!(mem::size_of::<IStr>(), mem::size_of::<usize>());
+        assert_eq!(mem::size_of::<Option<IStr>>(), mem::size_of::<usize>());
+        let a = IStr::new("1234567");
+        assert_eq!(a, "1234567");
+        assert_eq!(a.clone(), IStr::from_string("1234567".to_string()));
+        assert_eq!(IStr::new(""), "");
+        assert_eq!(
+            format!("{:?}", IStr::new("12345678")),
+            r#"IStr("12345678")"#
+        );
+    }
 }
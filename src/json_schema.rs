@@ -0,0 +1,65 @@
+//! JSON schemas for [schemars](https://crates.io/crates/schemars)
+//!
+//! Interning doesn't show in the data, the strings have the same schema as `String`
+
+use std::borrow::Cow;
+
+use ::schemars::{JsonSchema, Schema, SchemaGenerator};
+
+use crate::{IStr, MowStr};
+
+impl JsonSchema for IStr {
+    #[inline]
+    fn inline_schema() -> bool {
+        true
+    }
+
+    #[inline]
+    fn schema_name() -> Cow<'static, str> {
+        String::schema_name()
+    }
+
+    #[inline]
+    fn schema_id() -> Cow<'static, str> {
+        String::schema_id()
+    }
+
+    #[inline]
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        String::json_schema(generator)
+    }
+}
+
+impl JsonSchema for MowStr {
+    #[inline]
+    fn inline_schema() -> bool {
+        true
+    }
+
+    #[inline]
+    fn schema_name() -> Cow<'static, str> {
+        String::schema_name()
+    }
+
+    #[inline]
+    fn schema_id() -> Cow<'static, str> {
+        String::schema_id()
+    }
+
+    #[inline]
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        String::json_schema(generator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::schemars::schema_for;
+
+    #[test]
+    fn test_json_schema() {
+        assert_eq!(schema_for!(IStr), schema_for!(String));
+        assert_eq!(schema_for!(Vec<MowStr>), schema_for!(Vec<String>));
+    }
+}
@@ -0,0 +1,73 @@
+//! Migration from the index-based interners of [lasso](https://crates.io/crates/lasso)
+
+use std::{
+    collections::HashMap,
+    hash::{BuildHasher, Hash},
+};
+
+use ::lasso::{Key, Rodeo};
+
+use crate::{
+    pool::{Intern, Pool},
+    IStr,
+};
+
+impl<S: BuildHasher + Clone> Pool<str, S> {
+    /// Intern every string of `rodeo`, returning the intern of each key
+    ///
+    /// Like [`Pool::load`] the pool doesn't keep the strings alive, hold on to the returned interns to keep them
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::pool::Pool;
+    /// let mut rodeo = lasso::Rodeo::default();
+    /// let key = rodeo.get_or_intern("from lasso");
+    ///
+    /// let pool = Pool::<str>::new();
+    /// let interns = pool.import_rodeo(&rodeo);
+    /// assert_eq!(interns[&key].get(), "from lasso");
+    /// assert!(pool.contains("from lasso"));
+    /// ```
+    pub fn import_rodeo<K: Key + Hash, H>(&self, rodeo: &Rodeo<K, H>) -> HashMap<K, Intern<str>> {
+        let (keys, strs): (Vec<K>, Vec<&str>) = rodeo.iter().unzip();
+        keys.into_iter().zip(self.intern_many(&strs)).collect()
+    }
+}
+
+impl IStr {
+    /// Create a `IStr` for every string of `rodeo`, mapping each key to its `IStr`
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::IStr;
+    /// let mut rodeo = lasso::Rodeo::default();
+    /// let key = rodeo.get_or_intern("from lasso");
+    /// assert_eq!(IStr::from_rodeo(&rodeo)[&key], "from lasso");
+    /// ```
+    pub fn from_rodeo<K: Key + Hash, H>(rodeo: &Rodeo<K, H>) -> HashMap<K, IStr> {
+        let (keys, strs): (Vec<K>, Vec<&str>) = rodeo.iter().unzip();
+        keys.into_iter().zip(IStr::new_many(&strs)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::lasso::{Rodeo, Spur};
+
+    #[test]
+    fn test_lasso() {
+        let mut rodeo = Rodeo::<Spur>::new();
+        let a = rodeo.get_or_intern("lasso migration a");
+        let b = rodeo.get_or_intern("lasso migration b");
+        let pool = Pool::<str>::new();
+        let interns = pool.import_rodeo(&rodeo);
+        assert_eq!(interns.len(), 2);
+        assert_eq!(interns[&b].get(), "lasso migration b");
+        assert_eq!(pool.len(), 2);
+
+        let istrs = IStr::from_rodeo(&rodeo);
+        assert_eq!(istrs[&a], IStr::new(rodeo.resolve(&a)));
+        assert_eq!(istrs[&b], "lasso migration b");
+    }
+}
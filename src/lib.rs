@@ -23,20 +23,180 @@
 //! s.intern();
 //! assert!(s.is_interned());
 //! ```
+//!
+//! # Features
+//! - `ahash` - Hash the global pools with [ahash](https://crates.io/crates/ahash)
+//! - `fxhash` - Hash the global pools with [fxhash](https://crates.io/crates/fxhash)
+//! - `background-gc` - `pool::spawn_gc` to collect garbage of the global pools from a background thread
+//! - `macros` - `symbols!` declaring symbol tables at compile time and `#[derive(Interning)]` converting structs to interned strings
+//! - `rayon` - `par_intern` and `Pool::par_collect_garbage` using [rayon](https://crates.io/crates/rayon)
+//! - `unicode-normalization` - `IStr::new_nfc` and `MowStr::intern_nfc` interning the NFC form using [unicode-normalization](https://crates.io/crates/unicode-normalization)
+//! - `parking_lot` - Use [parking_lot](https://crates.io/crates/parking_lot) locks inside the pools
+//! - `counters` - `Pool::counters` counting intern hits, misses and garbage collection runs
+//! - `tracing` - Emit [tracing](https://crates.io/crates/tracing) events for intern misses, evictions and garbage collection runs
+//! - `metrics` - Report the entries, bytes, hits, misses and garbage collected entries of the global, registry and labeled pools to [metrics](https://crates.io/crates/metrics), with a `pool` label
+//! - `get-size` - Implement `GetSize` of [get-size](https://crates.io/crates/get-size) for `IStr`, `MowStr` and `Pool`
+//! - `capi` - `capi` exposing the global string pool to C
+//! - `single-thread` - Pools without atomics or locks for targets where everything runs on one thread like `wasm32-unknown-unknown`,
+//!   each thread has its own global pools and the handles are neither `Send` nor `Sync`, can't be combined with `background-gc` or `rayon`
+//! - `serde` - `Serialize` and `Deserialize` for `IStr` and `MowStr`, and `serde::deserialize_interned` interning every string of a document using [serde](https://crates.io/crates/serde)
+//! - `schemars` - `JsonSchema` of [schemars](https://crates.io/crates/schemars) for `IStr` and `MowStr`, as plain strings
+//! - `sqlx` - `Type`, `Encode` and `Decode` of [sqlx](https://crates.io/crates/sqlx) for `IStr` and `MowStr` as text, interning on fetch
+//! - `postgres` - `ToSql` and `FromSql` of [postgres-types](https://crates.io/crates/postgres-types) for `IStr` and `MowStr`, used by `tokio-postgres`, interning on decode
+//! - `redis` - `ToRedisArgs` and `FromRedisValue` of [redis](https://crates.io/crates/redis) for `IStr` and `MowStr`, interning replies
+//! - `http` - Conversions between `IStr` and `HeaderName`, `HeaderValue` and `Method` of [http](https://crates.io/crates/http), and `http::get_interned` interning header values
+//! - `smol_str` - Conversions between `IStr`, `MowStr` and `SmolStr` of [smol_str](https://crates.io/crates/smol_str)
+//! - `compact_str` - Conversions between `IStr`, `MowStr` and `CompactString` of [compact_str](https://crates.io/crates/compact_str)
+//! - `string_cache` - Conversions between `IStr`, `MowStr` and `Atom` of [string_cache](https://crates.io/crates/string_cache)
+//! - `lasso` - `Pool::import_rodeo` and `IStr::from_rodeo` for migrating from the `Rodeo` of [lasso](https://crates.io/crates/lasso)
+//! - `arbitrary` - `Arbitrary` of [arbitrary](https://crates.io/crates/arbitrary) for `IStr`, `MowStr`, `IOsStr` and `MowOsStr`
+//! - `proptest` - `Arbitrary` of [proptest](https://crates.io/crates/proptest) for `IStr`, `MowStr`, `IOsStr` and `MowOsStr`
+//!
+//! # Loom
+//! Built with `RUSTFLAGS="--cfg loom"` the pools lock and count with [loom](https://crates.io/crates/loom),
+//! so pools made inside `loom::model` can be model checked, the global pools can't be used then.
+//! `RUSTFLAGS="--cfg loom" cargo test --lib test_loom` runs the models of this crate
+
+#[cfg(all(
+    feature = "single-thread",
+    any(feature = "background-gc", feature = "rayon")
+))]
+compile_error!("the `single-thread` feature can't be combined with `background-gc` or `rayon`");
 
+#[macro_use]
+mod macros;
+
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+mod arbitrary;
+#[cfg(feature = "string_cache")]
+mod atom;
+mod builder;
+#[cfg(feature = "capi")]
+pub mod capi;
+mod hasher;
+#[cfg(feature = "get-size")]
+mod heap_size;
+#[cfg(feature = "http")]
+pub mod http;
+mod i_bytes;
+mod i_c_str;
 mod i_os_str;
+mod i_path;
+mod inline;
 pub mod intern;
+mod iref;
 mod istr;
+#[cfg(feature = "schemars")]
+mod json_schema;
+#[cfg(feature = "lasso")]
+mod lasso;
+mod lines;
+mod local;
+mod mow_bytes;
+mod mow_c_str;
 mod mow_os_str;
+mod mow_path;
 mod mow_str;
+#[cfg(feature = "unicode-normalization")]
+mod nfc;
+#[cfg(feature = "rayon")]
+mod par;
 pub mod pool;
-pub use intern::{Interning, Muterning};
+#[cfg(feature = "postgres")]
+mod postgres;
+mod prc;
+#[cfg(feature = "redis")]
+mod redis;
+mod scope;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(any(feature = "smol_str", feature = "compact_str"))]
+mod small_str;
+#[cfg(feature = "sqlx")]
+mod sql;
+mod stream;
+mod symbol;
+mod sync;
+mod transform;
+pub use builder::IStrBuilder;
+pub use hasher::{BuildIdentityHasher, IStrKey, IStrMap, IStrSet, IdentityHasher};
+pub use intern::{Interning, InterningIteratorExt, Muterning};
+pub use iref::{of, IRef, Interner};
 pub use istr::*;
+pub use lines::{intern_lines, InternLines};
+pub use local::PStr;
+#[cfg(feature = "rayon")]
+pub use par::par_intern;
+pub use scope::{scope, InternScope};
+pub use symbol::Symbol;
+
+#[cfg(feature = "macros")]
+pub use pstr_macros::{symbols, Interning};
 
 pub use mow_str::*;
 
+/// Intern every string of `iter` into the global string pool in one pass, see [`Pool::prime`](pool::Pool::prime)
+///
+/// # Example
+/// ```
+/// pstr::prime(["GET", "POST", "PUT", "DELETE"]);
+/// assert!(pstr::IStr::get_existing("POST").is_some());
+/// ```
+#[inline]
+pub fn prime<I>(iter: I)
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    pool::with_str_pool(|p| p.prime(iter))
+}
+
+/// Intern every string of `v`, equal strings end up sharing one allocation  
+///
+/// A string is only copied into the pool if it is new, strings already in the pool are just dropped
+///
+/// # Example
+/// ```
+/// let v = vec![
+///     String::from("application/json"),
+///     String::from("text/plain"),
+///     String::from("application/json"),
+/// ];
+/// let v = pstr::dedup_into_istrs(v);
+/// assert_eq!(v[0].as_ptr(), v[2].as_ptr());
+/// ```
+#[inline]
+pub fn dedup_into_istrs(v: Vec<String>) -> Vec<IStr> {
+    v.into_iter().map(IStr::from_string).collect()
+}
+
+/// Intern every string of `v`, equal strings end up sharing one allocation, see [`IStr::new_many`]
+#[inline]
+pub fn dedup_slice_into_istrs<S: AsRef<str>>(v: &[S]) -> Vec<IStr> {
+    IStr::new_many(v)
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use once_cell::sync::Lazy;
+}
+
 /// Utilities related to FFI bindings.
 pub mod ffi {
+    pub use crate::i_c_str::*;
     pub use crate::i_os_str::*;
+    pub use crate::mow_c_str::*;
     pub use crate::mow_os_str::*;
 }
+
+/// Interning byte strings.
+pub mod bytes {
+    pub use crate::i_bytes::*;
+    pub use crate::mow_bytes::*;
+}
+
+/// Interning filesystem paths.
+pub mod path {
+    pub use crate::i_path::*;
+    pub use crate::mow_path::*;
+}
@@ -0,0 +1,49 @@
+//! Interning lines of a reader
+
+use std::io::{self, BufRead};
+
+use crate::IStr;
+
+/// Intern every line of `reader`, reusing one buffer for all lines
+///
+/// Lines are split like [`BufRead::lines`], without the trailing `\n` or `\r\n`,
+/// a line already in the pool costs no allocation
+///
+/// # Example
+/// ```
+/// let text = "GET /api/v1/users\nPOST /\r\nGET /api/v1/users\n";
+/// let lines: Vec<_> = pstr::intern_lines(text.as_bytes()).map(Result::unwrap).collect();
+/// assert_eq!(lines, ["GET /api/v1/users", "POST /", "GET /api/v1/users"]);
+/// assert_eq!(lines[0].as_ptr(), lines[2].as_ptr());
+/// ```
+#[inline]
+pub fn intern_lines<R: BufRead>(reader: R) -> InternLines<R> {
+    InternLines {
+        reader,
+        buf: String::new(),
+    }
+}
+
+/// Iterator returned by [`intern_lines`]
+#[derive(Debug)]
+pub struct InternLines<R> {
+    reader: R,
+    buf: String,
+}
+
+impl<R: BufRead> Iterator for InternLines<R> {
+    type Item = io::Result<IStr>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buf.clear();
+        match self.reader.read_line(&mut self.buf) {
+            Ok(0) => None,
+            Ok(_) => {
+                let line = self.buf.strip_suffix('\n').unwrap_or(&self.buf);
+                let line = line.strip_suffix('\r').unwrap_or(line);
+                Some(Ok(IStr::new(line)))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::{
+    borrow::Borrow,
+    fmt,
+    hash::{self, BuildHasher, Hash},
+    marker::PhantomData,
+    ops::Deref,
+};
+
+use crate::pool::{Intern, Pool};
+
+/// Immutable Interning String bound to a local pool  
+///
+/// The lifetime ties it to the pool that created it, so a private pool can be dropped wholesale once every `PStr` is gone
+///
+/// # Example
+/// ```
+/// # use pstr::pool::Pool;
+/// let pool = Pool::new();
+/// let a = pool.intern_str("hello");
+/// let b = pool.intern_str(String::from("hello"));
+/// assert_eq!(a, b);
+/// assert_eq!(a, "hello");
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct PStr<'p> {
+    intern: Intern<str>,
+    _pool: PhantomData<&'p Pool<str>>,
+}
+
+impl<S: BuildHasher + Clone> Pool<str, S> {
+    /// Make a `PStr` bound to this pool
+    #[inline]
+    pub fn intern_str(&self, s: impl AsRef<str>) -> PStr<'_> {
+        PStr {
+            intern: self.intern(s.as_ref()),
+            _pool: PhantomData,
+        }
+    }
+}
+
+impl PStr<'_> {
+    /// Extracts a string slice containing the entire `PStr`
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self.intern.get()
+    }
+}
+
+impl Deref for PStr<'_> {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for PStr<'_> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for PStr<'_> {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Hash for PStr<'_> {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl fmt::Debug for PStr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl PartialEq<str> for PStr<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for PStr<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for PStr<'_> {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_pool() {
+        let pool = Pool::new();
+        let a = pool.intern_str("asd");
+        let b = pool.intern_str("asd");
+        let c = pool.intern_str("123");
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert!(crate::IStr::new("asd").as_ptr() != a.as_ptr());
+    }
+}
@@ -0,0 +1,157 @@
+//! Convenience macros
+
+/// Intern a string literal once per call site, then clone the cached `IStr` on later executions  
+///
+/// Repeated executions skip hashing and the pool lookup entirely,
+/// with the `single-thread` feature the `IStr` is cached per thread
+///
+/// # Example
+/// ```
+/// # use pstr::{istr, IStr};
+/// for _ in 0..3 {
+///     let s: IStr = istr!("keyword");
+///     assert_eq!(s, "keyword");
+/// }
+/// ```
+#[cfg(not(feature = "single-thread"))]
+#[macro_export]
+macro_rules! istr {
+    ($s:expr) => {{
+        static S: $crate::__private::Lazy<$crate::IStr> =
+            $crate::__private::Lazy::new(|| $crate::IStr::from_static($s));
+        ::std::clone::Clone::clone(&*S)
+    }};
+}
+
+/// Intern a string literal once per call site, then clone the cached `IStr` on later executions  
+///
+/// Repeated executions skip hashing and the pool lookup entirely,
+/// with the `single-thread` feature the `IStr` is cached per thread
+///
+/// # Example
+/// ```
+/// # use pstr::{istr, IStr};
+/// for _ in 0..3 {
+///     let s: IStr = istr!("keyword");
+///     assert_eq!(s, "keyword");
+/// }
+/// ```
+#[cfg(feature = "single-thread")]
+#[macro_export]
+macro_rules! istr {
+    ($s:expr) => {{
+        ::std::thread_local! {
+            static S: &'static $crate::IStr =
+                ::std::boxed::Box::leak(::std::boxed::Box::new($crate::IStr::from_static($s)));
+        }
+        S.with(|s| ::std::clone::Clone::clone(*s))
+    }};
+}
+
+/// Create a `IStr` using interpolation of runtime expressions, like `format!`  
+///
+/// See [`IStr::from_fmt`](crate::IStr::from_fmt)
+///
+/// # Example
+/// ```
+/// # use pstr::{iformat, IStr};
+/// let id = 42;
+/// let s: IStr = iformat!("user:{}", id);
+/// assert_eq!(s, "user:42");
+/// ```
+#[macro_export]
+macro_rules! iformat {
+    ($($arg:tt)*) => {
+        $crate::IStr::from_fmt(::std::format_args!($($arg)*))
+    };
+}
+
+/// Create a `HashMap<IStr, V>` from literal entries  
+///
+/// Keys must be constant expressions, each one is interned once at first use
+///
+/// # Example
+/// ```
+/// # use pstr::{imap, IStr};
+/// let map = imap! {
+///     "GET" => 1,
+///     "POST" => 2,
+/// };
+/// assert_eq!(map[&IStr::new("POST")], 2);
+/// ```
+#[macro_export]
+macro_rules! imap {
+    () => {
+        ::std::collections::HashMap::<$crate::IStr, _>::new()
+    };
+    ($($k:expr => $v:expr),+ $(,)?) => {{
+        let mut map = ::std::collections::HashMap::new();
+        $(map.insert($crate::istr!($k), $v);)+
+        map
+    }};
+}
+
+/// Create a `HashSet<IStr>` from literal entries  
+///
+/// Entries must be constant expressions, each one is interned once at first use
+///
+/// # Example
+/// ```
+/// # use pstr::{iset, IStr};
+/// let set = iset!["GET", "POST"];
+/// assert!(set.contains(&IStr::new("GET")));
+/// assert!(!set.contains(&IStr::new("PUT")));
+/// ```
+#[macro_export]
+macro_rules! iset {
+    () => {
+        ::std::collections::HashSet::<$crate::IStr>::new()
+    };
+    ($($k:expr),+ $(,)?) => {{
+        let mut set = ::std::collections::HashSet::new();
+        $(set.insert($crate::istr!($k));)+
+        set
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::IStr;
+
+    #[test]
+    fn test_imap() {
+        let f = || imap! { "a" => 1, "b" => 2 };
+        let (a, b) = (f(), f());
+        assert_eq!(a.len(), 2);
+        assert_eq!(a, b);
+        assert_eq!(a.get(&IStr::new("a")), Some(&1));
+        let empty: std::collections::HashMap<IStr, i32> = imap! {};
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_istr() {
+        let f = || istr!("test_istr_per_call_site");
+        assert_eq!(f().as_ptr(), f().as_ptr());
+        assert_eq!(f(), IStr::new("test_istr_per_call_site"));
+    }
+
+    #[test]
+    fn test_iformat() {
+        struct Nested;
+        impl std::fmt::Display for Nested {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(&iformat!("in{}", 1))
+            }
+        }
+        assert_eq!(iformat!("{}-{}", Nested, 2), "in1-2");
+        assert_eq!(iformat!("plain"), "plain");
+    }
+
+    #[test]
+    fn test_iset() {
+        let s = iset!["a", "b", "a"];
+        assert_eq!(s.len(), 2);
+        assert!(s.contains(&IStr::new("b")));
+    }
+}
@@ -0,0 +1,520 @@
+use std::{
+    borrow::{Borrow, BorrowMut},
+    hash::{self, Hash},
+    io,
+    iter::{Extend, FromIterator},
+    ops::{Deref, DerefMut},
+    rc::Rc,
+    sync::Arc,
+};
+
+use crate::{
+    bytes::IBytes,
+    intern::{Interned, Muterned},
+};
+
+#[derive(Debug, Eq, Ord, PartialOrd)]
+enum MowBytesInner {
+    I(IBytes),
+    M(Option<Vec<u8>>),
+}
+
+type Inner = MowBytesInner;
+
+impl PartialEq for MowBytesInner {
+    fn eq(&self, other: &Self) -> bool {
+        match self {
+            Self::I(s) => match other {
+                Self::I(o) => s == o,
+                Self::M(o) => o.as_deref().unwrap() == s.deref(),
+            },
+            Self::M(s) => match other {
+                Self::I(o) => s.as_deref().unwrap() == o.deref(),
+                Self::M(o) => s == o,
+            },
+        }
+    }
+}
+
+/// Mutable on Write Interning Bytes  
+///
+/// It will be auto switch to mutable when do modify operate  
+///
+/// Can call `.intern()` to save into intern pool
+///
+/// # Example
+/// ```
+/// # use pstr::bytes::MowBytes;
+/// let mut b = MowBytes::new(b"\x01");
+/// assert!(b.is_interned());
+///
+/// b.push(2);
+/// assert!(b.is_mutable());
+/// assert_eq!(b, [1, 2]);
+///
+/// b.intern();
+/// assert!(b.is_interned());
+/// ```
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct MowBytes(Inner);
+
+impl MowBytes {
+    /// Create a `MowBytes` from byte slice  
+    #[inline]
+    pub fn new(s: impl AsRef<[u8]>) -> Self {
+        Self(Inner::I(IBytes::new(s)))
+    }
+
+    /// Create a `MowBytes` from byte slice with mutable  
+    #[inline]
+    pub fn new_mut(s: impl Into<Vec<u8>>) -> Self {
+        Self(Inner::M(Some(s.into())))
+    }
+
+    /// Create a new empty `MowBytes` with mutable  
+    #[inline]
+    pub fn mut_empty() -> Self {
+        Self::new_mut(Vec::new())
+    }
+
+    /// Create a new empty `MowBytes` with a particular capacity and mutable  
+    #[inline]
+    pub fn mut_with_capacity(capacity: usize) -> Self {
+        Self::new_mut(Vec::with_capacity(capacity))
+    }
+
+    /// Create a `MowBytes` from `Vec<u8>`  
+    #[inline]
+    pub fn from_vec(s: Vec<u8>) -> Self {
+        Self(Inner::I(IBytes::from_vec(s)))
+    }
+
+    /// Create a `MowBytes` from `Vec<u8>` with mutable  
+    #[inline]
+    pub fn from_vec_mut(s: Vec<u8>) -> Self {
+        Self(Inner::M(Some(s)))
+    }
+
+    /// Create a `MowBytes` from `Box<[u8]>`  
+    #[inline]
+    pub fn from_boxed(s: Box<[u8]>) -> Self {
+        Self(Inner::I(IBytes::from_boxed(s)))
+    }
+
+    /// Create a `MowBytes` from `Arc<[u8]>`  
+    #[inline]
+    pub fn from_arc(s: Arc<[u8]>) -> Self {
+        Self(Inner::I(IBytes::from_arc(s)))
+    }
+
+    /// Create a `MowBytes` from `Rc<[u8]>`  
+    #[inline]
+    pub fn from_rc(s: Rc<[u8]>) -> Self {
+        Self(Inner::I(IBytes::from_rc(s)))
+    }
+
+    /// Create a `MowBytes` from `IBytes`  
+    #[inline]
+    pub fn from_ibytes(s: IBytes) -> Self {
+        Self(Inner::I(s))
+    }
+
+    /// Create a `MowBytes` from custom fn  
+    /// Entries are copied into the pool, so `to_arc` is never called
+    #[deprecated(note = "`to_arc` is never called, entries are copied into the pool, use `new`")]
+    #[inline]
+    pub fn from_to_arc<S: AsRef<[u8]>>(s: S, _to_arc: impl FnOnce(S) -> Arc<[u8]>) -> Self {
+        Self(Inner::I(IBytes::new(s)))
+    }
+}
+
+impl MowBytes {
+    /// Save the current state to the intern pool  
+    /// Do nothing if already in the pool  
+    #[inline]
+    pub fn intern(&mut self) {
+        let s = match &mut self.0 {
+            Inner::I(_) => return,
+            Inner::M(s) => s.take().unwrap(),
+        };
+        *self = Self::from_vec(s);
+    }
+
+    /// Get a mutable clone of the bytes on the pool  
+    /// Do nothing if already mutable  
+    #[inline]
+    pub fn to_mut(&mut self) {
+        let s = match &mut self.0 {
+            Inner::I(v) => v.to_vec(),
+            Inner::M(_) => return,
+        };
+        *self = Self::from_vec_mut(s);
+    }
+
+    /// Switch to mutable and return a mutable reference  
+    #[inline]
+    pub fn mutdown(&mut self) -> &mut Vec<u8> {
+        self.to_mut();
+        match &mut self.0 {
+            Inner::I(_) => panic!("never"),
+            Inner::M(v) => v.as_mut().unwrap(),
+        }
+    }
+
+    /// Do nothing if already mutable  
+    #[inline]
+    pub fn to_mut_by(&mut self, f: impl FnOnce(&mut IBytes) -> Vec<u8>) {
+        let s = match &mut self.0 {
+            Inner::I(v) => f(v),
+            Inner::M(_) => return,
+        };
+        *self = Self::from_vec_mut(s);
+    }
+
+    /// Swap internal Vec  
+    /// Return `None` if self is interned  
+    pub fn swap_mut(&mut self, s: Vec<u8>) -> Option<Vec<u8>> {
+        let r = match &mut self.0 {
+            Inner::I(_) => None,
+            Inner::M(s) => Some(s.take().unwrap()),
+        };
+        *self = Self::from_vec_mut(s);
+        r
+    }
+
+    /// Swap internal Vec when self is mutable  
+    /// Do nothing if self is interned  
+    /// Return `None` if self is interned  
+    pub fn try_swap_mut(&mut self, s: Vec<u8>) -> Option<Vec<u8>> {
+        let r = match &mut self.0 {
+            Inner::I(_) => None,
+            Inner::M(s) => Some(s.take().unwrap()),
+        };
+        if r.is_some() {
+            *self = Self::from_vec_mut(s);
+        }
+        r
+    }
+
+    /// Check if it is in intern pool  
+    #[inline]
+    pub fn is_interned(&self) -> bool {
+        matches!(&self.0, Inner::I(_))
+    }
+
+    /// Check if it is mutable  
+    #[inline]
+    pub fn is_mutable(&self) -> bool {
+        matches!(&self.0, Inner::M(_))
+    }
+
+    /// Try get `IBytes`
+    #[inline]
+    pub fn try_ibytes(&self) -> Option<&IBytes> {
+        match &self.0 {
+            Inner::I(v) => Some(v),
+            Inner::M(_) => None,
+        }
+    }
+
+    /// Try get `Vec<u8>`
+    #[inline]
+    pub fn try_vec(&self) -> Option<&Vec<u8>> {
+        match &self.0 {
+            Inner::I(_) => None,
+            Inner::M(v) => Some(v.as_ref().unwrap()),
+        }
+    }
+
+    /// Make a `IBytes`  
+    #[inline]
+    pub fn into_ibytes(&self) -> IBytes {
+        match &self.0 {
+            Inner::I(v) => v.clone(),
+            Inner::M(s) => s.as_ref().unwrap().into(),
+        }
+    }
+}
+
+impl MowBytes {
+    /// Extracts a byte slice containing the entire `MowBytes`
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.deref()
+    }
+
+    /// Switch to mutable and returns a mutable byte slice.
+    #[inline]
+    pub fn as_mut_bytes(&mut self) -> &mut [u8] {
+        self.mutdown()
+    }
+
+    /// Convert to `Vec<u8>`  
+    #[inline]
+    pub fn into_vec(self) -> Vec<u8> {
+        match self.0 {
+            Inner::I(v) => v.to_vec(),
+            Inner::M(v) => v.unwrap(),
+        }
+    }
+
+    /// Convert to `Box<[u8]>`  
+    #[inline]
+    pub fn into_boxed_bytes(self) -> Box<[u8]> {
+        match self.0 {
+            Inner::I(v) => v.into_boxed_bytes(),
+            Inner::M(v) => v.unwrap().into_boxed_slice(),
+        }
+    }
+}
+
+impl MowBytes {
+    /// Appends a byte to the end of this `MowBytes`
+    #[inline]
+    pub fn push(&mut self, b: u8) {
+        self.mutdown().push(b)
+    }
+
+    /// Appends all bytes in a slice to the end of this `MowBytes`
+    #[inline]
+    pub fn extend_from_slice(&mut self, other: &[u8]) {
+        self.mutdown().extend_from_slice(other)
+    }
+
+    /// Removes the last byte and returns it, or [`None`] if it is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<u8> {
+        self.mutdown().pop()
+    }
+
+    /// Shortens this `MowBytes` to the specified length.
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        self.mutdown().truncate(len)
+    }
+
+    /// Truncates this `MowBytes`, removing all contents.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.mutdown().clear()
+    }
+
+    /// Reserves capacity for at least `additional` more bytes.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.mutdown().reserve(additional)
+    }
+}
+
+unsafe impl Interned for MowBytes {}
+unsafe impl Muterned for MowBytes {}
+
+impl Clone for MowBytes {
+    fn clone(&self) -> Self {
+        match &self.0 {
+            Inner::I(v) => Self::from_ibytes(v.clone()),
+            Inner::M(v) => Self::from_vec(v.clone().unwrap()),
+        }
+    }
+}
+
+impl Deref for MowBytes {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_ref()
+    }
+}
+
+impl DerefMut for MowBytes {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut()
+    }
+}
+
+impl AsRef<[u8]> for MowBytes {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        match &self.0 {
+            Inner::I(v) => v.as_ref(),
+            Inner::M(v) => v.as_ref().unwrap(),
+        }
+    }
+}
+
+impl AsMut<[u8]> for MowBytes {
+    #[inline]
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.mutdown()
+    }
+}
+
+impl AsMut<Vec<u8>> for MowBytes {
+    #[inline]
+    fn as_mut(&mut self) -> &mut Vec<u8> {
+        self.mutdown()
+    }
+}
+
+impl Hash for MowBytes {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.deref().hash(state)
+    }
+}
+
+impl Borrow<[u8]> for MowBytes {
+    #[inline]
+    fn borrow(&self) -> &[u8] {
+        self.deref()
+    }
+}
+
+impl BorrowMut<[u8]> for MowBytes {
+    #[inline]
+    fn borrow_mut(&mut self) -> &mut [u8] {
+        self.deref_mut()
+    }
+}
+
+impl Extend<u8> for MowBytes {
+    #[inline]
+    fn extend<T: IntoIterator<Item = u8>>(&mut self, iter: T) {
+        self.mutdown().extend(iter)
+    }
+}
+
+impl<'a> Extend<&'a u8> for MowBytes {
+    #[inline]
+    fn extend<T: IntoIterator<Item = &'a u8>>(&mut self, iter: T) {
+        self.mutdown().extend(iter)
+    }
+}
+
+impl FromIterator<u8> for MowBytes {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = u8>>(iter: T) -> Self {
+        Self::from_vec(Vec::from_iter(iter))
+    }
+}
+
+impl io::Write for MowBytes {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl From<&[u8]> for MowBytes {
+    #[inline]
+    fn from(s: &[u8]) -> Self {
+        Self::new(s)
+    }
+}
+
+impl From<Vec<u8>> for MowBytes {
+    #[inline]
+    fn from(s: Vec<u8>) -> Self {
+        Self::from_vec(s)
+    }
+}
+
+impl From<Box<[u8]>> for MowBytes {
+    #[inline]
+    fn from(s: Box<[u8]>) -> Self {
+        Self::from_boxed(s)
+    }
+}
+
+impl From<Arc<[u8]>> for MowBytes {
+    #[inline]
+    fn from(s: Arc<[u8]>) -> Self {
+        Self::from_arc(s)
+    }
+}
+
+impl From<Rc<[u8]>> for MowBytes {
+    #[inline]
+    fn from(s: Rc<[u8]>) -> Self {
+        Self::from_rc(s)
+    }
+}
+
+impl From<IBytes> for MowBytes {
+    #[inline]
+    fn from(v: IBytes) -> Self {
+        Self::from_ibytes(v)
+    }
+}
+
+impl From<MowBytes> for IBytes {
+    fn from(v: MowBytes) -> Self {
+        match v.0 {
+            Inner::I(v) => v,
+            Inner::M(v) => IBytes::from_vec(v.unwrap()),
+        }
+    }
+}
+
+impl From<MowBytes> for Vec<u8> {
+    #[inline]
+    fn from(v: MowBytes) -> Self {
+        v.into_vec()
+    }
+}
+
+impl From<MowBytes> for Box<[u8]> {
+    #[inline]
+    fn from(v: MowBytes) -> Self {
+        v.into_boxed_bytes()
+    }
+}
+
+impl PartialEq<[u8]> for MowBytes {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.deref() == other
+    }
+}
+
+impl PartialEq<&[u8]> for MowBytes {
+    fn eq(&self, other: &&[u8]) -> bool {
+        self.deref() == *other
+    }
+}
+
+impl<const N: usize> PartialEq<[u8; N]> for MowBytes {
+    fn eq(&self, other: &[u8; N]) -> bool {
+        self.deref() == other
+    }
+}
+
+impl PartialEq<Vec<u8>> for MowBytes {
+    fn eq(&self, other: &Vec<u8>) -> bool {
+        self.deref() == other.as_slice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test() {
+        let mut b = MowBytes::new(b"a");
+        assert!(b.is_interned());
+        b.write_all(b"bc").unwrap();
+        assert!(b.is_mutable());
+        assert_eq!(b, *b"abc");
+        b.intern();
+        assert_eq!(b.try_ibytes(), Some(&IBytes::new(b"abc")));
+    }
+}
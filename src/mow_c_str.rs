@@ -0,0 +1,357 @@
+use std::{
+    borrow::Borrow,
+    ffi::{CStr, CString},
+    hash::{self, Hash},
+    mem,
+    ops::Deref,
+    rc::Rc,
+    sync::Arc,
+};
+
+use crate::{
+    ffi::ICStr,
+    intern::{Interned, Muterned},
+};
+
+#[derive(Debug, Eq, Ord, PartialOrd)]
+enum MowCStrInner {
+    I(ICStr),
+    M(Option<CString>),
+}
+
+type Inner = MowCStrInner;
+
+impl PartialEq for MowCStrInner {
+    fn eq(&self, other: &Self) -> bool {
+        match self {
+            Self::I(s) => match other {
+                Self::I(o) => s == o,
+                Self::M(o) => o.as_deref().unwrap() == s.deref(),
+            },
+            Self::M(s) => match other {
+                Self::I(o) => s.as_deref().unwrap() == o.deref(),
+                Self::M(o) => s == o,
+            },
+        }
+    }
+}
+
+/// Mutable on Write Interning CString  
+///
+/// It will be auto switch to mutable when do modify operate  
+///
+/// Can call `.intern()` to save into intern pool
+///
+/// # Example
+/// ```
+/// # use pstr::ffi::MowCStr;
+/// # use std::ffi::CString;
+/// let mut s = MowCStr::new(CString::new("hello").unwrap());
+/// assert!(s.is_interned());
+///
+/// s.push(CString::new(" world").unwrap());
+/// assert!(s.is_mutable());
+/// assert_eq!(s, CString::new("hello world").unwrap());
+///
+/// s.intern();
+/// assert!(s.is_interned());
+/// ```
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct MowCStr(Inner);
+
+impl MowCStr {
+    /// Create a `MowCStr` from CStr slice  
+    #[inline]
+    pub fn new(s: impl AsRef<CStr>) -> Self {
+        Self(Inner::I(ICStr::new(s)))
+    }
+
+    /// Create a `MowCStr` from CStr slice with mutable  
+    #[inline]
+    pub fn new_mut(s: impl Into<CString>) -> Self {
+        Self(Inner::M(Some(s.into())))
+    }
+
+    /// Create a new empty `MowCStr` with mutable  
+    #[inline]
+    pub fn mut_empty() -> Self {
+        Self::new_mut(CString::default())
+    }
+
+    /// Create a `MowCStr` from `CString`  
+    #[inline]
+    pub fn from_c_string(s: CString) -> Self {
+        Self(Inner::I(ICStr::from_c_string(s)))
+    }
+
+    /// Create a `MowCStr` from `CString` with mutable  
+    #[inline]
+    pub fn from_c_string_mut(s: CString) -> Self {
+        Self(Inner::M(Some(s)))
+    }
+
+    /// Create a `MowCStr` from `Box<CStr>`  
+    #[inline]
+    pub fn from_boxed(s: Box<CStr>) -> Self {
+        Self(Inner::I(ICStr::from_boxed(s)))
+    }
+
+    /// Create a `MowCStr` from `Arc<CStr>`  
+    #[inline]
+    pub fn from_arc(s: Arc<CStr>) -> Self {
+        Self(Inner::I(ICStr::from_arc(s)))
+    }
+
+    /// Create a `MowCStr` from `Rc<CStr>`  
+    #[inline]
+    pub fn from_rc(s: Rc<CStr>) -> Self {
+        Self(Inner::I(ICStr::from_rc(s)))
+    }
+
+    /// Create a `MowCStr` from `ICStr`  
+    #[inline]
+    pub fn from_i_c_str(s: ICStr) -> Self {
+        Self(Inner::I(s))
+    }
+
+    /// Create a `MowCStr` from custom fn  
+    /// Entries are copied into the pool, so `to_arc` is never called
+    #[deprecated(note = "`to_arc` is never called, entries are copied into the pool, use `new`")]
+    #[inline]
+    pub fn from_to_arc<S: AsRef<CStr>>(s: S, _to_arc: impl FnOnce(S) -> Arc<CStr>) -> Self {
+        Self(Inner::I(ICStr::new(s)))
+    }
+}
+
+impl MowCStr {
+    /// Save the current state to the intern pool  
+    /// Do nothing if already in the pool  
+    #[inline]
+    pub fn intern(&mut self) {
+        let s = match &mut self.0 {
+            Inner::I(_) => return,
+            Inner::M(s) => s.take().unwrap(),
+        };
+        *self = Self::from_c_string(s);
+    }
+
+    /// Get a mutable clone of the string on the pool  
+    /// Do nothing if already mutable  
+    #[inline]
+    pub fn to_mut(&mut self) {
+        let s = match &mut self.0 {
+            Inner::I(v) => CString::from(v.as_c_str()),
+            Inner::M(_) => return,
+        };
+        *self = Self::from_c_string_mut(s);
+    }
+
+    /// Switch to mutable and return a mutable reference  
+    #[inline]
+    pub fn mutdown(&mut self) -> &mut CString {
+        self.to_mut();
+        match &mut self.0 {
+            Inner::I(_) => panic!("never"),
+            Inner::M(v) => v.as_mut().unwrap(),
+        }
+    }
+
+    /// Check if it is in intern pool  
+    #[inline]
+    pub fn is_interned(&self) -> bool {
+        matches!(&self.0, Inner::I(_))
+    }
+
+    /// Check if it is mutable  
+    #[inline]
+    pub fn is_mutable(&self) -> bool {
+        matches!(&self.0, Inner::M(_))
+    }
+
+    /// Try get `ICStr`
+    #[inline]
+    pub fn try_i_c_str(&self) -> Option<&ICStr> {
+        match &self.0 {
+            Inner::I(v) => Some(v),
+            Inner::M(_) => None,
+        }
+    }
+
+    /// Try get `CString`
+    #[inline]
+    pub fn try_c_string(&self) -> Option<&CString> {
+        match &self.0 {
+            Inner::I(_) => None,
+            Inner::M(v) => Some(v.as_ref().unwrap()),
+        }
+    }
+
+    /// Make a `ICStr`  
+    #[inline]
+    pub fn into_i_c_str(&self) -> ICStr {
+        match &self.0 {
+            Inner::I(v) => v.clone(),
+            Inner::M(s) => s.as_ref().unwrap().into(),
+        }
+    }
+}
+
+impl MowCStr {
+    /// Extracts a CStr slice containing the entire `MowCStr`
+    #[inline]
+    pub fn as_c_str(&self) -> &CStr {
+        self.deref()
+    }
+
+    /// Convert to `CString`  
+    #[inline]
+    pub fn into_c_string(self) -> CString {
+        match self.0 {
+            Inner::I(v) => v.as_c_str().into(),
+            Inner::M(v) => v.unwrap(),
+        }
+    }
+
+    /// Convert to the bytes without the trailing nul  
+    #[inline]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.into_c_string().into_bytes()
+    }
+
+    /// Appends a given C string onto the end of this `MowCStr`  
+    #[inline]
+    pub fn push(&mut self, s: impl AsRef<CStr>) {
+        let c = self.mutdown();
+        let mut bytes = mem::take(c).into_bytes();
+        bytes.extend_from_slice(s.as_ref().to_bytes());
+        // a `CStr` has no interior nul, so neither does the concatenation
+        *c = unsafe { CString::from_vec_unchecked(bytes) };
+    }
+
+    /// Truncates this `MowCStr`, removing all contents.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self.mutdown() = CString::default();
+    }
+}
+
+unsafe impl Interned for MowCStr {}
+unsafe impl Muterned for MowCStr {}
+
+impl Clone for MowCStr {
+    fn clone(&self) -> Self {
+        match &self.0 {
+            Inner::I(v) => Self::from_i_c_str(v.clone()),
+            Inner::M(v) => Self::from_c_string(v.clone().unwrap()),
+        }
+    }
+}
+
+impl Deref for MowCStr {
+    type Target = CStr;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_ref()
+    }
+}
+
+impl AsRef<CStr> for MowCStr {
+    #[inline]
+    fn as_ref(&self) -> &CStr {
+        match &self.0 {
+            Inner::I(v) => v.as_ref(),
+            Inner::M(v) => v.as_ref().unwrap(),
+        }
+    }
+}
+
+impl Hash for MowCStr {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.deref().hash(state)
+    }
+}
+
+impl Borrow<CStr> for MowCStr {
+    #[inline]
+    fn borrow(&self) -> &CStr {
+        self.deref()
+    }
+}
+
+impl From<&CStr> for MowCStr {
+    #[inline]
+    fn from(s: &CStr) -> Self {
+        Self::new(s)
+    }
+}
+
+impl From<CString> for MowCStr {
+    #[inline]
+    fn from(s: CString) -> Self {
+        Self::from_c_string(s)
+    }
+}
+
+impl From<Box<CStr>> for MowCStr {
+    #[inline]
+    fn from(s: Box<CStr>) -> Self {
+        Self::from_boxed(s)
+    }
+}
+
+impl From<Arc<CStr>> for MowCStr {
+    #[inline]
+    fn from(s: Arc<CStr>) -> Self {
+        Self::from_arc(s)
+    }
+}
+
+impl From<Rc<CStr>> for MowCStr {
+    #[inline]
+    fn from(s: Rc<CStr>) -> Self {
+        Self::from_rc(s)
+    }
+}
+
+impl From<ICStr> for MowCStr {
+    #[inline]
+    fn from(v: ICStr) -> Self {
+        Self::from_i_c_str(v)
+    }
+}
+
+impl From<MowCStr> for ICStr {
+    fn from(v: MowCStr) -> Self {
+        match v.0 {
+            Inner::I(v) => v,
+            Inner::M(v) => ICStr::from_c_string(v.unwrap()),
+        }
+    }
+}
+
+impl From<MowCStr> for CString {
+    #[inline]
+    fn from(v: MowCStr) -> Self {
+        v.into_c_string()
+    }
+}
+
+impl PartialEq<CStr> for MowCStr {
+    fn eq(&self, other: &CStr) -> bool {
+        self.deref() == other
+    }
+}
+
+impl PartialEq<&CStr> for MowCStr {
+    fn eq(&self, other: &&CStr) -> bool {
+        self.deref() == *other
+    }
+}
+
+impl PartialEq<CString> for MowCStr {
+    fn eq(&self, other: &CString) -> bool {
+        self.deref() == other.as_c_str()
+    }
+}
@@ -1,8 +1,11 @@
 use std::{
-    borrow::{Borrow, BorrowMut},
+    borrow::{Borrow, BorrowMut, Cow},
+    cmp::Ordering,
     ffi::OsStr,
     ffi::OsString,
+    fmt,
     hash::{self, Hash},
+    iter::FromIterator,
     ops::{Add, AddAssign, Deref, DerefMut},
     path::Path,
     path::PathBuf,
@@ -13,9 +16,10 @@ use std::{
 use crate::{
     ffi::IOsStr,
     intern::{Interned, Muterned},
+    MowStr,
 };
 
-#[derive(Debug, Eq, Ord, PartialOrd)]
+#[derive(Debug, Eq)]
 enum MowOsStrInner {
     I(IOsStr),
     M(Option<OsString>),
@@ -38,6 +42,28 @@ impl PartialEq for MowOsStrInner {
     }
 }
 
+impl PartialOrd for MowOsStrInner {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MowOsStrInner {
+    /// Compares the contents like `PartialEq`, whichever way each side is stored
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_os_str().cmp(other.as_os_str())
+    }
+}
+
+impl MowOsStrInner {
+    fn as_os_str(&self) -> &OsStr {
+        match self {
+            Self::I(v) => v,
+            Self::M(v) => v.as_ref().unwrap(),
+        }
+    }
+}
+
 /// Mutable on Write Interning OsString  
 ///
 /// It will be auto switch to mutable when do modify operate  
@@ -144,9 +170,11 @@ impl MowOsStr {
     }
 
     /// Create a `MowOsStr` from custom fn  
+    /// Entries are copied into the pool, so `to_arc` is never called
+    #[deprecated(note = "`to_arc` is never called, entries are copied into the pool, use `new`")]
     #[inline]
-    pub fn from_to_arc<S: AsRef<OsStr>>(s: S, to_arc: impl FnOnce(S) -> Arc<OsStr>) -> Self {
-        Self(Inner::I(IOsStr::from_to_arc(s, to_arc)))
+    pub fn from_to_arc<S: AsRef<OsStr>>(s: S, _to_arc: impl FnOnce(S) -> Arc<OsStr>) -> Self {
+        Self(Inner::I(IOsStr::new(s)))
     }
 }
 
@@ -312,6 +340,30 @@ impl MowOsStr {
             Inner::M(v) => v.unwrap().into_boxed_os_str(),
         }
     }
+
+    /// Convert to `MowStr` if it is valid unicode, the original is returned otherwise  
+    /// A interned string stays interned, moving to the string pool, a mutable one keeps its buffer
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::ffi::MowOsStr;
+    /// let s = MowOsStr::new("hello");
+    /// assert_eq!(s.into_string().unwrap(), "hello");
+    /// ```
+    #[inline]
+    pub fn into_string(self) -> Result<MowStr, MowOsStr> {
+        match self.0 {
+            Inner::I(v) => match v.to_istr() {
+                Some(s) => Ok(MowStr::from_istr(s)),
+                None => Err(Self::from_i_os_str(v)),
+            },
+            Inner::M(v) => v
+                .unwrap()
+                .into_string()
+                .map(MowStr::from_string_mut)
+                .map_err(Self::from_os_string_mut),
+        }
+    }
 }
 
 impl MowOsStr {
@@ -358,6 +410,14 @@ impl Clone for MowOsStr {
     }
 }
 
+impl Default for MowOsStr {
+    /// A interned empty string
+    #[inline]
+    fn default() -> Self {
+        Self::from_i_os_str(IOsStr::default())
+    }
+}
+
 impl Deref for MowOsStr {
     type Target = OsStr;
 
@@ -412,6 +472,14 @@ impl Hash for MowOsStr {
     }
 }
 
+/// Displays lossily, invalid unicode is replaced with `U+FFFD`
+impl fmt::Display for MowOsStr {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_string_lossy(), f)
+    }
+}
+
 impl Borrow<OsStr> for MowOsStr {
     #[inline]
     fn borrow(&self) -> &OsStr {
@@ -489,6 +557,54 @@ impl From<PathBuf> for MowOsStr {
     }
 }
 
+impl<'a> Extend<&'a OsStr> for MowOsStr {
+    #[inline]
+    fn extend<T: IntoIterator<Item = &'a OsStr>>(&mut self, iter: T) {
+        self.mutdown().extend(iter)
+    }
+}
+
+impl Extend<OsString> for MowOsStr {
+    #[inline]
+    fn extend<T: IntoIterator<Item = OsString>>(&mut self, iter: T) {
+        self.mutdown().extend(iter)
+    }
+}
+
+impl<'a> Extend<Cow<'a, OsStr>> for MowOsStr {
+    #[inline]
+    fn extend<T: IntoIterator<Item = Cow<'a, OsStr>>>(&mut self, iter: T) {
+        self.mutdown().extend(iter)
+    }
+}
+
+impl<'a> FromIterator<&'a OsStr> for MowOsStr {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = &'a OsStr>>(iter: T) -> Self {
+        Self::from_os_string(OsString::from_iter(iter))
+    }
+}
+
+impl FromIterator<OsString> for MowOsStr {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = OsString>>(iter: T) -> Self {
+        Self::from_os_string(OsString::from_iter(iter))
+    }
+}
+
+impl<'a> FromIterator<Cow<'a, OsStr>> for MowOsStr {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = Cow<'a, OsStr>>>(iter: T) -> Self {
+        Self::from_os_string(OsString::from_iter(iter))
+    }
+}
+
+impl From<&Path> for MowOsStr {
+    fn from(s: &Path) -> Self {
+        Self::new(s.as_os_str())
+    }
+}
+
 impl From<MowOsStr> for OsString {
     fn from(v: MowOsStr) -> Self {
         match v.0 {
@@ -498,6 +614,12 @@ impl From<MowOsStr> for OsString {
     }
 }
 
+impl From<MowOsStr> for PathBuf {
+    fn from(v: MowOsStr) -> Self {
+        OsString::from(v).into()
+    }
+}
+
 impl From<MowOsStr> for Box<OsStr> {
     fn from(v: MowOsStr) -> Self {
         v.deref().into()
@@ -564,9 +686,46 @@ impl PartialEq<String> for MowOsStr {
     }
 }
 
+impl PartialEq<Path> for MowOsStr {
+    fn eq(&self, other: &Path) -> bool {
+        self.deref() == other.as_os_str()
+    }
+}
+
+impl PartialEq<&Path> for MowOsStr {
+    fn eq(&self, other: &&Path) -> bool {
+        self.deref() == other.as_os_str()
+    }
+}
+
+impl PartialEq<PathBuf> for MowOsStr {
+    fn eq(&self, other: &PathBuf) -> bool {
+        self.deref() == other.as_os_str()
+    }
+}
+
+impl PartialEq<IOsStr> for MowOsStr {
+    /// Compares the handles if interned, the contents otherwise
+    #[inline]
+    fn eq(&self, other: &IOsStr) -> bool {
+        match &self.0 {
+            Inner::I(v) => v == other,
+            Inner::M(v) => v.as_ref().unwrap() == other.deref(),
+        }
+    }
+}
+
+impl PartialEq<MowOsStr> for IOsStr {
+    #[inline]
+    fn eq(&self, other: &MowOsStr) -> bool {
+        other == self
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::IStr;
 
     #[test]
     fn test() {
@@ -579,4 +738,87 @@ mod tests {
         s.mutdown().push("world");
         assert_eq!(s, "hello world");
     }
+
+    #[test]
+    fn test_ord_mixed() {
+        let interned = MowOsStr::new("aa");
+        let mutable = MowOsStr::new_mut("aa");
+        assert_eq!(interned.cmp(&mutable), Ordering::Equal);
+        assert_eq!(MowOsStr::new_mut("zz").cmp(&interned), Ordering::Greater);
+        assert_eq!(MowOsStr::new("zz").cmp(&mutable), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_eq_i_os_str() {
+        let i = IOsStr::new("compare with ios");
+        let mut m = MowOsStr::new("compare with ios");
+        assert_eq!(m, i);
+        assert_eq!(i, m);
+        m.push("!");
+        assert_ne!(i, m);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_bytes() {
+        let a = IOsStr::from_bytes(b"raw \xff bytes");
+        let b = IOsStr::from_vec(b"raw \xff bytes".to_vec());
+        assert_eq!(a, b);
+        assert_eq!(a.as_bytes(), b"raw \xff bytes");
+    }
+
+    #[test]
+    fn test_istr() {
+        let s = IOsStr::from(IStr::new("between the pools"));
+        assert_eq!(s, "between the pools");
+        assert_eq!(s.to_istr(), Some(IStr::new("between the pools")));
+        assert_eq!(s.to_istr_lossy(), "between the pools");
+    }
+
+    #[test]
+    fn test_into_string() {
+        let s = MowOsStr::new("into string").into_string().unwrap();
+        assert!(s.is_interned());
+        assert_eq!(s, "into string");
+        let s = MowOsStr::new_mut("into string").into_string().unwrap();
+        assert!(s.is_mutable());
+        #[cfg(unix)]
+        {
+            let s = MowOsStr::from_i_os_str(IOsStr::from_bytes(b"\xff"));
+            assert_eq!(s.into_string().unwrap_err(), IOsStr::from_bytes(b"\xff"));
+        }
+    }
+
+    #[test]
+    fn test_path() {
+        let path = Path::new("/tmp/pstr/path");
+        let m = MowOsStr::from(path);
+        let i = IOsStr::from(path);
+        assert_eq!(m, path);
+        assert_eq!(i, path);
+        assert_eq!(m, path.to_path_buf());
+        assert_eq!(i, path.to_path_buf());
+        assert_eq!(PathBuf::from(m), path);
+    }
+
+    #[test]
+    fn test_from_iter() {
+        let parts = [OsStr::new("from"), OsStr::new(" "), OsStr::new("parts")];
+        let i: IOsStr = parts.iter().copied().collect();
+        let mut m: MowOsStr = parts.iter().map(|&s| s.to_os_string()).collect();
+        assert_eq!(i, "from parts");
+        assert_eq!(m, i);
+        m.extend(vec![Cow::Borrowed(OsStr::new("!"))]);
+        assert!(m.is_mutable());
+        assert_eq!(m, "from parts!");
+    }
+
+    #[test]
+    fn test_default() {
+        let a = IOsStr::default();
+        let b = IOsStr::default();
+        assert!(a.is_empty());
+        assert!(std::ptr::eq(a.as_os_str(), b.as_os_str()));
+        assert!(MowOsStr::default().is_interned());
+    }
 }
@@ -0,0 +1,503 @@
+use std::{
+    borrow::Borrow,
+    ffi::{OsStr, OsString},
+    hash::{self, Hash},
+    ops::Deref,
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::Arc,
+};
+
+use crate::{
+    intern::{Interned, Muterned},
+    path::IPath,
+};
+
+#[derive(Debug, Eq, Ord, PartialOrd)]
+enum MowPathInner {
+    I(IPath),
+    M(Option<PathBuf>),
+}
+
+type Inner = MowPathInner;
+
+impl PartialEq for MowPathInner {
+    fn eq(&self, other: &Self) -> bool {
+        match self {
+            Self::I(s) => match other {
+                Self::I(o) => s == o,
+                Self::M(o) => o.as_deref().unwrap() == s.deref(),
+            },
+            Self::M(s) => match other {
+                Self::I(o) => s.as_deref().unwrap() == o.deref(),
+                Self::M(o) => s == o,
+            },
+        }
+    }
+}
+
+/// Mutable on Write Interning Path  
+///
+/// It will be auto switch to mutable when do modify operate  
+///
+/// Can call `.intern()` to save into intern pool
+///
+/// # Example
+/// ```
+/// # use pstr::path::MowPath;
+/// let mut p = MowPath::new("/usr");
+/// assert!(p.is_interned());
+///
+/// p.push("lib");
+/// assert!(p.is_mutable());
+/// assert_eq!(p, std::path::Path::new("/usr/lib"));
+///
+/// p.intern();
+/// assert!(p.is_interned());
+/// ```
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct MowPath(Inner);
+
+impl MowPath {
+    /// Create a `MowPath` from path slice  
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::path::MowPath;
+    /// let p = MowPath::new("/usr/lib");
+    /// ```
+    #[inline]
+    pub fn new(s: impl AsRef<Path>) -> Self {
+        Self(Inner::I(IPath::new(s)))
+    }
+
+    /// Create a `MowPath` from path slice with mutable  
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::path::MowPath;
+    /// let p = MowPath::new_mut("/usr/lib");
+    /// assert!(p.is_mutable());
+    /// ```
+    #[inline]
+    pub fn new_mut(s: impl Into<PathBuf>) -> Self {
+        Self(Inner::M(Some(s.into())))
+    }
+
+    /// Create a new empty `MowPath` with mutable  
+    #[inline]
+    pub fn mut_empty() -> Self {
+        Self::new_mut(PathBuf::new())
+    }
+
+    /// Create a new empty `MowPath` with a particular capacity and mutable  
+    #[inline]
+    pub fn mut_with_capacity(capacity: usize) -> Self {
+        Self::new_mut(PathBuf::with_capacity(capacity))
+    }
+
+    /// Create a `MowPath` from `PathBuf`  
+    #[inline]
+    pub fn from_path_buf(s: PathBuf) -> Self {
+        Self(Inner::I(IPath::from_path_buf(s)))
+    }
+
+    /// Create a `MowPath` from `PathBuf` with mutable  
+    #[inline]
+    pub fn from_path_buf_mut(s: PathBuf) -> Self {
+        Self(Inner::M(Some(s)))
+    }
+
+    /// Create a `MowPath` from `Box<Path>`  
+    #[inline]
+    pub fn from_boxed(s: Box<Path>) -> Self {
+        Self(Inner::I(IPath::from_boxed(s)))
+    }
+
+    /// Create a `MowPath` from `Arc<Path>`  
+    #[inline]
+    pub fn from_arc(s: Arc<Path>) -> Self {
+        Self(Inner::I(IPath::from_arc(s)))
+    }
+
+    /// Create a `MowPath` from `Rc<Path>`  
+    #[inline]
+    pub fn from_rc(s: Rc<Path>) -> Self {
+        Self(Inner::I(IPath::from_rc(s)))
+    }
+
+    /// Create a `MowPath` from `IPath`  
+    #[inline]
+    pub fn from_ipath(s: IPath) -> Self {
+        Self(Inner::I(s))
+    }
+
+    /// Create a `MowPath` from custom fn  
+    /// Entries are copied into the pool, so `to_arc` is never called
+    #[deprecated(note = "`to_arc` is never called, entries are copied into the pool, use `new`")]
+    #[inline]
+    pub fn from_to_arc<S: AsRef<Path>>(s: S, _to_arc: impl FnOnce(S) -> Arc<Path>) -> Self {
+        Self(Inner::I(IPath::new(s)))
+    }
+}
+
+impl MowPath {
+    /// Save the current state to the intern pool  
+    /// Do nothing if already in the pool  
+    #[inline]
+    pub fn intern(&mut self) {
+        let s = match &mut self.0 {
+            Inner::I(_) => return,
+            Inner::M(s) => s.take().unwrap(),
+        };
+        *self = Self::from_path_buf(s);
+    }
+
+    /// Get a mutable clone of the path on the pool  
+    /// Do nothing if already mutable  
+    #[inline]
+    pub fn to_mut(&mut self) {
+        let s = match &mut self.0 {
+            Inner::I(v) => v.to_path_buf(),
+            Inner::M(_) => return,
+        };
+        *self = Self::from_path_buf_mut(s);
+    }
+
+    /// Switch to mutable and return a mutable reference  
+    #[inline]
+    pub fn mutdown(&mut self) -> &mut PathBuf {
+        self.to_mut();
+        match &mut self.0 {
+            Inner::I(_) => panic!("never"),
+            Inner::M(v) => v.as_mut().unwrap(),
+        }
+    }
+
+    /// Do nothing if already mutable  
+    #[inline]
+    pub fn to_mut_by(&mut self, f: impl FnOnce(&mut IPath) -> PathBuf) {
+        let s = match &mut self.0 {
+            Inner::I(v) => f(v),
+            Inner::M(_) => return,
+        };
+        *self = Self::from_path_buf_mut(s);
+    }
+
+    /// Swap internal PathBuf  
+    /// Return `None` if self is interned  
+    pub fn swap_mut(&mut self, s: PathBuf) -> Option<PathBuf> {
+        let r = match &mut self.0 {
+            Inner::I(_) => None,
+            Inner::M(s) => Some(s.take().unwrap()),
+        };
+        *self = Self::from_path_buf_mut(s);
+        r
+    }
+
+    /// Swap internal PathBuf when self is mutable  
+    /// Do nothing if self is interned  
+    /// Return `None` if self is interned  
+    pub fn try_swap_mut(&mut self, s: PathBuf) -> Option<PathBuf> {
+        let r = match &mut self.0 {
+            Inner::I(_) => None,
+            Inner::M(s) => Some(s.take().unwrap()),
+        };
+        if r.is_some() {
+            *self = Self::from_path_buf_mut(s);
+        }
+        r
+    }
+
+    /// Check if it is in intern pool  
+    #[inline]
+    pub fn is_interned(&self) -> bool {
+        matches!(&self.0, Inner::I(_))
+    }
+
+    /// Check if it is mutable  
+    #[inline]
+    pub fn is_mutable(&self) -> bool {
+        matches!(&self.0, Inner::M(_))
+    }
+
+    /// Try get `IPath`
+    #[inline]
+    pub fn try_ipath(&self) -> Option<&IPath> {
+        match &self.0 {
+            Inner::I(v) => Some(v),
+            Inner::M(_) => None,
+        }
+    }
+
+    /// Try get `PathBuf`
+    #[inline]
+    pub fn try_path_buf(&self) -> Option<&PathBuf> {
+        match &self.0 {
+            Inner::I(_) => None,
+            Inner::M(v) => Some(v.as_ref().unwrap()),
+        }
+    }
+
+    /// Make a `IPath`  
+    #[inline]
+    pub fn into_ipath(&self) -> IPath {
+        match &self.0 {
+            Inner::I(v) => v.clone(),
+            Inner::M(s) => s.as_ref().unwrap().into(),
+        }
+    }
+}
+
+impl MowPath {
+    /// Extracts a path slice containing the entire `MowPath`
+    #[inline]
+    pub fn as_path(&self) -> &Path {
+        self.deref()
+    }
+
+    /// Switch to mutable and returns a mutable `PathBuf` reference
+    #[inline]
+    pub fn as_mut_path_buf(&mut self) -> &mut PathBuf {
+        self.mutdown()
+    }
+
+    /// Convert to `PathBuf`  
+    #[inline]
+    pub fn into_path_buf(self) -> PathBuf {
+        match self.0 {
+            Inner::I(v) => v.to_path_buf(),
+            Inner::M(v) => v.unwrap(),
+        }
+    }
+
+    /// Convert to `Box<Path>`  
+    #[inline]
+    pub fn into_boxed_path(self) -> Box<Path> {
+        match self.0 {
+            Inner::I(v) => v.into_boxed_path(),
+            Inner::M(v) => v.unwrap().into_boxed_path(),
+        }
+    }
+}
+
+impl MowPath {
+    /// Extends `self` with `path`, see [`PathBuf::push`]
+    #[inline]
+    pub fn push(&mut self, path: impl AsRef<Path>) {
+        self.mutdown().push(path)
+    }
+
+    /// Truncates `self` to its parent, see [`PathBuf::pop`]
+    #[inline]
+    pub fn pop(&mut self) -> bool {
+        self.mutdown().pop()
+    }
+
+    /// Updates the file name, see [`PathBuf::set_file_name`]
+    #[inline]
+    pub fn set_file_name(&mut self, file_name: impl AsRef<OsStr>) {
+        self.mutdown().set_file_name(file_name)
+    }
+
+    /// Updates the extension, see [`PathBuf::set_extension`]
+    #[inline]
+    pub fn set_extension(&mut self, extension: impl AsRef<OsStr>) -> bool {
+        self.mutdown().set_extension(extension)
+    }
+}
+
+unsafe impl Interned for MowPath {}
+unsafe impl Muterned for MowPath {}
+
+impl Clone for MowPath {
+    fn clone(&self) -> Self {
+        match &self.0 {
+            Inner::I(v) => Self::from_ipath(v.clone()),
+            Inner::M(v) => Self::from_path_buf(v.clone().unwrap()),
+        }
+    }
+}
+
+impl Deref for MowPath {
+    type Target = Path;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_ref()
+    }
+}
+
+impl AsRef<Path> for MowPath {
+    #[inline]
+    fn as_ref(&self) -> &Path {
+        match &self.0 {
+            Inner::I(v) => v.as_ref(),
+            Inner::M(v) => v.as_ref().unwrap(),
+        }
+    }
+}
+
+impl AsRef<OsStr> for MowPath {
+    #[inline]
+    fn as_ref(&self) -> &OsStr {
+        self.as_path().as_os_str()
+    }
+}
+
+impl AsMut<PathBuf> for MowPath {
+    #[inline]
+    fn as_mut(&mut self) -> &mut PathBuf {
+        self.mutdown()
+    }
+}
+
+impl Hash for MowPath {
+    #[inline]
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.deref().hash(state)
+    }
+}
+
+impl Borrow<Path> for MowPath {
+    #[inline]
+    fn borrow(&self) -> &Path {
+        self.deref()
+    }
+}
+
+impl<P: AsRef<Path>> Extend<P> for MowPath {
+    #[inline]
+    fn extend<T: IntoIterator<Item = P>>(&mut self, iter: T) {
+        self.mutdown().extend(iter)
+    }
+}
+
+impl From<&Path> for MowPath {
+    #[inline]
+    fn from(s: &Path) -> Self {
+        Self::new(s)
+    }
+}
+
+impl From<PathBuf> for MowPath {
+    #[inline]
+    fn from(s: PathBuf) -> Self {
+        Self::from_path_buf(s)
+    }
+}
+
+impl From<&str> for MowPath {
+    #[inline]
+    fn from(s: &str) -> Self {
+        Self::new(s)
+    }
+}
+
+impl From<OsString> for MowPath {
+    #[inline]
+    fn from(s: OsString) -> Self {
+        Self::from_path_buf(s.into())
+    }
+}
+
+impl From<Box<Path>> for MowPath {
+    #[inline]
+    fn from(s: Box<Path>) -> Self {
+        Self::from_boxed(s)
+    }
+}
+
+impl From<Arc<Path>> for MowPath {
+    #[inline]
+    fn from(s: Arc<Path>) -> Self {
+        Self::from_arc(s)
+    }
+}
+
+impl From<Rc<Path>> for MowPath {
+    #[inline]
+    fn from(s: Rc<Path>) -> Self {
+        Self::from_rc(s)
+    }
+}
+
+impl From<IPath> for MowPath {
+    #[inline]
+    fn from(v: IPath) -> Self {
+        Self::from_ipath(v)
+    }
+}
+
+impl From<MowPath> for IPath {
+    fn from(v: MowPath) -> Self {
+        match v.0 {
+            Inner::I(v) => v,
+            Inner::M(v) => IPath::from_path_buf(v.unwrap()),
+        }
+    }
+}
+
+impl From<MowPath> for PathBuf {
+    #[inline]
+    fn from(v: MowPath) -> Self {
+        v.into_path_buf()
+    }
+}
+
+impl From<MowPath> for OsString {
+    #[inline]
+    fn from(v: MowPath) -> Self {
+        v.into_path_buf().into_os_string()
+    }
+}
+
+impl From<MowPath> for Box<Path> {
+    #[inline]
+    fn from(v: MowPath) -> Self {
+        v.into_boxed_path()
+    }
+}
+
+impl From<MowPath> for Arc<Path> {
+    fn from(v: MowPath) -> Self {
+        match v.0 {
+            Inner::I(v) => v.into(),
+            Inner::M(v) => v.unwrap().into(),
+        }
+    }
+}
+
+impl PartialEq<Path> for MowPath {
+    fn eq(&self, other: &Path) -> bool {
+        self.deref() == other
+    }
+}
+
+impl PartialEq<&Path> for MowPath {
+    fn eq(&self, other: &&Path) -> bool {
+        self.deref() == *other
+    }
+}
+
+impl PartialEq<PathBuf> for MowPath {
+    fn eq(&self, other: &PathBuf) -> bool {
+        self.deref() == other.as_path()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let mut p = MowPath::new("/a");
+        assert!(p.is_interned());
+        p.push("b");
+        assert!(p.is_mutable());
+        assert_eq!(p, Path::new("/a/b"));
+        p.intern();
+        assert_eq!(p.try_ipath(), Some(&IPath::new("/a/b")));
+    }
+}
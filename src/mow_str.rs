@@ -1,10 +1,13 @@
 use std::{
     borrow::{Borrow, BorrowMut, Cow},
+    cmp::Ordering,
     error::Error,
     ffi::{OsStr, OsString},
-    fmt::Write,
+    fmt::{self, Write},
     hash::{self, Hash},
+    io::Cursor,
     iter::{Extend, FromIterator},
+    mem,
     net::ToSocketAddrs,
     ops::{Add, AddAssign, Deref, DerefMut, Index, IndexMut, RangeBounds},
     path::{Path, PathBuf},
@@ -16,39 +19,75 @@ use std::{
 };
 
 use crate::{
+    inline::InlineStr,
     intern::{Interned, Muterned},
     IStr,
 };
 
-#[derive(Debug, Eq, Ord, PartialOrd)]
+/// Mutable strings up to this many bytes are stored inline instead of in a `String`  
+///
+/// The inline arm has to fit in the two words of `String` beside its capacity, which carries the niche of the enum,
+/// so `MowStr` stays as large as `String`. That leaves room for 15 bytes and the length on 64-bit targets, 7 on 32-bit,
+/// inlining 22 bytes would cost a extra word in every `MowStr`
+const INLINE_CAP: usize = 2 * mem::size_of::<usize>() - 1;
+
+#[derive(Debug, Eq)]
 enum MowStrInner {
     I(IStr),
-    M(Option<String>),
+    M(String),
+    /// Mutable and short enough to be stored inline
+    S(InlineStr<INLINE_CAP>),
 }
 
 type Inner = MowStrInner;
 
+impl MowStrInner {
+    #[inline]
+    fn as_str(&self) -> &str {
+        match self {
+            Self::I(v) => v,
+            Self::M(v) => v,
+            Self::S(v) => v.as_str(),
+        }
+    }
+}
+
 impl PartialEq for MowStrInner {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
-        match self {
-            Self::I(s) => match other {
-                Self::I(o) => s == o,
-                Self::M(o) => o.as_ref().unwrap() == s.deref(),
-            },
-            Self::M(s) => match other {
-                Self::I(o) => s.as_ref().unwrap() == o.deref(),
-                Self::M(o) => s == o,
-            },
+        match (self, other) {
+            (Self::I(s), Self::I(o)) => s == o,
+            _ => self.as_str() == other.as_str(),
         }
     }
 }
 
+impl PartialOrd for MowStrInner {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MowStrInner {
+    /// Compares the contents like `PartialEq`, whichever way each side is stored
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+/// A `std::io::Read`/`BufRead` cursor over the bytes of a `MowStr`, see [`MowStr::into_reader`]
+pub type MowStrReader = Cursor<MowStr>;
+
 /// Mutable on Write Interning String  
 ///
 /// It will be auto switch to mutable when do modify operate  
 ///
-/// Can call `.intern()` to save into intern pool
+/// Can call `.intern()` to save into intern pool  
+///
+/// Short mutable strings, up to 15 bytes on 64-bit targets, are stored inline
+/// and only move to a `String` when they grow or a `&mut String` is requested
 ///
 /// # Example
 /// ```
@@ -91,7 +130,7 @@ impl MowStr {
     /// ```
     #[inline]
     pub fn new_mut(s: impl Into<String>) -> Self {
-        Self(Inner::M(Some(s.into())))
+        Self(Inner::M(s.into()))
     }
 
     /// Create a new empty `MowStr` with mutable  
@@ -104,12 +143,15 @@ impl MowStr {
     /// ```
     #[inline]
     pub fn mut_empty() -> Self {
-        Self::new_mut(String::new())
+        Self(Inner::S(InlineStr::empty()))
     }
 
     /// Create a new empty `MowStr` with a particular capacity and mutable  
     #[inline]
     pub fn mut_with_capacity(capacity: usize) -> Self {
+        if capacity <= INLINE_CAP {
+            return Self::mut_empty();
+        }
         Self::new_mut(String::with_capacity(capacity))
     }
 
@@ -122,7 +164,7 @@ impl MowStr {
     /// Create a `MowStr` from `String` with mutable  
     #[inline]
     pub fn from_string_mut(s: String) -> Self {
-        Self(Inner::M(Some(s)))
+        Self(Inner::M(s))
     }
 
     /// Create a `MowStr` from `Box<str>`  
@@ -149,10 +191,18 @@ impl MowStr {
         Self(Inner::I(s))
     }
 
+    /// A interned empty string, see [`IStr::empty`]
+    #[inline]
+    pub const fn interned_empty() -> Self {
+        Self(Inner::I(IStr::empty()))
+    }
+
     /// Create a `MowStr` from custom fn  
+    /// Entries are copied into the pool, so `to_arc` is never called
+    #[deprecated(note = "`to_arc` is never called, entries are copied into the pool, use `new`")]
     #[inline]
-    pub fn from_to_arc<S: AsRef<str>>(s: S, to_arc: impl FnOnce(S) -> Arc<str>) -> Self {
-        Self(Inner::I(IStr::from_to_arc(s, to_arc)))
+    pub fn from_to_arc<S: AsRef<str>>(s: S, _to_arc: impl FnOnce(S) -> Arc<str>) -> Self {
+        Self(Inner::I(IStr::new(s)))
     }
 }
 
@@ -163,29 +213,65 @@ impl MowStr {
     pub fn intern(&mut self) {
         let s = match &mut self.0 {
             Inner::I(_) => return,
-            MowStrInner::M(s) => s.take().unwrap(),
+            MowStrInner::M(s) => mem::take(s),
+            MowStrInner::S(s) => {
+                let s = *s;
+                *self = Self::new(s.as_str());
+                return;
+            }
         };
         *self = Self::from_string(s);
     }
 
     /// Get a mutable clone of the string on the pool  
-    /// Do nothing if already mutable  
+    /// Do nothing if already mutable in a `String`, a inline string is moved into a `String`
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::MowStr;
+    /// let mut s = MowStr::new("short");
+    /// s.to_mut();
+    /// assert!(s.is_mutable());
+    /// assert_eq!(s.try_string().unwrap(), "short");
+    /// ```
     #[inline]
     pub fn to_mut(&mut self) {
-        let s = match &mut self.0 {
-            Inner::I(v) => v.to_string(),
-            Inner::M(_) => return,
-        };
-        *self = Self::from_string_mut(s);
+        self.mutdown();
+    }
+
+    /// Switch to mutable, storing the string inline if it is short enough
+    #[inline]
+    fn unintern(&mut self) {
+        if let Inner::I(v) = &self.0 {
+            self.0 = match InlineStr::new(v) {
+                Some(s) => Inner::S(s),
+                None => Inner::M(v.to_string()),
+            };
+        }
     }
 
     /// Switch to mutable and return a mutable reference  
+    /// A inline string is moved into a `String`
     #[inline]
     pub fn mutdown(&mut self) -> &mut String {
-        self.to_mut();
+        match &self.0 {
+            Inner::I(v) => self.0 = Inner::M(v.to_string()),
+            Inner::S(v) => self.0 = Inner::M(v.as_str().to_string()),
+            Inner::M(_) => {}
+        }
         match &mut self.0 {
-            Inner::I(_) => panic!("never"),
-            Inner::M(v) => v.as_mut().unwrap(),
+            Inner::M(v) => v,
+            _ => panic!("never"),
+        }
+    }
+
+    /// Switch to mutable and return the inline string if it is stored inline
+    #[inline]
+    fn inline_mut(&mut self) -> Option<&mut InlineStr<INLINE_CAP>> {
+        self.unintern();
+        match &mut self.0 {
+            Inner::S(v) => Some(v),
+            _ => None,
         }
     }
 
@@ -194,7 +280,7 @@ impl MowStr {
     pub fn to_mut_by(&mut self, f: impl FnOnce(&mut IStr) -> String) {
         let s = match &mut self.0 {
             Inner::I(v) => f(v),
-            Inner::M(_) => return,
+            Inner::M(_) | Inner::S(_) => return,
         };
         *self = Self::from_string_mut(s);
     }
@@ -204,7 +290,8 @@ impl MowStr {
     pub fn swap_mut(&mut self, s: String) -> Option<String> {
         let r = match &mut self.0 {
             Inner::I(_) => None,
-            MowStrInner::M(s) => Some(s.take().unwrap()),
+            MowStrInner::M(s) => Some(mem::take(s)),
+            MowStrInner::S(s) => Some(s.as_str().to_string()),
         };
         *self = Self::from_string_mut(s);
         r
@@ -216,7 +303,8 @@ impl MowStr {
     pub fn try_swap_mut(&mut self, s: String) -> Option<String> {
         let r = match &mut self.0 {
             Inner::I(_) => None,
-            MowStrInner::M(s) => Some(s.take().unwrap()),
+            MowStrInner::M(s) => Some(mem::take(s)),
+            MowStrInner::S(s) => Some(s.as_str().to_string()),
         };
         if r.is_some() {
             *self = Self::from_string_mut(s);
@@ -233,7 +321,7 @@ impl MowStr {
     /// Check if it is mutable  
     #[inline]
     pub fn is_mutable(&self) -> bool {
-        matches!(&self.0, Inner::M(_))
+        matches!(&self.0, Inner::M(_) | Inner::S(_))
     }
 
     /// Try get `IStr`
@@ -241,16 +329,17 @@ impl MowStr {
     pub fn try_istr(&self) -> Option<&IStr> {
         match &self.0 {
             Inner::I(v) => Some(v),
-            Inner::M(_) => None,
+            Inner::M(_) | Inner::S(_) => None,
         }
     }
 
-    /// Try get `String`
+    /// Try get `String`  
+    /// Return `None` if self is interned or stored inline
     #[inline]
     pub fn try_string(&self) -> Option<&String> {
         match &self.0 {
-            Inner::I(_) => None,
-            Inner::M(v) => Some(v.as_ref().unwrap()),
+            Inner::I(_) | Inner::S(_) => None,
+            Inner::M(v) => Some(v),
         }
     }
 
@@ -259,9 +348,10 @@ impl MowStr {
     pub fn into_istr(&self) -> IStr {
         match &self.0 {
             Inner::I(v) => v.clone(),
-            Inner::M(s) => s.as_ref().unwrap().into(),
+            Inner::M(s) => s.into(),
+            Inner::S(s) => s.as_str().into(),
         }
-    } 
+    }
 }
 
 impl MowStr {
@@ -302,6 +392,10 @@ impl MowStr {
     }
 
     /// Switch to mutable and returns a mutable `Vec<u8>` reference
+    ///
+    /// # Safety
+    ///
+    /// See [`String::as_mut_vec`], the bytes must remain valid UTF-8
     #[inline]
     pub unsafe fn as_mut_vec(&mut self) -> &mut Vec<u8> {
         self.mutdown().as_mut_vec()
@@ -312,16 +406,47 @@ impl MowStr {
     pub fn into_string(self) -> String {
         match self.0 {
             Inner::I(v) => v.to_string(),
-            Inner::M(v) => v.unwrap(),
+            Inner::M(v) => v,
+            Inner::S(v) => v.as_str().to_string(),
         }
     }
 
+    /// Convert to the bytes of the string, reusing the buffer if mutable  
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::MowStr;
+    /// let mut s = MowStr::new("hello");
+    /// s.push_str(" world");
+    /// assert_eq!(s.into_bytes(), b"hello world");
+    /// ```
+    #[inline]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.into_string().into_bytes()
+    }
+
+    /// Convert to a reader over the bytes without copying  
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::MowStr;
+    /// use std::io::Read;
+    /// let mut buf = String::new();
+    /// MowStr::new("hello").into_reader().read_to_string(&mut buf).unwrap();
+    /// assert_eq!(buf, "hello");
+    /// ```
+    #[inline]
+    pub fn into_reader(self) -> MowStrReader {
+        Cursor::new(self)
+    }
+
     /// Convert to `Box<str>`  
     #[inline]
     pub fn into_boxed_str(self) -> Box<str> {
         match self.0 {
             Inner::I(v) => v.into_boxed_str(),
-            Inner::M(v) => v.unwrap().into_boxed_str(),
+            Inner::M(v) => v.into_boxed_str(),
+            Inner::S(v) => v.as_str().into(),
         }
     }
 }
@@ -330,7 +455,13 @@ impl MowStr {
     /// Appends a given string slice onto the end of this `MowStr`  
     #[inline]
     pub fn push_str(&mut self, string: impl AsRef<str>) {
-        self.mutdown().push_str(string.as_ref())
+        let string = string.as_ref();
+        if let Some(s) = self.inline_mut() {
+            if s.push_str(string) {
+                return;
+            }
+        }
+        self.mutdown().push_str(string)
     }
 
     /// Ensures that this `MowStr`'s capacity is at least `additional` bytes larger than its length.  
@@ -371,10 +502,30 @@ impl MowStr {
         self.mutdown().shrink_to_fit()
     }
 
+    /// Returns the capacity in bytes of this `MowStr` if it is mutable, `None` if it is interned  
+    /// A short string stored inline has the inline capacity, it moves to a `String` when it grows past it
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::MowStr;
+    /// let mut s = MowStr::new("hello");
+    /// assert_eq!(s.capacity(), None);
+    /// s.reserve(64);
+    /// assert!(s.capacity().unwrap() >= 69);
+    /// ```
+    #[inline]
+    pub fn capacity(&self) -> Option<usize> {
+        match &self.0 {
+            Inner::I(_) => None,
+            Inner::M(v) => Some(v.capacity()),
+            Inner::S(_) => Some(INLINE_CAP),
+        }
+    }
+
     /// Appends the given [`char`] to the end of this `MowStr`.
     #[inline]
     pub fn push(&mut self, ch: char) {
-        self.mutdown().push(ch)
+        self.push_str(ch.encode_utf8(&mut [0; 4]))
     }
 
     /// Shortens this `MowStr` to the specified length.
@@ -390,7 +541,10 @@ impl MowStr {
     /// Panics if `new_len` does not lie on a [`char`] boundary.
     #[inline]
     pub fn truncate(&mut self, new_len: usize) {
-        self.mutdown().truncate(new_len)
+        match self.inline_mut() {
+            Some(s) => s.truncate(new_len),
+            None => self.mutdown().truncate(new_len),
+        }
     }
 
     /// Removes the last character from the string buffer and returns it.
@@ -398,7 +552,10 @@ impl MowStr {
     /// Returns [`None`] if this `MowStr` is empty.
     #[inline]
     pub fn pop(&mut self) -> Option<char> {
-        self.mutdown().pop()
+        match self.inline_mut() {
+            Some(s) => s.pop(),
+            None => self.mutdown().pop(),
+        }
     }
 
     /// Removes a [`char`] from this `MowStr` at a byte position and returns it.
@@ -476,7 +633,10 @@ impl MowStr {
     /// touch its capacity.
     #[inline]
     pub fn clear(&mut self) {
-        self.mutdown().clear()
+        match &mut self.0 {
+            Inner::M(v) => v.clear(),
+            _ => *self = Self::mut_empty(),
+        }
     }
 
     /// Creates a draining iterator that removes the specified range in the `MowStr`
@@ -515,11 +675,20 @@ impl Clone for MowStr {
     fn clone(&self) -> Self {
         match &self.0 {
             Inner::I(v) => Self::from_istr(v.clone()),
-            Inner::M(v) => Self::from_string(v.clone().unwrap()),
+            Inner::M(v) => Self::from_string(v.clone()),
+            Inner::S(v) => Self::new(v.as_str()),
         }
     }
 }
 
+impl Default for MowStr {
+    /// A interned empty string
+    #[inline]
+    fn default() -> Self {
+        Self::interned_empty()
+    }
+}
+
 impl Deref for MowStr {
     type Target = str;
 
@@ -548,17 +717,19 @@ impl FromStr for MowStr {
 impl AsRef<str> for MowStr {
     #[inline]
     fn as_ref(&self) -> &str {
-        match &self.0 {
-            Inner::I(v) => v.as_ref(),
-            Inner::M(v) => v.as_ref().unwrap(),
-        }
+        self.0.as_str()
     }
 }
 
 impl AsMut<str> for MowStr {
     #[inline]
     fn as_mut(&mut self) -> &mut str {
-        self.mutdown()
+        self.unintern();
+        match &mut self.0 {
+            Inner::I(_) => panic!("never"),
+            Inner::M(v) => v,
+            Inner::S(v) => v.as_mut_str(),
+        }
     }
 }
 
@@ -572,30 +743,21 @@ impl AsMut<String> for MowStr {
 impl AsRef<[u8]> for MowStr {
     #[inline]
     fn as_ref(&self) -> &[u8] {
-        match &self.0 {
-            Inner::I(v) => v.as_ref(),
-            Inner::M(v) => v.as_ref().unwrap().as_ref(),
-        }
+        self.0.as_str().as_ref()
     }
 }
 
 impl AsRef<OsStr> for MowStr {
     #[inline]
     fn as_ref(&self) -> &OsStr {
-        match &self.0 {
-            Inner::I(v) => v.as_ref(),
-            Inner::M(v) => v.as_ref().unwrap().as_ref(),
-        }
+        self.0.as_str().as_ref()
     }
 }
 
 impl AsRef<Path> for MowStr {
     #[inline]
     fn as_ref(&self) -> &Path {
-        match &self.0 {
-            Inner::I(v) => v.as_ref(),
-            Inner::M(v) => v.as_ref().unwrap().as_ref(),
-        }
+        self.0.as_str().as_ref()
     }
 }
 
@@ -692,7 +854,7 @@ impl Add<&str> for MowStr {
 
     #[inline]
     fn add(mut self, rhs: &str) -> Self::Output {
-        self.mutdown().push_str(rhs);
+        self.push_str(rhs);
         self
     }
 }
@@ -700,7 +862,75 @@ impl Add<&str> for MowStr {
 impl AddAssign<&str> for MowStr {
     #[inline]
     fn add_assign(&mut self, rhs: &str) {
-        self.mutdown().push_str(rhs);
+        self.push_str(rhs);
+    }
+}
+
+impl Add<IStr> for MowStr {
+    type Output = MowStr;
+
+    #[inline]
+    fn add(mut self, rhs: IStr) -> Self::Output {
+        self.push_str(rhs);
+        self
+    }
+}
+
+impl AddAssign<IStr> for MowStr {
+    #[inline]
+    fn add_assign(&mut self, rhs: IStr) {
+        self.push_str(rhs);
+    }
+}
+
+impl Add<String> for MowStr {
+    type Output = MowStr;
+
+    #[inline]
+    fn add(mut self, rhs: String) -> Self::Output {
+        self.push_str(rhs);
+        self
+    }
+}
+
+impl AddAssign<String> for MowStr {
+    #[inline]
+    fn add_assign(&mut self, rhs: String) {
+        self.push_str(rhs);
+    }
+}
+
+impl Add<&MowStr> for MowStr {
+    type Output = MowStr;
+
+    #[inline]
+    fn add(mut self, rhs: &MowStr) -> Self::Output {
+        self.push_str(rhs);
+        self
+    }
+}
+
+impl AddAssign<&MowStr> for MowStr {
+    #[inline]
+    fn add_assign(&mut self, rhs: &MowStr) {
+        self.push_str(rhs);
+    }
+}
+
+impl Add<char> for MowStr {
+    type Output = MowStr;
+
+    #[inline]
+    fn add(mut self, rhs: char) -> Self::Output {
+        self.push(rhs);
+        self
+    }
+}
+
+impl AddAssign<char> for MowStr {
+    #[inline]
+    fn add_assign(&mut self, rhs: char) {
+        self.push(rhs);
     }
 }
 
@@ -760,6 +990,13 @@ impl<'a> From<Cow<'a, str>> for MowStr {
     }
 }
 
+impl<'a> From<&Cow<'a, str>> for MowStr {
+    #[inline]
+    fn from(s: &Cow<'a, str>) -> Self {
+        Self::new(s)
+    }
+}
+
 impl From<char> for MowStr {
     #[inline]
     fn from(c: char) -> Self {
@@ -791,13 +1028,10 @@ impl Write for MowStr {
     }
 }
 
-impl ToString for MowStr {
+impl fmt::Display for MowStr {
     #[inline]
-    fn to_string(&self) -> String {
-        match &self.0 {
-            Inner::I(v) => v.to_string(),
-            Inner::M(v) => v.clone().unwrap(),
-        }
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.deref(), f)
     }
 }
 
@@ -855,7 +1089,8 @@ impl From<MowStr> for Box<str> {
     fn from(v: MowStr) -> Self {
         match &v.0 {
             Inner::I(v) => Self::from(v.deref()),
-            Inner::M(v) => Self::from(v.as_deref().unwrap()),
+            Inner::M(v) => Self::from(v.as_str()),
+            Inner::S(v) => Self::from(v.as_str()),
         }
     }
 }
@@ -865,7 +1100,8 @@ impl From<MowStr> for Vec<u8> {
     fn from(v: MowStr) -> Self {
         match &v.0 {
             Inner::I(v) => Self::from(v.deref()),
-            Inner::M(v) => Self::from(v.as_deref().unwrap()),
+            Inner::M(v) => Self::from(v.as_str()),
+            Inner::S(v) => Self::from(v.as_str()),
         }
     }
 }
@@ -875,7 +1111,8 @@ impl From<MowStr> for Arc<str> {
     fn from(v: MowStr) -> Self {
         match &v.0 {
             Inner::I(v) => Self::from(v.clone()),
-            Inner::M(v) => Self::from(v.clone().unwrap()),
+            Inner::M(v) => Self::from(v.clone()),
+            Inner::S(v) => Self::from(v.as_str()),
         }
     }
 }
@@ -885,7 +1122,8 @@ impl From<MowStr> for Rc<str> {
     fn from(v: MowStr) -> Self {
         match &v.0 {
             Inner::I(v) => Self::from(v.clone()),
-            Inner::M(v) => Self::from(v.clone().unwrap()),
+            Inner::M(v) => Self::from(v.clone()),
+            Inner::S(v) => Self::from(v.as_str()),
         }
     }
 }
@@ -909,7 +1147,8 @@ impl From<MowStr> for Box<dyn Error> {
     fn from(v: MowStr) -> Self {
         match &v.0 {
             Inner::I(v) => Self::from(v.clone()),
-            Inner::M(v) => Self::from(v.clone().unwrap()),
+            Inner::M(v) => Self::from(v.clone()),
+            Inner::S(v) => Self::from(v.as_str()),
         }
     }
 }
@@ -919,7 +1158,8 @@ impl From<MowStr> for Box<dyn Error + Send + Sync> {
     fn from(v: MowStr) -> Self {
         match &v.0 {
             Inner::I(v) => Self::from(v.clone()),
-            Inner::M(v) => Self::from(v.clone().unwrap()),
+            Inner::M(v) => Self::from(v.clone()),
+            Inner::S(v) => Self::from(v.as_str()),
         }
     }
 }
@@ -929,7 +1169,8 @@ impl From<MowStr> for OsString {
     fn from(v: MowStr) -> Self {
         match &v.0 {
             Inner::I(v) => Self::from(v.deref()),
-            Inner::M(v) => Self::from(v.as_ref().unwrap()),
+            Inner::M(v) => Self::from(v),
+            Inner::S(v) => Self::from(v.as_str()),
         }
     }
 }
@@ -939,7 +1180,8 @@ impl From<MowStr> for PathBuf {
     fn from(v: MowStr) -> Self {
         match &v.0 {
             Inner::I(v) => Self::from(v.deref()),
-            Inner::M(v) => Self::from(v.as_ref().unwrap()),
+            Inner::M(v) => Self::from(v),
+            Inner::S(v) => Self::from(v.as_str()),
         }
     }
 }
@@ -955,7 +1197,8 @@ impl From<MowStr> for IStr {
     fn from(v: MowStr) -> Self {
         match v.0 {
             Inner::I(v) => v,
-            Inner::M(v) => Self::from_string(v.unwrap()),
+            Inner::M(v) => Self::from_string(v),
+            Inner::S(v) => Self::new(v.as_str()),
         }
     }
 }
@@ -996,6 +1239,30 @@ impl PartialEq<OsString> for MowStr {
     }
 }
 
+impl<'a> PartialEq<Cow<'a, str>> for MowStr {
+    fn eq(&self, other: &Cow<'a, str>) -> bool {
+        self.deref() == other.as_ref()
+    }
+}
+
+impl PartialEq<IStr> for MowStr {
+    /// Compares the handles if interned, the contents otherwise
+    #[inline]
+    fn eq(&self, other: &IStr) -> bool {
+        match &self.0 {
+            Inner::I(v) => v == other,
+            _ => self.deref() == other.as_str(),
+        }
+    }
+}
+
+impl PartialEq<MowStr> for IStr {
+    #[inline]
+    fn eq(&self, other: &MowStr) -> bool {
+        other == self
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1037,4 +1304,117 @@ mod tests {
         assert!(a.is_mutable());
         assert_eq!(a, "asd123");
     }
+
+    #[test]
+    fn test_size() {
+        assert_eq!(std::mem::size_of::<MowStr>(), std::mem::size_of::<String>());
+    }
+
+    #[test]
+    fn test_inline() {
+        let mut a = MowStr::new("asd");
+        a.push_str("123");
+        a.push('4');
+        assert!(a.is_mutable());
+        assert!(a.try_string().is_none());
+        assert_eq!(a.pop(), Some('4'));
+        a.truncate(4);
+        assert_eq!(a, "asd1");
+        a.push_str("a longer string than fits inline");
+        assert_eq!(
+            a.try_string().unwrap(),
+            "asd1a longer string than fits inline"
+        );
+        a.clear();
+        assert!(a.is_empty());
+        a.intern();
+        assert_eq!(a.into_istr(), IStr::new(""));
+    }
+
+    #[test]
+    fn test_display() {
+        let mut a = MowStr::new("asd");
+        assert_eq!(format!("{}", a), "asd");
+        assert_eq!(format!("{:>5}", a.into_istr()), "  asd");
+        a.push_str("123");
+        assert_eq!(a.to_string(), "asd123");
+    }
+
+    #[test]
+    fn test_ord_mixed() {
+        let interned = MowStr::new("aa");
+        let mut inline = MowStr::mut_empty();
+        inline.push_str("aa");
+        let mut string = MowStr::new("aa");
+        string.mutdown();
+        let mut zz = MowStr::new("zz");
+        zz.mutdown();
+        assert_eq!(inline.cmp(&string), Ordering::Equal);
+        assert_eq!(interned.cmp(&inline), Ordering::Equal);
+        assert_eq!(interned.cmp(&string), Ordering::Equal);
+        assert_eq!(zz.cmp(&inline), Ordering::Greater);
+        assert_eq!(inline.cmp(&zz), Ordering::Less);
+
+        let mut map = std::collections::BTreeMap::new();
+        map.insert(string, 1);
+        assert_eq!(map.get(&inline), Some(&1));
+        assert_eq!(map.get(&interned), Some(&1));
+    }
+
+    #[test]
+    fn test_to_mut_inline() {
+        let mut s = MowStr::new("short");
+        s.push('!');
+        assert!(s.try_string().is_none());
+        s.to_mut();
+        assert_eq!(s.try_string().unwrap(), "short!");
+
+        let mut long = MowStr::new("longer than the inline capacity");
+        long.to_mut();
+        assert_eq!(
+            long.try_string().unwrap(),
+            "longer than the inline capacity"
+        );
+    }
+
+    #[test]
+    fn test_inline_cap() {
+        let mut a = MowStr::mut_empty();
+        a.push_str("a".repeat(INLINE_CAP));
+        assert!(a.try_string().is_none());
+        a.push('a');
+        assert_eq!(a.try_string().unwrap().len(), INLINE_CAP + 1);
+    }
+
+    #[test]
+    fn test_capacity() {
+        let mut s = MowStr::new("capacity");
+        assert_eq!(s.capacity(), None);
+        assert!(s.is_interned());
+        s.to_mut();
+        assert!(s.capacity().unwrap() >= s.len());
+        assert_eq!(MowStr::mut_empty().capacity(), Some(INLINE_CAP));
+    }
+
+    #[test]
+    fn test_add() {
+        let tail = MowStr::new(" tail");
+        let mut s = MowStr::new("head") + IStr::new(" istr") + String::from(" string") + '!';
+        s += &tail;
+        s += '?';
+        assert_eq!(s, "head istr string! tail?");
+        assert!(s.is_mutable());
+    }
+
+    #[test]
+    fn test_eq_istr() {
+        let i = IStr::new("compare with istr");
+        let mut m = MowStr::new("compare with istr");
+        assert_eq!(m, i);
+        assert_eq!(i, m);
+        m.to_mut();
+        assert_eq!(m, i);
+        m.push('!');
+        assert_ne!(i, m);
+    }
 }
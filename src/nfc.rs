@@ -0,0 +1,50 @@
+//! Interning the NFC form of strings
+
+use unicode_normalization::{is_nfc_quick, IsNormalized, UnicodeNormalization};
+
+use crate::{builder::with_scratch, IStr, MowStr};
+
+impl IStr {
+    /// Create a `IStr` from the NFC normalized form of `s`  
+    ///
+    /// Strings that look the same but are encoded differently, e.g. with a precomposed `é` or `e` and a combining accent,
+    /// become the same `IStr`
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::IStr;
+    /// let a = IStr::new_nfc("caf\u{e9}");
+    /// let b = IStr::new_nfc("cafe\u{301}");
+    /// assert_eq!(a, b);
+    /// ```
+    pub fn new_nfc(s: impl AsRef<str>) -> Self {
+        let s = s.as_ref();
+        if is_nfc_quick(s.chars()) == IsNormalized::Yes {
+            return Self::new(s);
+        }
+        with_scratch(|buf| buf.extend(s.nfc()))
+    }
+}
+
+impl MowStr {
+    /// Save the NFC normalized form of the current state to the intern pool, see [`IStr::new_nfc`]
+    #[inline]
+    pub fn intern_nfc(&mut self) {
+        *self = Self::from_istr(IStr::new_nfc(self.as_str()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nfc() {
+        let a = IStr::new_nfc("\u{212b}");
+        assert_eq!(a, "\u{c5}");
+        let mut b = MowStr::new_mut("A\u{30a}");
+        b.intern_nfc();
+        assert!(b.is_interned());
+        assert_eq!(b.into_istr(), a);
+    }
+}
@@ -0,0 +1,22 @@
+//! Parallel interning
+
+use rayon::prelude::*;
+
+use crate::IStr;
+
+/// Intern every string of `iter` in parallel on the rayon thread pool, keeping the order of `iter`
+///
+/// # Example
+/// ```
+/// let words = vec!["a", "b", "a"];
+/// let interned = pstr::par_intern(words);
+/// assert_eq!(interned[0], interned[2]);
+/// ```
+pub fn par_intern<I>(iter: I) -> Vec<IStr>
+where
+    I: IntoParallelIterator,
+    I::Iter: IndexedParallelIterator,
+    I::Item: AsRef<str>,
+{
+    iter.into_par_iter().map(IStr::new).collect()
+}
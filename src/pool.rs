@@ -2,134 +2,1069 @@
 
 use std::{
     borrow::Borrow,
-    ffi::OsStr,
-    hash::Hash,
+    collections::hash_map::RandomState,
+    ffi::{CStr, OsStr},
+    fmt,
+    hash::{BuildHasher, Hash},
+    mem::{self, ManuallyDrop},
     ops::Deref,
-    sync::{Arc, RwLock},
+    path::Path,
+    ptr,
+    sync::Arc,
+    time::Instant,
 };
 
-use dashmap::DashSet;
+#[cfg(feature = "single-thread")]
+use std::thread::LocalKey;
+
+#[cfg(not(feature = "single-thread"))]
 use once_cell::sync::Lazy;
 
-/// The String Intern Pool  
-pub static STR_POOL: Lazy<Pool<str>> = Lazy::new(|| Pool::new());
+#[cfg(feature = "background-gc")]
+pub use self::background::{spawn_gc, GcHandle};
+use self::blocks::Blocks;
+use self::bloom::BloomFilter;
+pub(crate) use self::cache::intern_str;
+pub use self::cache::{disable_thread_cache, enable_thread_cache};
+pub use self::config::{configure_global_pools, GlobalPoolConfig};
+use self::counters::Counters;
+#[cfg(feature = "counters")]
+pub use self::counters::PoolCounters;
+pub use self::eviction::{EvictAll, EvictionPolicy};
+pub use self::gc::{GcReport, GcThreshold};
+pub use self::hooks::PoolHooks;
+pub use self::normalize::Normalizer;
+pub use self::ordered::OrderedPool;
+pub use self::registry::{registry, Registry};
+pub use self::stats::PoolStats;
+use self::table::Table;
+pub use self::test_scope::{test_scope, TestScope};
+pub(crate) use self::test_scope::{with_owning_str_pool, with_str_pool};
+pub use crate::prc::{Pointee, PoolValue};
+use crate::{
+    prc::Prc,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        RwLock,
+    },
+};
+
+#[cfg(feature = "background-gc")]
+mod background;
+mod batch;
+pub(crate) mod blocks;
+mod bloom;
+mod cache;
+mod config;
+mod counters;
+mod eviction;
+mod gc;
+mod hooks;
+mod normalize;
+mod ordered;
+#[cfg(feature = "rayon")]
+mod par;
+mod persist;
+mod registry;
+mod similar;
+mod stats;
+mod table;
+mod test_scope;
+
+/// The hasher used by the global pools  
+///
+/// `ahash::RandomState` with the `ahash` feature, `fxhash::FxBuildHasher` with the `fxhash` feature,
+/// otherwise the std `RandomState`
+#[cfg(feature = "ahash")]
+pub type GlobalHasher = ahash::RandomState;
+/// The hasher used by the global pools  
+///
+/// `ahash::RandomState` with the `ahash` feature, `fxhash::FxBuildHasher` with the `fxhash` feature,
+/// otherwise the std `RandomState`
+#[cfg(all(feature = "fxhash", not(feature = "ahash")))]
+pub type GlobalHasher = fxhash::FxBuildHasher;
+/// The hasher used by the global pools  
+///
+/// `ahash::RandomState` with the `ahash` feature, `fxhash::FxBuildHasher` with the `fxhash` feature,
+/// otherwise the std `RandomState`
+#[cfg(not(any(feature = "ahash", feature = "fxhash")))]
+pub type GlobalHasher = RandomState;
+
+/// A global pool, shared by the whole process  
+/// With the `single-thread` feature each thread has its own, see `LocalPool`
+#[cfg(not(feature = "single-thread"))]
+pub type GlobalPool<T> = Lazy<Pool<T, GlobalHasher>>;
+/// A global pool, shared by the whole process  
+/// With the `single-thread` feature each thread has its own, see `LocalPool`
+#[cfg(feature = "single-thread")]
+pub type GlobalPool<T> = LocalPool<T>;
+
+#[cfg(not(feature = "single-thread"))]
+macro_rules! global_pool {
+    ($(#[$meta:meta])* $name:ident: $t:ty) => {
+        $(#[$meta])*
+        pub static $name: GlobalPool<$t> = Lazy::new(|| config::global_pool(Some(stringify!($t))));
+    };
+}
+
+#[cfg(feature = "single-thread")]
+macro_rules! global_pool {
+    ($(#[$meta:meta])* $name:ident: $t:ty) => {
+        $(#[$meta])*
+        pub static $name: GlobalPool<$t> = LocalPool({
+            thread_local! {
+                static POOL: &'static Pool<$t, GlobalHasher> = Box::leak(Box::new(config::global_pool(Some(stringify!($t)))));
+            }
+            &POOL
+        });
+    };
+}
 
-/// The OsString Intern Pool  
-pub static OS_STR_POOL: Lazy<Pool<OsStr>> = Lazy::new(|| Pool::new());
+global_pool! {
+    /// The String Intern Pool
+    STR_POOL: str
+}
+
+global_pool! {
+    /// The OsString Intern Pool
+    OS_STR_POOL: OsStr
+}
+
+global_pool! {
+    /// The CString Intern Pool
+    C_STR_POOL: CStr
+}
+
+global_pool! {
+    /// The Bytes Intern Pool
+    BYTES_POOL: [u8]
+}
+
+global_pool! {
+    /// The Path Intern Pool
+    PATH_POOL: Path
+}
+
+/// A global pool with the `single-thread` feature, every thread lazily creates its own  
+///
+/// Pools, and the handles into them, are neither `Send` nor `Sync` then, so a handle never leaves the thread of its pool.
+/// The pool of a thread is leaked when the thread exits, handles held by thread locals can be dropped at any point of teardown
+///
+/// ```compile_fail
+/// let s = pstr::IStr::new("stays on its thread");
+/// std::thread::spawn(move || drop(s));
+/// ```
+#[cfg(feature = "single-thread")]
+pub struct LocalPool<T: Eq + Hash + ?Sized + Pointee + 'static>(
+    &'static LocalKey<&'static Pool<T, GlobalHasher>>,
+);
+
+#[cfg(feature = "single-thread")]
+impl<T: Eq + Hash + ?Sized + Pointee + 'static> Deref for LocalPool<T> {
+    type Target = Pool<T, GlobalHasher>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        // The key holds a plain reference without a destructor, so it is never destroyed
+        self.0.with(|pool| *pool)
+    }
+}
+
+#[cfg(feature = "single-thread")]
+impl<T: Eq + Hash + ?Sized + Pointee + fmt::Debug + 'static> fmt::Debug for LocalPool<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LocalPool").field(&**self).finish()
+    }
+}
 
 /// The Intern Pool  
-#[derive(Debug)]
-pub struct Pool<T: Eq + Hash + ?Sized> {
-    pool: DashSet<Arc<T>>,
-    gc_lock: RwLock<()>,
+///
+/// `S` hashes the entries, the hash of each entry is computed once on insertion
+pub struct Pool<T: Eq + Hash + ?Sized + Pointee, S = RandomState> {
+    /// Entries, each carrying its precomputed hash
+    pool: Table<T, Blocks<S>>,
+    hasher: Blocks<S>,
+    /// Unique among all pools ever made, unlike the address of the pool
+    id: u64,
+    filter: Option<BloomFilter>,
+    policy: RwLock<Option<Box<dyn EvictionPolicy<T>>>>,
+    hooks: RwLock<Option<Box<dyn PoolHooks<T>>>>,
+    normalizer: Option<Box<dyn Normalizer<T>>>,
+    release_on_drop: AtomicBool,
+    entries: AtomicUsize,
+    bytes: AtomicUsize,
+    gc_threshold: RwLock<Option<GcThreshold>>,
+    /// Level of the threshold at which the next automatic collection runs
+    next_gc: AtomicUsize,
+    /// Next shard to collect by `collect_garbage_incremental`
+    gc_cursor: AtomicUsize,
+    counters: Counters,
 }
 
-impl<T: Eq + Hash + ?Sized> Pool<T> {
+impl<T: Eq + Hash + ?Sized + Pointee> Pool<T> {
     /// New a empty intern pool
     #[inline]
     pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+
+    /// New a empty intern pool with space for at least `capacity` entries before reallocating
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+
+    /// New a empty intern pool split into `shard_amount` independently locked shards  
+    ///
+    /// More shards reduce contention between threads, the default is 4 times the number of CPUs
+    ///
+    /// # Panics
+    /// Panics if `shard_amount` is not a power of two greater than 1
+    #[inline]
+    pub fn with_shard_amount(shard_amount: usize) -> Self {
+        Self::build(0, Some(shard_amount), RandomState::new())
+    }
+
+    /// New a empty intern pool with a bloom filter sized for about `expected_items` entries  
+    ///
+    /// Interning a string that has definitely never been seen skips the lookup and goes straight to insertion,
+    /// which speeds up bulk-loading mostly unique data.  
+    /// The filter is never shrunk by garbage collection, so it only degrades into more false positives over time
+    #[inline]
+    pub fn with_bloom_filter(expected_items: usize) -> Self {
+        let mut pool = Self::new();
+        pool.filter = Some(BloomFilter::new(expected_items));
+        pool
+    }
+
+    /// New a empty intern pool which interns every value as its normal form given by `normalizer`  
+    ///
+    /// Lookups with [`get`](Pool::get) and [`contains`](Pool::contains) are normalized too,
+    /// see [`Normalizer`]
+    #[inline]
+    pub fn with_normalizer(normalizer: impl Normalizer<T> + 'static) -> Self {
+        Self::with_normalizer_and_hasher(normalizer, RandomState::new())
+    }
+}
+
+impl<T: Eq + Hash + ?Sized + Pointee, S: BuildHasher + Clone> Pool<T, S> {
+    /// New a empty intern pool which uses `hasher` to hash entries  
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::pool::Pool;
+    /// # use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+    /// let pool = Pool::<str, _>::with_hasher(BuildHasherDefault::<DefaultHasher>::default());
+    /// let a = pool.intern("a");
+    /// assert_eq!(a.precomputed_hash(), pool.hash("a"));
+    /// ```
+    #[inline]
+    pub fn with_hasher(hasher: S) -> Self {
+        Self::build(0, None, hasher)
+    }
+
+    /// New a empty intern pool which uses `hasher`, with space for at least `capacity` entries before reallocating
+    #[inline]
+    pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+        Self::build(capacity, None, hasher)
+    }
+
+    fn build(capacity: usize, shard_amount: Option<usize>, hasher: S) -> Self {
+        /// Id of the next pool, independent of loom, it doesn't order anything
+        static NEXT_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+        let hasher = Blocks(hasher);
         Self {
-            pool: DashSet::new(),
-            gc_lock: RwLock::new(()),
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            pool: Table::new(capacity, shard_amount, hasher.clone()),
+            hasher,
+            filter: None,
+            policy: RwLock::new(None),
+            hooks: RwLock::new(None),
+            normalizer: None,
+            release_on_drop: AtomicBool::new(false),
+            entries: AtomicUsize::new(0),
+            bytes: AtomicUsize::new(0),
+            gc_threshold: RwLock::new(None),
+            next_gc: AtomicUsize::new(gc::NO_GC),
+            gc_cursor: AtomicUsize::new(0),
+            counters: Counters::default(),
         }
     }
+
+    /// New a empty intern pool which uses `hasher`, with a bloom filter sized for about `expected_items` entries  
+    /// See [`with_bloom_filter`](Pool::with_bloom_filter)
+    #[inline]
+    pub fn with_bloom_filter_and_hasher(expected_items: usize, hasher: S) -> Self {
+        let mut pool = Self::with_hasher(hasher);
+        pool.filter = Some(BloomFilter::new(expected_items));
+        pool
+    }
+
+    /// New a empty intern pool which uses `hasher` and normalizes values with `normalizer`  
+    /// See [`with_normalizer`](Pool::with_normalizer)
+    #[inline]
+    pub fn with_normalizer_and_hasher(normalizer: impl Normalizer<T> + 'static, hasher: S) -> Self {
+        let mut pool = Self::with_hasher(hasher);
+        pool.normalizer = Some(Box::new(normalizer));
+        pool
+    }
 }
 
-impl<T: Eq + Hash + ?Sized> Pool<T> {
-    /// Make a intern
+impl<T: Eq + Hash + ?Sized + Pointee + fmt::Debug, S: BuildHasher + Clone> fmt::Debug
+    for Pool<T, S>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pool")
+            .field("pool", &self.pool)
+            .field("filter", &self.filter)
+            .finish()
+    }
+}
+
+impl<T: Eq + Hash + ?Sized + Pointee, S: BuildHasher + Clone + Default> Default for Pool<T, S> {
     #[inline]
-    pub fn intern<A: AsRef<T>>(&self, a: A, to_arc: impl FnOnce(A) -> Arc<T>) -> Intern<T> {
-        match self.pool.get(a.as_ref()).map(|v| v.key().clone()) {
-            Some(v) => Intern(v),
-            None => {
-                let arc = to_arc(a);
-                Intern(self.insert_arc(arc))
-            }
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<T: Eq + Hash + ?Sized + Pointee, S: BuildHasher + Clone> Pool<T, S> {
+    /// Make a intern  
+    ///
+    /// A new value is copied into a single allocation together with its reference count and hash
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::pool::Pool;
+    /// let pool = Pool::<str>::new();
+    /// let a = pool.intern("a");
+    /// assert_eq!(pool.intern(String::from("a")), a);
+    /// ```
+    #[inline]
+    pub fn intern<A: AsRef<T>>(&self, a: A) -> Intern<T>
+    where
+        T: PoolValue,
+    {
+        self.intern_report(a).0
+    }
+
+    /// Make a intern, the old signature of [`intern`](Pool::intern)  
+    ///
+    /// Entries are copied into the pool, so `to_arc` is never called
+    #[deprecated(note = "`to_arc` is never called, entries are copied into the pool, use `intern`")]
+    #[inline]
+    pub fn intern_to_arc<A: AsRef<T>>(&self, a: A, _to_arc: impl FnOnce(A) -> Arc<T>) -> Intern<T>
+    where
+        T: PoolValue,
+    {
+        self.intern(a)
+    }
+
+    /// Make a intern and report whether it was newly inserted, `false` if the value was already in the pool  
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::pool::Pool;
+    /// let pool = Pool::<str>::new();
+    /// let (a, inserted) = pool.intern_report("a");
+    /// assert!(inserted);
+    /// let (b, inserted) = pool.intern_report("a");
+    /// assert!(!inserted);
+    /// assert_eq!(a, b);
+    /// ```
+    pub fn intern_report<A: AsRef<T>>(&self, a: A) -> (Intern<T>, bool)
+    where
+        T: PoolValue,
+    {
+        let a = a.as_ref();
+        let r = match self.normalize(a) {
+            Some(v) => self.intern_hashed(&v, self.hash(&v), true),
+            None => self.intern_hashed(a, self.hash(a), true),
+        };
+        // Waiting for the shard always gets it
+        r.unwrap()
+    }
+
+    /// Make a intern without blocking, return `None` if the shard of `a` is locked,
+    /// e.g. because garbage collection is running on it  
+    ///
+    /// For latency critical threads which would rather fall back to a owned value
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::pool::Pool;
+    /// let pool = Pool::<str>::new();
+    /// let a = pool.try_intern("a").unwrap_or_else(|| pool.intern("a"));
+    /// assert_eq!(a.get(), "a");
+    /// ```
+    #[inline]
+    pub fn try_intern<A: AsRef<T>>(&self, a: A) -> Option<Intern<T>>
+    where
+        T: PoolValue,
+    {
+        let a = a.as_ref();
+        let r = match self.normalize(a) {
+            Some(v) => self.intern_hashed(&v, self.hash(&v), false),
+            None => self.intern_hashed(a, self.hash(a), false),
+        };
+        r.map(|(v, _)| v)
+    }
+
+    /// Make a intern of a `'static` value, a new entry points at `a` instead of copying it  
+    ///
+    /// Only the entry header is allocated, if `a` is already in the pool the existing entry is returned.
+    /// A value changed by the [normalizer](Pool::with_normalizer) is copied like [`intern`](Pool::intern)
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::pool::Pool;
+    /// static NAME: &str = "a static name";
+    /// let pool = Pool::<str>::new();
+    /// let a = pool.intern_static(NAME);
+    /// assert_eq!(a.get().as_ptr(), NAME.as_ptr());
+    /// assert_eq!(pool.intern("a static name"), a);
+    /// ```
+    pub fn intern_static(&self, a: &'static T) -> Intern<T>
+    where
+        T: PoolValue,
+    {
+        if let Some(v) = self.normalize(a) {
+            return self.intern_hashed(&v, self.hash(&v), true).unwrap().0;
+        }
+        let hash = self.hash(a);
+        self.intern_with(a, hash, true, || Intern(Prc::from_static(a, hash)))
+            .unwrap()
+            .0
+    }
+
+    /// Make a intern of a owned value, which is moved into the pool instead of copied if it is new
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::pool::Pool;
+    /// let pool = Pool::<Vec<u32>>::new();
+    /// let a = pool.intern_value(vec![1, 2, 3]);
+    /// assert_eq!(pool.intern_value(vec![1, 2, 3]), a);
+    /// ```
+    pub fn intern_value(&self, value: T) -> Intern<T>
+    where
+        T: Sized,
+    {
+        let value = match self.normalize(&value) {
+            Some(v) => *v,
+            None => value,
+        };
+        let hash = self.hash(&value);
+        let value = ManuallyDrop::new(value);
+        let mut moved = false;
+        let (intern, _) = self
+            .intern_with(&value, hash, true, || {
+                moved = true;
+                // SAFETY: the bytes of `value` are moved into the entry and `value` is never dropped
+                Intern(unsafe {
+                    Prc::new_with(&*value, hash, |dst| {
+                        ptr::copy_nonoverlapping(&*value as *const T, dst as *mut T, 1)
+                    })
+                })
+            })
+            .unwrap();
+        if !moved {
+            drop(ManuallyDrop::into_inner(value));
         }
+        intern
     }
 
+    /// Make a intern of a value whose hash was already computed with [`hash`](Pool::hash), and whether it was inserted  
+    /// Return `None` if the shard is locked and `wait` is `false`
+    ///
+    /// The lookup goes straight to the shard selected by `hash` and doesn't hash the value again
     #[inline]
-    fn insert_arc(&self, arc: Arc<T>) -> Arc<T> {
-        if self.pool.insert(Clone::clone(&arc)) {
-            arc
+    fn intern_hashed(&self, a: &T, hash: u64, wait: bool) -> Option<(Intern<T>, bool)>
+    where
+        T: PoolValue,
+    {
+        self.intern_with(a, hash, wait, || Intern::new(a, hash))
+    }
+
+    /// Like [`intern_hashed`](Pool::intern_hashed), but a new entry is made by `make`
+    fn intern_with(
+        &self,
+        a: &T,
+        hash: u64,
+        wait: bool,
+        make: impl FnOnce() -> Intern<T>,
+    ) -> Option<(Intern<T>, bool)> {
+        if let Some(filter) = &self.filter {
+            if !filter.may_contain(hash) {
+                return self.insert_new(a, hash, wait, make);
+            }
+        }
+        let shard = self.pool.shard_of(hash);
+        let shard = if wait {
+            self.pool.read(shard)
         } else {
-            self.when_failed(arc)
+            self.pool.try_read(shard)?
+        };
+        let found = shard
+            .raw_entry()
+            .from_hash(hash, |k| k.0.precomputed_hash() == hash && k.0.get() == a)
+            .map(|(k, _)| k.0.clone());
+        drop(shard);
+        match found {
+            Some(v) => {
+                self.counters.hit();
+                Some((v, false))
+            }
+            None => self.insert_new(a, hash, wait, make),
         }
     }
 
-    #[cold]
-    fn when_failed(&self, arc: Arc<T>) -> Arc<T> {
-        let lock = self.gc_lock.read();
-        let r = match self.pool.get(arc.as_ref()).map(|v| v.key().clone()) {
-            Some(v) => v,
-            None => {
-                let s = self.pool.insert(Clone::clone(&arc));
-                assert!(s);
-                arc
+    /// Hash a value with the pool's hasher, equal to the precomputed hash of its entry
+    #[inline]
+    pub fn hash(&self, value: &T) -> u64 {
+        self.hasher.hash_one(value)
+    }
+
+    /// Start hashing a value written in pieces, the bytes have to be written in blocks like [`Blocks`] does
+    #[inline]
+    pub(crate) fn piece_hasher(&self) -> S::Hasher {
+        self.hasher.0.build_hasher()
+    }
+
+    /// Get the id of the pool, no other pool ever has the same id, so it tells whether a hash was computed by this pool
+    #[inline]
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Make a intern of a value whose hash was computed with [`piece_hasher`](Pool::piece_hasher)
+    pub(crate) fn intern_prehashed(&self, a: &T, hash: u64) -> Intern<T>
+    where
+        T: PoolValue,
+    {
+        let r = match self.normalize(a) {
+            Some(v) => self.intern_hashed(&v, self.hash(&v), true),
+            None => self.intern_hashed(a, hash, true),
+        };
+        r.unwrap().0
+    }
+
+    /// Get the normal form of `value` if the pool has a normalizer and `value` isn't normal
+    #[inline]
+    fn normalize(&self, value: &T) -> Option<Box<T>> {
+        self.normalizer.as_ref()?.normalize(value)
+    }
+
+    /// Get the intern of `key` if it is in the pool, without inserting it
+    pub fn get(&self, key: &T) -> Option<Intern<T>> {
+        let normalized = self.normalize(key);
+        let key = normalized.as_deref().unwrap_or(key);
+        if let Some(filter) = &self.filter {
+            if !filter.may_contain(self.hash(key)) {
+                return None;
             }
+        }
+        self.pool.find(key, self.hash(key), |k| k.0.clone())
+    }
+
+    /// Check if `key` is in the pool
+    #[inline]
+    pub fn contains(&self, key: &T) -> bool {
+        let normalized = self.normalize(key);
+        let key = normalized.as_deref().unwrap_or(key);
+        self.pool.find(key, self.hash(key), |_| ()).is_some()
+    }
+
+    /// Get the number of entries in the pool
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.pool.len()
+    }
+
+    /// Check if the pool is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the number of entries not referenced outside the pool, which [`collect_garbage`](Pool::collect_garbage) would remove  
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::pool::Pool;
+    /// let pool = Pool::<str>::new();
+    /// let _a = pool.intern("a");
+    /// pool.intern("b");
+    /// assert_eq!(pool.len(), 2);
+    /// assert_eq!(pool.garbage_count(), 1);
+    /// ```
+    pub fn garbage_count(&self) -> usize {
+        let mut count = 0;
+        self.pool
+            .for_each(|k| count += (k.0.strong_count() == 1) as usize);
+        count
+    }
+
+    /// Call `f` on every entry in the pool  
+    ///
+    /// Parts of the pool are locked while `f` runs, so `f` must not intern into or collect this pool
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::pool::Pool;
+    /// let pool = Pool::<str>::new();
+    /// pool.intern("a");
+    /// pool.intern("bc");
+    /// let mut total = 0;
+    /// pool.for_each(|s| total += s.len());
+    /// assert_eq!(total, 3);
+    /// ```
+    pub fn for_each(&self, mut f: impl FnMut(&T)) {
+        self.pool.for_each(|k| f(k.0.get()))
+    }
+
+    /// Get a snapshot of all entries in the pool, in no particular order
+    pub fn to_vec(&self) -> Vec<Intern<T>> {
+        let mut v = Vec::with_capacity(self.len());
+        self.pool.for_each(|k| v.push(k.0.clone()));
+        v
+    }
+
+    /// Intern every value of `iter` in one pass  
+    ///
+    /// Primed entries are only referenced by the pool, so garbage collection removes them unless they are in use,
+    /// keep them with an [eviction policy](Pool::set_eviction_policy) if needed
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::pool::Pool;
+    /// let pool = Pool::<str>::new();
+    /// pool.prime(["GET", "POST", "GET"]);
+    /// assert_eq!(pool.len(), 2);
+    /// ```
+    pub fn prime<I>(&self, iter: I)
+    where
+        I: IntoIterator,
+        I::Item: AsRef<T>,
+        T: PoolValue,
+    {
+        for v in iter {
+            let v = v.as_ref();
+            self.intern_hashed(v, self.hash(v), true);
+        }
+    }
+
+    /// Insert `value` unless another thread got there first, the shard stays write locked in between  
+    ///
+    /// Garbage collection locks the same shard to remove entries,
+    /// so a entry is never removed between being found and being cloned
+    fn insert_new(
+        &self,
+        value: &T,
+        hash: u64,
+        wait: bool,
+        make: impl FnOnce() -> Intern<T>,
+    ) -> Option<(Intern<T>, bool)> {
+        let shard = self.pool.shard_of(hash);
+        let mut shard = if wait {
+            self.pool.write(shard)
+        } else {
+            self.pool.try_write(shard)?
         };
-        drop(lock);
-        r
+        let mut inserted = false;
+        let (key, _) = shard
+            .raw_entry_mut()
+            .from_hash(hash, |k| {
+                k.0.precomputed_hash() == hash && k.0.get() == value
+            })
+            .or_insert_with(|| {
+                inserted = true;
+                (Key(make()), table::value())
+            });
+        let intern = key.0.clone();
+        if inserted {
+            // Before the shard is unlocked, a lookup seeing the entry must not be turned away by the filter
+            if let Some(filter) = &self.filter {
+                filter.insert(hash);
+            }
+            self.on_insert(intern.get());
+        } else {
+            self.counters.hit();
+        }
+        drop(shard);
+        if inserted {
+            self.hook_insert(intern.get());
+            self.maybe_collect_garbage();
+        }
+        Some((intern, inserted))
     }
 }
 
-impl<T: Eq + Hash + ?Sized> Pool<T> {
-    /// Delete all interning string with reference count == 1 in the pool
-    pub fn collect_garbage(&self) {
-        let lock = self.gc_lock.write();
-        self.pool.retain(|arc| Arc::<T>::strong_count(arc) > 1);
-        drop(lock);
+impl<T: Eq + Hash + ?Sized + Pointee, S: BuildHasher + Clone> Pool<T, S> {
+    /// Delete all interning string with reference count == 1 in the pool  
+    /// Entries kept by the eviction policy set with [`set_eviction_policy`](Pool::set_eviction_policy) are skipped
+    ///
+    /// The pool is collected one shard at a time, interning only waits while its own shard is collected
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::pool::Pool;
+    /// let pool = Pool::<str>::new();
+    /// let _a = pool.intern("kept");
+    /// pool.intern("garbage");
+    /// let report = pool.collect_garbage();
+    /// assert_eq!(report.removed, 1);
+    /// assert_eq!(report.bytes_freed, 7);
+    /// assert_eq!(report.remaining, 1);
+    /// ```
+    pub fn collect_garbage(&self) -> GcReport {
+        let policy = self.policy.read();
+        match policy.as_deref() {
+            Some(policy) => self.collect_garbage_with(policy),
+            None => self.collect_garbage_with(&EvictAll),
+        }
+    }
+
+    /// Delete interning string with reference count == 1 in the pool which `policy` decides to evict
+    pub fn collect_garbage_with(&self, policy: &(impl EvictionPolicy<T> + ?Sized)) -> GcReport {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("collect_garbage").entered();
+        let start = Instant::now();
+        let mut report = GcReport::default();
+        for shard in 0..self.pool.shard_count() {
+            self.collect_shard(shard, policy, &mut report);
+        }
+        self.counters.gc_run();
+        report.remaining = self.entries.load(Ordering::Relaxed);
+        report.duration = start.elapsed();
+        #[cfg(feature = "tracing")]
+        report.trace();
+        report
+    }
+
+    /// Collect garbage in at most `shards` shards, continuing where the previous call stopped  
+    /// Return `true` if this call finished a pass over the whole pool
+    ///
+    /// Spreads the work of [`collect_garbage`](Pool::collect_garbage) over many calls, e.g. one per event loop tick
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::pool::Pool;
+    /// let pool = Pool::<str>::with_shard_amount(4);
+    /// pool.intern("garbage");
+    /// while !pool.collect_garbage_incremental(1) {}
+    /// assert!(pool.is_empty());
+    /// ```
+    pub fn collect_garbage_incremental(&self, shards: usize) -> bool {
+        let policy = self.policy.read();
+        let policy: &dyn EvictionPolicy<T> = match policy.as_deref() {
+            Some(policy) => policy,
+            None => &EvictAll,
+        };
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("collect_garbage_incremental", shards).entered();
+        let len = self.pool.shard_count();
+        let mut finished = false;
+        for _ in 0..shards.min(len) {
+            let shard = self.gc_cursor.fetch_add(1, Ordering::Relaxed) % len;
+            self.collect_shard(shard, policy, &mut GcReport::default());
+            finished |= shard == len - 1;
+        }
+        if finished {
+            self.counters.gc_run();
+        }
+        finished
+    }
+
+    /// Remove the garbage of one shard, adding the removed entries to `report`  
+    ///
+    /// Only this shard is locked, interning into other shards goes on meanwhile.
+    /// The hooks see the removed entries after the shard is unlocked
+    fn collect_shard(
+        &self,
+        shard: usize,
+        policy: &(impl EvictionPolicy<T> + ?Sized),
+        report: &mut GcReport,
+    ) {
+        let hooked = self.has_hooks();
+        let mut removed = Vec::new();
+        self.pool.write(shard).retain(|key, _| {
+            let keep = key.0.strong_count() > 1 || !policy.should_evict(key.0.get());
+            if !keep {
+                #[cfg(feature = "tracing")]
+                tracing::trace!(bytes = mem::size_of_val::<T>(key.0.get()), "evict");
+                self.counters.evicted();
+                self.on_remove(key.0.get());
+                report.removed += 1;
+                report.bytes_freed += mem::size_of_val::<T>(key.0.get());
+                if hooked {
+                    removed.push(key.0.clone());
+                }
+            }
+            keep
+        });
+        for intern in removed {
+            self.hook_remove(intern.get());
+        }
+    }
+
+    /// Release a intern, removing its entry from the pool if nothing else references it
+    /// and the [eviction policy](Pool::set_eviction_policy) doesn't keep it  
+    /// Return `true` if the entry was removed
+    #[inline]
+    pub fn release(&self, intern: Intern<T>) -> bool {
+        self.remove_unused(&intern)
+    }
+
+    /// Remove the entry of `intern` if `intern` is the only reference outside the pool and the eviction policy agrees
+    pub(crate) fn remove_unused(&self, intern: &Intern<T>) -> bool {
+        let policy = self.policy.read();
+        // The shard is locked, so no other handle can be made from the table meanwhile
+        let r = self
+            .pool
+            .remove_if(intern.get(), intern.precomputed_hash(), |key| {
+                key.0 == *intern
+                    && key.0.strong_count() == 2
+                    && match policy.as_deref() {
+                        Some(policy) => policy.should_evict(key.0.get()),
+                        None => true,
+                    }
+            });
+        drop(policy);
+        match r {
+            Some(key) => {
+                self.on_remove(key.0.get());
+                self.hook_remove(key.0.get());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Enable or disable removing a entry automatically when its last external handle is dropped  
+    ///
+    /// This is best-effort, handles dropped concurrently on different threads may all miss the last reference,
+    /// [`collect_garbage`](Pool::collect_garbage) still picks those up
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::{IStr, pool::STR_POOL};
+    /// STR_POOL.set_release_on_drop(true);
+    /// let s = IStr::new("transient");
+    /// drop(s); // entry removed from the pool here
+    /// # STR_POOL.set_release_on_drop(false);
+    /// ```
+    #[inline]
+    pub fn set_release_on_drop(&self, enable: bool) {
+        self.release_on_drop.store(enable, Ordering::Relaxed);
+    }
+
+    /// Check if entries are removed automatically when their last external handle is dropped
+    #[inline]
+    pub fn is_release_on_drop(&self) -> bool {
+        self.release_on_drop.load(Ordering::Relaxed)
+    }
+
+    /// Check if the entry of `intern` is in this pool, not just a equal value
+    #[inline]
+    pub(crate) fn owns(&self, intern: &Intern<T>) -> bool {
+        self.pool
+            .find(intern.get(), intern.precomputed_hash(), |key| {
+                key.0 == *intern
+            })
+            .unwrap_or(false)
+    }
+
+    /// Called when a handle owned by a front-end type is dropped
+    #[inline]
+    pub(crate) fn on_drop(&self, intern: &Intern<T>) {
+        if self.is_release_on_drop() {
+            self.remove_unused(intern);
+        }
+    }
+
+    /// Set the eviction policy used by [`collect_garbage`](Pool::collect_garbage)
+    pub fn set_eviction_policy(&self, policy: impl EvictionPolicy<T> + 'static) {
+        *self.policy.write() = Some(Box::new(policy));
+    }
+
+    /// Restore the default policy which evicts every unreferenced entry
+    pub fn clear_eviction_policy(&self) {
+        *self.policy.write() = None;
     }
 }
 
 /// Intern Ptr  
-#[derive(Debug, Eq, Ord, PartialOrd)]
-pub struct Intern<T: ?Sized>(Arc<T>);
+///
+/// A single pointer to the entry, one allocation holding the reference count,
+/// the hash precomputed by its pool and the value, so hashing a handle is O(1)
+pub struct Intern<T: ?Sized + Pointee>(Prc<T>);
+
+impl<T: ?Sized + Pointee> Intern<T> {
+    #[inline]
+    fn new(value: &T, hash: u64) -> Self
+    where
+        T: PoolValue,
+    {
+        Self(Prc::new(value, hash))
+    }
 
-impl<T: ?Sized> Intern<T> {
     /// Get target ref
     #[inline]
     pub fn get(&self) -> &T {
-        self.0.as_ref()
+        self.0.get()
+    }
+
+    /// Get the hash precomputed by the pool  
+    /// Equal to [`Pool::hash`] of the target on the pool which made this intern
+    #[inline]
+    pub fn precomputed_hash(&self) -> u64 {
+        self.0.hash()
+    }
+
+    /// Get the number of handles to the entry, including the one owned by the pool
+    #[inline]
+    fn strong_count(&self) -> usize {
+        self.0.strong_count()
+    }
+
+    /// Get the size of the entry's allocation in bytes
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.0.heap_size()
+    }
+
+    /// Turn into a thin pointer to the entry, see [`from_raw`](Intern::from_raw)
+    #[inline]
+    pub(crate) fn into_raw(self) -> *const () {
+        self.0.into_raw()
+    }
+
+    /// Rebuild a intern from a pointer returned by [`into_raw`](Intern::into_raw)
+    ///
+    /// # Safety
+    /// `ptr` must come from `into_raw` of a `Intern<T>` and is consumed by this call
+    #[inline]
+    pub(crate) unsafe fn from_raw(ptr: *const ()) -> Self {
+        Self(Prc::from_raw(ptr))
     }
 }
 
-impl<T: ?Sized> PartialEq for Intern<T> {
+impl<T: ?Sized + Pointee + fmt::Debug> fmt::Debug for Intern<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Intern")
+            .field(&self.get())
+            .field(&self.precomputed_hash())
+            .finish()
+    }
+}
+
+impl<T: ?Sized + Pointee> PartialEq for Intern<T> {
     fn eq(&self, other: &Self) -> bool {
-        std::sync::Arc::<T>::as_ptr(&self.0) == std::sync::Arc::<T>::as_ptr(&other.0)
+        self.0.ptr_eq(&other.0)
     }
 }
 
-impl<T: ?Sized> Clone for Intern<T> {
+impl<T: ?Sized + Pointee> Eq for Intern<T> {}
+
+impl<T: ?Sized + Pointee + Ord> PartialOrd for Intern<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: ?Sized + Pointee + Ord> Ord for Intern<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.get()
+            .cmp(other.get())
+            .then_with(|| self.precomputed_hash().cmp(&other.precomputed_hash()))
+    }
+}
+
+impl<T: ?Sized + Pointee> Clone for Intern<T> {
     fn clone(&self) -> Self {
         Self(self.0.clone())
     }
 }
 
-impl<T: ?Sized> Deref for Intern<T> {
+impl<T: ?Sized + Pointee> Deref for Intern<T> {
     type Target = T;
 
     #[inline]
     fn deref(&self) -> &Self::Target {
-        self.0.deref()
+        self.get()
     }
 }
 
-impl<T: ?Sized> AsRef<T> for Intern<T> {
+impl<T: ?Sized + Pointee> AsRef<T> for Intern<T> {
     fn as_ref(&self) -> &T {
-        self.0.as_ref()
+        self.get()
     }
 }
 
-impl<T: ?Sized> Borrow<T> for Intern<T> {
+impl<T: ?Sized + Pointee> Borrow<T> for Intern<T> {
     fn borrow(&self) -> &T {
-        self.0.borrow()
+        self.get()
     }
 }
 
-impl<T: ?Sized> From<Intern<T>> for Arc<T> {
+/// Copies the value into a new `Arc`  
+///
+/// Entries are not `Arc`s since they carry their hash in the same allocation,
+/// so this allocates and copies instead of sharing the entry
+impl<T: ?Sized + Pointee> From<Intern<T>> for Arc<T>
+where
+    for<'a> Arc<T>: From<&'a T>,
+{
     fn from(v: Intern<T>) -> Self {
-        v.0
+        Arc::from(v.get())
+    }
+}
+
+/// Table key of a entry, hashed and compared by value so lookups can borrow it as `T`
+struct Key<T: ?Sized + Pointee>(Intern<T>);
+
+impl<T: ?Sized + Pointee + Hash> Hash for Key<T> {
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.get().hash(state)
+    }
+}
+
+impl<T: ?Sized + Pointee + PartialEq> PartialEq for Key<T> {
+    /// Entries of one pool share a hasher, so differing hashes settle it without comparing the values
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0.precomputed_hash() == other.0.precomputed_hash() && self.0.get() == other.0.get()
+    }
+}
+
+impl<T: ?Sized + Pointee + Eq> Eq for Key<T> {}
+
+impl<T: ?Sized + Pointee + Ord> PartialOrd for Key<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: ?Sized + Pointee + Ord> Ord for Key<T> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.get().cmp(other.0.get())
+    }
+}
+
+impl<T: ?Sized + Pointee> Borrow<T> for Key<T> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        self.0.get()
+    }
+}
+
+impl<T: ?Sized + Pointee + fmt::Debug> fmt::Debug for Key<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.0.get(), f)
     }
 }
 
@@ -139,56 +1074,70 @@ mod tests {
 
     #[test]
     fn test_basic() {
-        let h = STR_POOL.intern("asd", Arc::from);
+        let h = STR_POOL.intern("asd");
         assert_eq!(h.get(), "asd");
     }
 
     #[test]
     fn test_same() {
-        let h1 = STR_POOL.intern("asd", Arc::from);
-        let h2 = STR_POOL.intern("asd", Arc::from);
+        let h1 = STR_POOL.intern("asd");
+        let h2 = STR_POOL.intern("asd");
         assert_eq!(h1, h2);
         assert_eq!(h1.get(), "asd");
         assert_eq!(h2.get(), "asd");
+        assert_eq!(h1.precomputed_hash(), STR_POOL.hash("asd"));
     }
 
     #[test]
     fn test_not_same() {
-        let h1 = STR_POOL.intern("asd", Arc::from);
-        let h2 = STR_POOL.intern("123", Arc::from);
+        let h1 = STR_POOL.intern("asd");
+        let h2 = STR_POOL.intern("123");
         assert_ne!(h1, h2);
         assert_eq!(h1.get(), "asd");
         assert_eq!(h2.get(), "123");
     }
 
     #[test]
-    #[ignore]
     fn test_pool_gc() {
-        assert_eq!(STR_POOL.pool.len(), 0);
-        STR_POOL.intern("asd", Arc::from);
-        assert_eq!(STR_POOL.pool.len(), 1);
-        let h = STR_POOL.intern("123", Arc::from);
-        assert_eq!(STR_POOL.pool.len(), 2);
-        STR_POOL.collect_garbage();
-        assert_eq!(STR_POOL.pool.len(), 1);
+        let pool = test_scope();
+        assert_eq!(pool.len(), 0);
+        pool.intern("asd");
+        assert_eq!(pool.len(), 1);
+        let h = pool.intern("123");
+        assert_eq!(pool.len(), 2);
+        pool.collect_garbage();
+        assert_eq!(pool.len(), 1);
         drop(h);
-        assert_eq!(STR_POOL.pool.len(), 1);
-        STR_POOL.collect_garbage();
-        assert_eq!(STR_POOL.pool.len(), 0);
+        assert_eq!(pool.len(), 1);
+        pool.collect_garbage();
+        assert_eq!(pool.len(), 0);
     }
 
     #[test]
+    #[cfg(feature = "single-thread")]
+    fn test_local_pools() {
+        STR_POOL.intern("test_local_pools_main");
+        std::thread::spawn(|| {
+            assert!(!STR_POOL.contains("test_local_pools_main"));
+            STR_POOL.intern("test_local_pools_spawned");
+        })
+        .join()
+        .unwrap();
+        assert!(STR_POOL.contains("test_local_pools_main"));
+        assert!(!STR_POOL.contains("test_local_pools_spawned"));
+    }
+
+    #[test]
+    #[cfg(not(feature = "single-thread"))]
     fn test_concurrent_1() {
         use std::thread::spawn;
 
         let t: Vec<_> = (0..100)
-            .into_iter()
             .map(|i| {
                 spawn(move || {
-                    let a = STR_POOL.intern(i.to_string(), Arc::from);
+                    let a = STR_POOL.intern(i.to_string());
                     let v: Vec<_> = (0..100)
-                        .into_iter()
-                        .map(|_| spawn(move || STR_POOL.intern(i.to_string(), Arc::from)))
+                        .map(|_| spawn(move || STR_POOL.intern(i.to_string())))
                         .collect();
                     for b in v.into_iter() {
                         assert_eq!(a, b.join().unwrap());
@@ -203,26 +1152,188 @@ mod tests {
     }
 
     #[test]
+    fn test_eviction_policy() {
+        let pool = Pool::<str>::new();
+        pool.set_eviction_policy(|s: &str| s != "keep");
+        pool.intern("keep");
+        pool.intern("drop");
+        pool.collect_garbage();
+        assert_eq!(pool.len(), 1);
+        assert!(pool.contains("keep"));
+        pool.clear_eviction_policy();
+        pool.collect_garbage();
+        assert_eq!(pool.len(), 0);
+
+        pool.set_eviction_policy(|s: &str| s != "keep");
+        assert!(!pool.release(pool.intern("keep")));
+        assert!(pool.release(pool.intern("drop")));
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[test]
+    fn test_release() {
+        let pool = Pool::<str>::new();
+        let a = pool.intern("asd");
+        let b = a.clone();
+        assert!(!pool.release(a));
+        assert_eq!(pool.len(), 1);
+        assert!(pool.release(b));
+        assert_eq!(pool.len(), 0);
+    }
+
+    #[test]
+    fn test_scope_release() {
+        let kept = {
+            let scope = crate::scope();
+            scope.intern("scope_test_dropped");
+            scope.intern("scope_test_dropped");
+            let kept = scope.intern("scope_test_kept_alive");
+            assert_eq!(scope.len(), 3);
+            assert!(STR_POOL.contains("scope_test_dropped"));
+            kept
+        };
+        assert!(!STR_POOL.contains("scope_test_dropped"));
+        assert!(STR_POOL.contains("scope_test_kept_alive"));
+        assert_eq!(kept, "scope_test_kept_alive");
+    }
+
+    #[test]
+    fn test_release_on_drop() {
+        let pool = Pool::<str>::new();
+        pool.set_release_on_drop(true);
+        let a = pool.intern("asd");
+        let b = a.clone();
+        pool.on_drop(&a);
+        drop(a);
+        assert_eq!(pool.len(), 1);
+        pool.on_drop(&b);
+        drop(b);
+        assert_eq!(pool.len(), 0);
+    }
+
+    #[test]
+    fn test_with_shard_amount() {
+        let pool = Pool::<str>::with_shard_amount(2);
+        for i in 0..100 {
+            pool.intern(i.to_string());
+        }
+        assert_eq!(pool.len(), 100);
+        let pool = Pool::<str>::with_capacity(1000);
+        assert!(pool.pool.capacity() >= 1000);
+    }
+
+    #[test]
+    fn test_get() {
+        let pool = Pool::<str>::with_bloom_filter(16);
+        assert!(pool.get("x").is_none());
+        assert!(!pool.contains("x"));
+        let a = pool.intern("x");
+        assert_eq!(pool.get("x"), Some(a));
+        assert!(pool.contains("x"));
+        assert_eq!(pool.to_vec().len(), 1);
+    }
+
+    #[test]
+    fn test_try_intern() {
+        let pool = Pool::<str>::new();
+        let lock = pool.pool.write(pool.pool.shard_of(pool.hash("x")));
+        assert!(pool.try_intern("x").is_none());
+        drop(lock);
+        let a = pool.try_intern("x").unwrap();
+        assert_eq!(pool.try_intern("x"), Some(a));
+    }
+
+    #[test]
+    fn test_with_normalizer() {
+        let pool = Pool::<str>::with_normalizer(|s: &str| {
+            if s.contains(' ') {
+                Some(s.replace(' ', "").into())
+            } else {
+                None
+            }
+        });
+        let a = pool.intern("a b");
+        assert_eq!(a.get(), "ab");
+        assert_eq!(pool.get("ab"), Some(a.clone()));
+        assert!(pool.contains(" a b "));
+        let r = pool.intern_many(&["ab", "a  b", "c d"]);
+        assert_eq!(r[1], a);
+        assert_eq!(r[2].get(), "cd");
+        assert_eq!(pool.len(), 2);
+    }
+
+    #[test]
+    fn test_to_vec() {
+        let pool = Pool::<str>::new();
+        let a = pool.intern("a");
+        pool.intern("b");
+        let mut all = pool.to_vec();
+        all.sort();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0], a);
+        assert_eq!(all[1].get(), "b");
+    }
+
+    #[test]
+    fn test_bloom_filter() {
+        let pool = Pool::<str>::with_bloom_filter(16);
+        let h1 = pool.intern("asd");
+        let h2 = pool.intern("asd");
+        let h3 = pool.intern(String::from("asd"));
+        assert_eq!(h1, h2);
+        assert_eq!(h1, h3);
+        assert_eq!(pool.len(), 1);
+        for i in 0..100 {
+            pool.intern(i.to_string());
+        }
+        assert_eq!(pool.len(), 101);
+    }
+
+    #[test]
+    #[cfg(not(feature = "single-thread"))]
+    fn test_concurrent_release() {
+        use std::thread::spawn;
+
+        let pool = Arc::new(Pool::<str>::new());
+        pool.set_release_on_drop(true);
+        let t: Vec<_> = (0..8)
+            .map(|_| {
+                let pool = pool.clone();
+                spawn(move || {
+                    for i in 0..1000 {
+                        let s = (i % 10).to_string();
+                        let a = pool.intern(s.as_str());
+                        assert_eq!(a.get(), s);
+                        pool.on_drop(&a);
+                    }
+                })
+            })
+            .collect();
+        for r in t.into_iter() {
+            assert!(r.join().is_ok());
+        }
+        assert!(pool.len() <= 10);
+    }
+
+    #[test]
+    #[cfg(not(feature = "single-thread"))]
     fn test_concurrent_2_gc() {
         use std::thread::spawn;
 
         let t: Vec<_> = (0..100)
-            .into_iter()
             .map(|i| {
                 spawn(move || {
                     let v: Vec<_> = (0..100)
-                        .into_iter()
-                        .map(|_| spawn(move || STR_POOL.intern(i.to_string(), Arc::from)))
+                        .map(|_| spawn(move || STR_POOL.intern(i.to_string())))
                         .collect();
                     for b in v.into_iter() {
                         assert_eq!(b.join().unwrap().get(), i.to_string());
                     }
                 })
             })
-            .zip((0..100).into_iter().map(|_| {
+            .zip((0..100).map(|_| {
                 spawn(move || {
                     let v: Vec<_> = (0..100)
-                        .into_iter()
                         .map(|_| {
                             spawn(move || {
                                 STR_POOL.collect_garbage();
@@ -241,4 +1352,77 @@ mod tests {
             assert!(b.join().is_ok());
         }
     }
+
+    /// A pool hashing the same in every execution of a model
+    #[cfg(loom)]
+    fn loom_pool(
+    ) -> Pool<str, std::hash::BuildHasherDefault<std::collections::hash_map::DefaultHasher>> {
+        Pool::with_hasher(Default::default())
+    }
+
+    #[test]
+    #[cfg(loom)]
+    fn test_loom_intern() {
+        loom::model(|| {
+            let pool = loom::sync::Arc::new(loom_pool());
+            let p = pool.clone();
+            let t = loom::thread::spawn(move || p.intern("loom"));
+            let a = pool.intern("loom");
+            let b = t.join().unwrap();
+            assert_eq!(a, b);
+            assert_eq!(pool.len(), 1);
+        });
+    }
+
+    #[test]
+    #[cfg(loom)]
+    fn test_loom_release() {
+        loom::model(|| {
+            let pool = loom::sync::Arc::new(loom_pool());
+            let a = pool.intern("loom");
+            let p = pool.clone();
+            let t = loom::thread::spawn(move || p.intern("loom"));
+            pool.release(a);
+            let b = t.join().unwrap();
+            // Whether the release came first or not, the entry of `b` is in the pool
+            assert_eq!(pool.get("loom"), Some(b));
+            assert_eq!(pool.len(), 1);
+        });
+    }
+
+    #[test]
+    #[cfg(loom)]
+    fn test_loom_gc() {
+        loom::model(|| {
+            let pool = loom::sync::Arc::new(loom_pool());
+            pool.intern("loom");
+            let p = pool.clone();
+            let t = loom::thread::spawn(move || p.collect_garbage().removed);
+            let a = pool.intern("loom");
+            let removed = t.join().unwrap();
+            // The garbage entry is either collected before `a` is interned or revived by it
+            assert_eq!(pool.get("loom"), Some(a));
+            assert!(removed <= 1);
+        });
+    }
+
+    #[test]
+    #[cfg(loom)]
+    fn test_loom_bloom_filter() {
+        loom::model(|| {
+            let pool = loom::sync::Arc::new(Pool::<str, _>::with_bloom_filter_and_hasher(
+                16,
+                std::hash::BuildHasherDefault::<std::collections::hash_map::DefaultHasher>::default(
+                ),
+            ));
+            let p = pool.clone();
+            let t = loom::thread::spawn(move || p.intern("loom"));
+            // Once the entry is in the table the filter lets the lookup through
+            if pool.pool.find("loom", pool.hash("loom"), |_| ()).is_some() {
+                assert!(pool.get("loom").is_some());
+            }
+            let a = t.join().unwrap();
+            assert_eq!(pool.get("loom"), Some(a));
+        });
+    }
 }
@@ -0,0 +1,86 @@
+//! Background garbage collection of the global pools
+
+use std::{
+    sync::mpsc::{self, RecvTimeoutError, Sender},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use super::{BYTES_POOL, C_STR_POOL, OS_STR_POOL, PATH_POOL, STR_POOL};
+
+/// Spawn a thread running [`collect_garbage`](super::Pool::collect_garbage) on every global pool each `interval`
+///
+/// The thread stops when the returned handle is dropped.
+/// This initializes the global pools, so call [`configure_global_pools`](super::configure_global_pools) first if needed
+///
+/// # Example
+/// ```
+/// # use std::time::Duration;
+/// let gc = pstr::pool::spawn_gc(Duration::from_secs(60));
+/// // ...
+/// gc.stop();
+/// ```
+pub fn spawn_gc(interval: Duration) -> GcHandle {
+    let (stop, rx) = mpsc::channel();
+    let thread = thread::Builder::new()
+        .name("pstr-gc".into())
+        .spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = rx.recv_timeout(interval) {
+                STR_POOL.collect_garbage();
+                OS_STR_POOL.collect_garbage();
+                C_STR_POOL.collect_garbage();
+                BYTES_POOL.collect_garbage();
+                PATH_POOL.collect_garbage();
+            }
+        })
+        .expect("failed to spawn gc thread");
+    GcHandle {
+        stop: Some(stop),
+        thread: Some(thread),
+    }
+}
+
+/// Handle to the thread started by [`spawn_gc`], stops it when dropped
+#[derive(Debug)]
+pub struct GcHandle {
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl GcHandle {
+    /// Stop the thread and wait for it to finish
+    #[inline]
+    pub fn stop(self) {
+        drop(self)
+    }
+}
+
+impl Drop for GcHandle {
+    fn drop(&mut self) {
+        drop(self.stop.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spawn_gc() {
+        let gc = spawn_gc(Duration::from_millis(1));
+        BYTES_POOL.intern(&b"background_gc"[..]);
+        let mut removed = false;
+        for _ in 0..1000 {
+            if !BYTES_POOL.contains(b"background_gc") {
+                removed = true;
+                break;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+        gc.stop();
+        assert!(removed);
+    }
+}
@@ -0,0 +1,90 @@
+//! Bulk interning
+
+use std::hash::{BuildHasher, Hash};
+
+use super::{table::value, Intern, Key, Pointee, Pool, PoolValue};
+
+impl<T: Eq + Hash + ?Sized + Pointee, S: BuildHasher + Clone> Pool<T, S> {
+    /// Intern every value of `values`, returning the interns in the same order
+    ///
+    /// Values are grouped by shard, so each shard is locked once for the whole batch instead of once per value
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::pool::Pool;
+    /// let pool = Pool::<str>::new();
+    /// let r = pool.intern_many(&["a", "b", "a"]);
+    /// assert_eq!(r[0], r[2]);
+    /// assert_eq!(r[1].get(), "b");
+    /// assert_eq!(pool.len(), 2);
+    /// ```
+    pub fn intern_many<A: AsRef<T>>(&self, values: &[A]) -> Vec<Intern<T>>
+    where
+        T: PoolValue,
+    {
+        let normalized: Vec<Option<Box<T>>> =
+            values.iter().map(|v| self.normalize(v.as_ref())).collect();
+        let key = |i: usize| normalized[i].as_deref().unwrap_or(values[i].as_ref());
+        let hashes: Vec<u64> = (0..values.len()).map(|i| self.hash(key(i))).collect();
+        let shards: Vec<usize> = hashes.iter().map(|&h| self.pool.shard_of(h)).collect();
+        let mut order: Vec<usize> = (0..values.len()).collect();
+        order.sort_unstable_by_key(|&i| shards[i]);
+
+        let mut interns: Vec<Option<Intern<T>>> = vec![None; values.len()];
+        let mut inserted = Vec::new();
+        for group in order.chunk_by(|&a, &b| shards[a] == shards[b]) {
+            let mut shard = self.pool.write(shards[group[0]]);
+            for &i in group {
+                let key = key(i);
+                let hash = hashes[i];
+                let found = shard
+                    .raw_entry()
+                    .from_hash(hash, |k| k.0.precomputed_hash() == hash && k.0.get() == key);
+                let intern = match found {
+                    Some((k, _)) => {
+                        self.counters.hit();
+                        k.0.clone()
+                    }
+                    None => {
+                        let intern = Intern::new(key, hash);
+                        shard.insert(Key(intern.clone()), value());
+                        self.on_insert(intern.get());
+                        if let Some(filter) = &self.filter {
+                            filter.insert(hash);
+                        }
+                        inserted.push(i);
+                        intern
+                    }
+                };
+                interns[i] = Some(intern);
+            }
+        }
+        // The hooks run once every shard is unlocked again
+        for &i in &inserted {
+            self.hook_insert(interns[i].as_ref().unwrap().get());
+        }
+        if !inserted.is_empty() {
+            self.maybe_collect_garbage();
+        }
+        interns.into_iter().map(Option::unwrap).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern_many() {
+        let pool = Pool::<str>::with_bloom_filter(100);
+        let existing = pool.intern("5");
+        let values: Vec<String> = (0..100).map(|i| (i % 10).to_string()).collect();
+        let interns = pool.intern_many(&values);
+        assert_eq!(pool.len(), 10);
+        for (v, i) in values.iter().zip(&interns) {
+            assert_eq!(i.get(), v);
+            assert_eq!(*i, pool.intern(v.as_str()));
+        }
+        assert_eq!(interns[5], existing);
+    }
+}
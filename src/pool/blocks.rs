@@ -0,0 +1,96 @@
+//! Hashing in fixed size blocks
+//!
+//! Hashers like ahash and fxhash mix every `write` as a unit, so writing a value in pieces gives another hash
+//! than writing it at once. [`Blocks`] passes the bytes on in blocks of [`BLOCK`] bytes and the rest at the end,
+//! so the hash only depends on the bytes, which lets [`IStrBuilder`](crate::IStrBuilder) hash its content as it arrives
+
+use std::{
+    cell::{Cell, RefCell},
+    hash::{BuildHasher, Hasher},
+};
+
+/// Size of the blocks written to the inner hasher
+pub(crate) const BLOCK: usize = 32;
+
+/// Builds [`BlockHasher`]s around the hashers of `S`
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Blocks<S>(pub(crate) S);
+
+impl<S: BuildHasher> BuildHasher for Blocks<S> {
+    type Hasher = BlockHasher<S::Hasher>;
+
+    #[inline]
+    fn build_hasher(&self) -> Self::Hasher {
+        BlockHasher {
+            inner: RefCell::new(self.0.build_hasher()),
+            buf: [0; BLOCK],
+            len: Cell::new(0),
+        }
+    }
+}
+
+/// Buffers the written bytes and writes them to `H` a block at a time
+/// The bytes left are written by `finish`, writing more after it starts a new block
+pub(crate) struct BlockHasher<H> {
+    inner: RefCell<H>,
+    buf: [u8; BLOCK],
+    len: Cell<usize>,
+}
+
+impl<H: Hasher> Hasher for BlockHasher<H> {
+    #[inline]
+    fn finish(&self) -> u64 {
+        let mut inner = self.inner.borrow_mut();
+        let len = self.len.replace(0);
+        if len != 0 {
+            inner.write(&self.buf[..len]);
+        }
+        inner.finish()
+    }
+
+    fn write(&mut self, mut bytes: &[u8]) {
+        let inner = self.inner.get_mut();
+        let len = self.len.get();
+        if len != 0 {
+            let n = (BLOCK - len).min(bytes.len());
+            self.buf[len..len + n].copy_from_slice(&bytes[..n]);
+            bytes = &bytes[n..];
+            if len + n < BLOCK {
+                self.len.set(len + n);
+                return;
+            }
+            inner.write(&self.buf);
+        }
+        let mut blocks = bytes.chunks_exact(BLOCK);
+        for block in &mut blocks {
+            inner.write(block);
+        }
+        let rest = blocks.remainder();
+        self.buf[..rest.len()].copy_from_slice(rest);
+        self.len.set(rest.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pool::GlobalHasher;
+
+    #[test]
+    fn test_split_writes() {
+        let s = Blocks(GlobalHasher::default());
+        let bytes: Vec<u8> = (0..100).collect();
+        let whole = {
+            let mut h = s.build_hasher();
+            h.write(&bytes);
+            h.finish()
+        };
+        for split in [1, 7, 31, 32, 33, 64, 99] {
+            let mut h = s.build_hasher();
+            for piece in bytes.chunks(split) {
+                h.write(piece);
+            }
+            assert_eq!(h.finish(), whole, "split {}", split);
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! Probabilistic pre-check for definite misses
+
+use crate::sync::atomic::{AtomicU64, Ordering};
+
+/// Bits allocated per expected item, gives roughly 1% false positives with `HASHES`
+const BITS_PER_ITEM: usize = 10;
+/// Number of bit probes per item
+const HASHES: u64 = 7;
+
+/// A lock-free bloom filter over precomputed hashes  
+/// The pool sets the bits of a entry while the shard of the entry is still locked, so a reader never misses it
+///
+/// Bits are only ever set, never cleared, so a removed entry just degrades into a false positive
+#[derive(Debug)]
+pub(crate) struct BloomFilter {
+    bits: Box<[AtomicU64]>,
+    mask: u64,
+}
+
+impl BloomFilter {
+    /// New a filter sized for about `expected_items` entries
+    pub fn new(expected_items: usize) -> Self {
+        let len = (expected_items.max(1) * BITS_PER_ITEM)
+            .next_power_of_two()
+            .max(64);
+        Self {
+            bits: (0..len / 64).map(|_| AtomicU64::new(0)).collect(),
+            mask: len as u64 - 1,
+        }
+    }
+
+    #[inline]
+    fn probes(&self, hash: u64) -> impl Iterator<Item = u64> {
+        let h2 = hash.rotate_left(32) | 1;
+        let mask = self.mask;
+        (0..HASHES).map(move |i| hash.wrapping_add(i.wrapping_mul(h2)) & mask)
+    }
+
+    /// Mark a hash as seen
+    #[inline]
+    pub fn insert(&self, hash: u64) {
+        // Probes of the same word are merged, so each word is written once
+        let mut words = [(0, 0); HASHES as usize];
+        let mut len = 0;
+        for bit in self.probes(hash) {
+            let (word, mask) = ((bit / 64) as usize, 1 << (bit % 64));
+            match words[..len].iter_mut().find(|(w, _)| *w == word) {
+                Some((_, m)) => *m |= mask,
+                None => {
+                    words[len] = (word, mask);
+                    len += 1;
+                }
+            }
+        }
+        for &(word, mask) in &words[..len] {
+            self.bits[word].fetch_or(mask, Ordering::Relaxed);
+        }
+    }
+
+    /// Return `false` if the hash has definitely never been inserted
+    #[inline]
+    pub fn may_contain(&self, hash: u64) -> bool {
+        self.probes(hash).all(|bit| {
+            self.bits[(bit / 64) as usize].load(Ordering::Relaxed) & (1 << (bit % 64)) != 0
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_false_negative() {
+        let filter = BloomFilter::new(100);
+        let hashes: Vec<_> = (0..100u64)
+            .map(|i| i.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+            .collect();
+        for h in hashes.iter() {
+            filter.insert(*h);
+        }
+        assert!(hashes.iter().all(|h| filter.may_contain(*h)));
+        assert!(!BloomFilter::new(100).may_contain(hashes[0]));
+    }
+}
@@ -0,0 +1,194 @@
+//! Thread-local cache in front of the global string pool
+
+use std::{cell::RefCell, mem};
+
+use hashbrown::hash_map::RawEntryMut;
+
+use super::{
+    test_scope::{in_test_scope, with_str_pool},
+    Intern, STR_POOL,
+};
+
+/// Marks the end of the recency list
+const NIL: usize = usize::MAX;
+
+thread_local! {
+    static CACHE: RefCell<Option<Lru>> = const { RefCell::new(None) };
+}
+
+/// Cache the `capacity` most recently interned strings of the current thread in front of [`STR_POOL`]
+///
+/// `IStr::new` and friends look in the cache before the shared pool, so repeated strings skip the shard locks.
+/// The cache holds a handle to every cached string, which keeps their entries from being collected or released
+/// until they fall out of it or the cache is disabled.
+/// Enabling again replaces the cache, a `capacity` of 0 disables it
+///
+/// # Example
+/// ```
+/// # use pstr::{IStr, pool::{disable_thread_cache, enable_thread_cache}};
+/// enable_thread_cache(1024);
+/// let a = IStr::new("thread cached");
+/// let b = IStr::new("thread cached");
+/// assert_eq!(a.as_ptr(), b.as_ptr());
+/// disable_thread_cache();
+/// ```
+pub fn enable_thread_cache(capacity: usize) {
+    let cache = if capacity == 0 {
+        None
+    } else {
+        Some(Lru::new(capacity))
+    };
+    // Drop the old cache outside the borrow, its handles may release entries
+    let old = CACHE.with(|c| c.replace(cache));
+    drop(old);
+}
+
+/// Drop the cache of the current thread, see [`enable_thread_cache`]
+#[inline]
+pub fn disable_thread_cache() {
+    enable_thread_cache(0)
+}
+
+/// Intern `s` into [`STR_POOL`], through the cache of the current thread if it is enabled  
+/// Inside a [test scope](super::test_scope()) `s` goes into the pool of the scope instead, bypassing the cache
+pub(crate) fn intern_str(s: &str) -> Intern<str> {
+    if !in_test_scope() {
+        let cached = CACHE.with(|c| {
+            let mut c = c.try_borrow_mut().ok()?;
+            let c = c.as_mut()?;
+            Some(c.get_or_insert(s, STR_POOL.hash(s)))
+        });
+        if let Some(v) = cached {
+            return v;
+        }
+    }
+    with_str_pool(|pool| pool.intern(s))
+}
+
+struct Slot {
+    intern: Intern<str>,
+    prev: usize,
+    next: usize,
+}
+
+/// A least recently used cache, the slots form a doubly linked list from the most to the least recent
+struct Lru {
+    capacity: usize,
+    /// Slot index of every cached string, hashed by its precomputed hash
+    map: hashbrown::HashMap<usize, (), ()>,
+    slots: Vec<Slot>,
+    head: usize,
+    tail: usize,
+}
+
+impl Lru {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            map: hashbrown::HashMap::with_capacity_and_hasher(capacity, ()),
+            slots: Vec::with_capacity(capacity),
+            head: NIL,
+            tail: NIL,
+        }
+    }
+
+    fn get_or_insert(&mut self, s: &str, hash: u64) -> Intern<str> {
+        let slots = &self.slots;
+        let found = self
+            .map
+            .raw_entry()
+            .from_hash(hash, |&i| slots[i].intern.get() == s)
+            .map(|(&i, _)| i);
+        if let Some(i) = found {
+            self.unlink(i);
+            self.push_front(i);
+            return self.slots[i].intern.clone();
+        }
+
+        let intern = STR_POOL.intern_hashed(s, hash, true).unwrap().0;
+        let i = if self.slots.len() < self.capacity {
+            self.slots.push(Slot {
+                intern: intern.clone(),
+                prev: NIL,
+                next: NIL,
+            });
+            self.slots.len() - 1
+        } else {
+            let i = self.tail;
+            self.unlink(i);
+            let old_hash = self.slots[i].intern.precomputed_hash();
+            if let RawEntryMut::Occupied(e) =
+                self.map.raw_entry_mut().from_hash(old_hash, |&j| j == i)
+            {
+                e.remove();
+            }
+            let old = mem::replace(&mut self.slots[i].intern, intern.clone());
+            STR_POOL.on_drop(&old);
+            i
+        };
+        self.push_front(i);
+        let slots = &self.slots;
+        if let RawEntryMut::Vacant(e) = self.map.raw_entry_mut().from_hash(hash, |&j| j == i) {
+            e.insert_with_hasher(hash, i, (), |&j| slots[j].intern.precomputed_hash());
+        }
+        intern
+    }
+
+    fn unlink(&mut self, i: usize) {
+        let Slot { prev, next, .. } = self.slots[i];
+        match prev {
+            NIL => self.head = next,
+            prev => self.slots[prev].next = next,
+        }
+        match next {
+            NIL => self.tail = prev,
+            next => self.slots[next].prev = prev,
+        }
+    }
+
+    fn push_front(&mut self, i: usize) {
+        self.slots[i].prev = NIL;
+        self.slots[i].next = self.head;
+        match self.head {
+            NIL => self.tail = i,
+            head => self.slots[head].prev = i,
+        }
+        self.head = i;
+    }
+}
+
+impl Drop for Lru {
+    fn drop(&mut self) {
+        for slot in &self.slots {
+            STR_POOL.on_drop(&slot.intern);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thread_cache() {
+        enable_thread_cache(2);
+        let a = intern_str("thread_cache_a");
+        intern_str("thread_cache_b");
+        assert_eq!(intern_str("thread_cache_a"), a);
+        // `b` is the least recent now
+        intern_str("thread_cache_c");
+        CACHE.with(|c| {
+            let c = c.borrow();
+            let c = c.as_ref().unwrap();
+            let cached: Vec<&str> = c.slots.iter().map(|s| s.intern.get()).collect();
+            assert_eq!(c.map.len(), 2);
+            assert!(cached.contains(&"thread_cache_a"));
+            assert!(cached.contains(&"thread_cache_c"));
+            assert_eq!(c.slots[c.head].intern.get(), "thread_cache_c");
+            assert_eq!(c.slots[c.tail].intern.get(), "thread_cache_a");
+        });
+        assert_eq!(intern_str("thread_cache_a"), a);
+        disable_thread_cache();
+        assert!(CACHE.with(|c| c.borrow().is_none()));
+    }
+}
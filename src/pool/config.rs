@@ -0,0 +1,62 @@
+//! Configuration of the global pools
+
+use std::hash::Hash;
+
+use once_cell::sync::OnceCell;
+
+use super::{GlobalHasher, Pointee, Pool};
+
+static GLOBAL_CONFIG: OnceCell<GlobalPoolConfig> = OnceCell::new();
+
+/// Options for the global pools, see [`configure_global_pools`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GlobalPoolConfig {
+    /// Number of entries each global pool has space for before reallocating
+    pub capacity: usize,
+    /// Number of shards of each global pool, must be a power of two greater than 1, see [`Pool::with_shard_amount`]
+    pub shard_amount: Option<usize>,
+}
+
+/// Configure the global pools, must be called before any global pool is used
+///
+/// Return `Err` with the rejected config if `shard_amount` isn't a power of two greater than 1,
+/// if it was already configured, or if a global pool was already used
+///
+/// # Example
+/// ```
+/// # use pstr::{IStr, pool::{configure_global_pools, GlobalPoolConfig}};
+/// let config = GlobalPoolConfig {
+///     capacity: 1 << 16,
+///     shard_amount: Some(64),
+/// };
+/// let bad = GlobalPoolConfig { shard_amount: Some(3), ..config };
+/// assert_eq!(configure_global_pools(bad), Err(bad));
+/// assert!(configure_global_pools(config).is_ok());
+/// let _s = IStr::new("configured");
+/// assert!(configure_global_pools(config).is_err());
+/// ```
+pub fn configure_global_pools(config: GlobalPoolConfig) -> Result<(), GlobalPoolConfig> {
+    // Checked here, a global pool failing to build would panic on first use instead
+    if let Some(shard_amount) = config.shard_amount {
+        if shard_amount <= 1 || !shard_amount.is_power_of_two() {
+            return Err(config);
+        }
+    }
+    GLOBAL_CONFIG.set(config)
+}
+
+/// Create a global pool from the configuration, fixing the configuration from now on  
+/// With the `metrics` feature a pool with a `label` reports to metrics, see `Pool::with_metrics_label`
+#[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+pub(crate) fn global_pool<T: Eq + Hash + ?Sized + Pointee>(
+    label: Option<&str>,
+) -> Pool<T, GlobalHasher> {
+    let config = GLOBAL_CONFIG.get_or_init(Default::default);
+    let pool = Pool::build(config.capacity, config.shard_amount, Default::default());
+    #[cfg(feature = "metrics")]
+    let pool = match label {
+        Some(label) => pool.with_metrics_label(std::sync::Arc::<str>::from(label)),
+        None => pool,
+    };
+    pool
+}
@@ -0,0 +1,281 @@
+//! Hit and miss counters
+//!
+//! With the `metrics` feature labeled pools also report their entries, bytes, hits, misses and evictions
+//! to the [metrics](https://crates.io/crates/metrics) facade
+
+#[cfg(any(feature = "counters", feature = "metrics"))]
+use std::hash::{BuildHasher, Hash};
+#[cfg(feature = "counters")]
+use std::sync::atomic::AtomicU64;
+#[cfg(any(feature = "counters", feature = "metrics"))]
+use std::sync::atomic::Ordering;
+
+#[cfg(any(feature = "counters", feature = "metrics"))]
+use super::{Pointee, Pool};
+
+/// Counts of pool operations since the pool was created, see [`Pool::counters`]
+#[cfg(feature = "counters")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PoolCounters {
+    /// Number of interns which found the value already in the pool
+    pub hits: u64,
+    /// Number of interns which inserted a new entry
+    pub misses: u64,
+    /// Number of finished garbage collection passes over the whole pool
+    pub gc_runs: u64,
+}
+
+/// The atomic counters of a pool, empty and free without the `counters` and `metrics` features
+#[derive(Debug, Default)]
+pub(super) struct Counters {
+    #[cfg(feature = "counters")]
+    hits: AtomicU64,
+    #[cfg(feature = "counters")]
+    misses: AtomicU64,
+    #[cfg(feature = "counters")]
+    gc_runs: AtomicU64,
+    /// Value of the `pool` label, pools without a label don't report to metrics
+    #[cfg(feature = "metrics")]
+    label: Option<::metrics::SharedString>,
+}
+
+impl Counters {
+    #[inline]
+    pub fn hit(&self) {
+        #[cfg(feature = "counters")]
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        if let Some(label) = &self.label {
+            ::metrics::counter!("pstr_pool_hits_total", "pool" => label.clone()).increment(1);
+        }
+    }
+
+    #[inline]
+    pub fn miss(&self) {
+        #[cfg(feature = "counters")]
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        if let Some(label) = &self.label {
+            ::metrics::counter!("pstr_pool_misses_total", "pool" => label.clone()).increment(1);
+        }
+    }
+
+    #[inline]
+    pub fn gc_run(&self) {
+        #[cfg(feature = "counters")]
+        self.gc_runs.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A entry was evicted by garbage collection
+    #[inline]
+    pub fn evicted(&self) {
+        #[cfg(feature = "metrics")]
+        if let Some(label) = &self.label {
+            ::metrics::counter!("pstr_pool_gc_removed_total", "pool" => label.clone()).increment(1);
+        }
+    }
+
+    /// A entry of `bytes` was added to the pool
+    #[inline]
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    pub fn added(&self, bytes: usize) {
+        #[cfg(feature = "metrics")]
+        if let Some(label) = &self.label {
+            ::metrics::gauge!("pstr_pool_entries", "pool" => label.clone()).increment(1.0);
+            ::metrics::gauge!("pstr_pool_bytes", "pool" => label.clone()).increment(bytes as f64);
+        }
+    }
+
+    /// A entry of `bytes` was removed from the pool
+    #[inline]
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    pub fn removed(&self, bytes: usize) {
+        #[cfg(feature = "metrics")]
+        if let Some(label) = &self.label {
+            ::metrics::gauge!("pstr_pool_entries", "pool" => label.clone()).decrement(1.0);
+            ::metrics::gauge!("pstr_pool_bytes", "pool" => label.clone()).decrement(bytes as f64);
+        }
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl<T: Eq + Hash + ?Sized + Pointee, S: BuildHasher + Clone> Pool<T, S> {
+    /// Report the entries, bytes, hits, misses and evicted entries of the pool to metrics, with `label` as the `pool` label  
+    ///
+    /// Only labeled pools report, the global pools are labeled by their type and registry pools by their name.
+    /// Dropping the pool removes its remaining entries from the gauges
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::pool::Pool;
+    /// let pool = Pool::<str>::new().with_metrics_label("requests");
+    /// pool.intern("reported");
+    /// ```
+    pub fn with_metrics_label(mut self, label: impl Into<::metrics::SharedString>) -> Self {
+        self.counters.label = Some(label.into());
+        self
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl<T: Eq + Hash + ?Sized + Pointee, S> Drop for Pool<T, S> {
+    fn drop(&mut self) {
+        // The entries leave with the pool, so they leave the gauges too
+        if let Some(label) = &self.counters.label {
+            let entries = self.entries.load(Ordering::Relaxed);
+            let bytes = self.bytes.load(Ordering::Relaxed);
+            ::metrics::gauge!("pstr_pool_entries", "pool" => label.clone())
+                .decrement(entries as f64);
+            ::metrics::gauge!("pstr_pool_bytes", "pool" => label.clone()).decrement(bytes as f64);
+        }
+    }
+}
+
+#[cfg(feature = "counters")]
+impl<T: Eq + Hash + ?Sized + Pointee, S: BuildHasher + Clone> Pool<T, S> {
+    /// Get the hit, miss and garbage collection counters  
+    ///
+    /// A hit rate well above zero shows interning is actually deduplicating the workload
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::pool::Pool;
+    /// let pool = Pool::<str>::new();
+    /// pool.intern("a");
+    /// pool.intern("a");
+    /// pool.collect_garbage();
+    /// let counters = pool.counters();
+    /// assert_eq!(counters.hits, 1);
+    /// assert_eq!(counters.misses, 1);
+    /// assert_eq!(counters.gc_runs, 1);
+    /// ```
+    pub fn counters(&self) -> PoolCounters {
+        PoolCounters {
+            hits: self.counters.hits.load(Ordering::Relaxed),
+            misses: self.counters.misses.load(Ordering::Relaxed),
+            gc_runs: self.counters.gc_runs.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "counters"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counters() {
+        let pool = Pool::<str>::with_shard_amount(4);
+        pool.intern_many(&["a", "b", "a"]);
+        pool.intern("b");
+        assert_eq!(pool.counters().hits, 2);
+        assert_eq!(pool.counters().misses, 2);
+        while !pool.collect_garbage_incremental(1) {}
+        assert_eq!(pool.counters().gc_runs, 1);
+    }
+}
+
+#[cfg(all(test, feature = "metrics"))]
+mod metrics_tests {
+    use std::sync::{Arc, Mutex};
+
+    use metrics::{
+        Counter, CounterFn, Gauge, GaugeFn, Histogram, Key, KeyName, Metadata, Recorder,
+        SharedString, Unit,
+    };
+
+    use super::*;
+
+    /// Sum of every gauge and counter change, by name and `pool` label
+    #[derive(Default)]
+    struct Sums(Mutex<Vec<(String, String, f64)>>);
+
+    struct Series(Arc<Sums>, String, String);
+
+    impl Series {
+        fn add(&self, v: f64) {
+            let mut sums = self.0 .0.lock().unwrap();
+            match sums
+                .iter_mut()
+                .find(|(n, l, _)| *n == self.1 && *l == self.2)
+            {
+                Some((_, _, sum)) => *sum += v,
+                None => sums.push((self.1.clone(), self.2.clone(), v)),
+            }
+        }
+    }
+
+    impl CounterFn for Series {
+        fn increment(&self, v: u64) {
+            self.add(v as f64)
+        }
+
+        fn absolute(&self, _: u64) {}
+    }
+
+    impl GaugeFn for Series {
+        fn increment(&self, v: f64) {
+            self.add(v)
+        }
+
+        fn decrement(&self, v: f64) {
+            self.add(-v)
+        }
+
+        fn set(&self, _: f64) {}
+    }
+
+    struct TestRecorder(Arc<Sums>);
+
+    impl TestRecorder {
+        fn series(&self, key: &Key) -> Arc<Series> {
+            let label = key
+                .labels()
+                .find(|l| l.key() == "pool")
+                .map(|l| l.value().to_string());
+            Arc::new(Series(
+                self.0.clone(),
+                key.name().to_string(),
+                label.unwrap_or_default(),
+            ))
+        }
+    }
+
+    impl Recorder for TestRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            Counter::from_arc(self.series(key))
+        }
+
+        fn register_gauge(&self, key: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::from_arc(self.series(key))
+        }
+
+        fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::noop()
+        }
+    }
+
+    #[test]
+    fn test_metrics_label() {
+        let sums = Arc::new(Sums::default());
+        metrics::with_local_recorder(&TestRecorder(sums.clone()), || {
+            let pool = Pool::<str>::new().with_metrics_label("labeled");
+            pool.intern("a");
+            pool.intern("a");
+            let unlabeled = Pool::<str>::new();
+            unlabeled.intern("b");
+            drop(unlabeled);
+            drop(pool);
+        });
+        let sums = sums.0.lock().unwrap();
+        let sum = |name: &str| sums.iter().find(|(n, _, _)| n == name).unwrap().2;
+        assert!(sums.iter().all(|(_, label, _)| label == "labeled"));
+        assert_eq!(sum("pstr_pool_hits_total"), 1.0);
+        assert_eq!(sum("pstr_pool_misses_total"), 1.0);
+        assert_eq!(sum("pstr_pool_entries"), 0.0);
+        assert_eq!(sum("pstr_pool_bytes"), 0.0);
+    }
+}
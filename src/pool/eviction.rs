@@ -0,0 +1,41 @@
+//! Custom retention rules for garbage collection
+
+/// Decide whether garbage collection removes an unreferenced entry  
+///
+/// Only entries with reference count == 1 (held by nothing but the pool) are ever offered to the policy,
+/// evicting a referenced entry would break the pointer identity of interned strings
+///
+/// # Example
+/// ```
+/// # use pstr::pool::{Pool, EvictionPolicy};
+/// let pool = Pool::<str>::new();
+/// pool.set_eviction_policy(|s: &str| !s.starts_with("keep:"));
+///
+/// pool.intern("keep:a");
+/// pool.intern("drop:b");
+/// pool.collect_garbage();
+///
+/// assert!(pool.intern("keep:a").get() == "keep:a");
+/// ```
+pub trait EvictionPolicy<T: ?Sized>: Send + Sync {
+    /// Return `true` to remove the unreferenced `value` from the pool
+    fn should_evict(&self, value: &T) -> bool;
+}
+
+impl<T: ?Sized, F: Fn(&T) -> bool + Send + Sync> EvictionPolicy<T> for F {
+    #[inline]
+    fn should_evict(&self, value: &T) -> bool {
+        self(value)
+    }
+}
+
+/// Evict every unreferenced entry, the default behavior of garbage collection
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EvictAll;
+
+impl<T: ?Sized> EvictionPolicy<T> for EvictAll {
+    #[inline]
+    fn should_evict(&self, _: &T) -> bool {
+        true
+    }
+}
@@ -0,0 +1,202 @@
+//! Automatic garbage collection
+
+use std::{
+    hash::{BuildHasher, Hash},
+    mem,
+    sync::atomic::Ordering,
+    time::Duration,
+};
+
+use super::{Pointee, Pool};
+
+/// Disabled value of `Pool::next_gc`
+pub(super) const NO_GC: usize = usize::MAX;
+
+/// What a garbage collection did, see [`Pool::collect_garbage`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GcReport {
+    /// Number of removed entries
+    pub removed: usize,
+    /// Number of entries left in the pool
+    pub remaining: usize,
+    /// Sum of the byte sizes of the removed entries
+    pub bytes_freed: usize,
+    /// Time spent collecting
+    pub duration: Duration,
+}
+
+impl GcReport {
+    /// Emit the report as a `tracing` event
+    #[cfg(feature = "tracing")]
+    pub(super) fn trace(&self) {
+        tracing::debug!(
+            removed = self.removed,
+            remaining = self.remaining,
+            bytes_freed = self.bytes_freed,
+            duration_us = self.duration.as_micros() as u64,
+            "garbage collected"
+        );
+    }
+}
+
+/// When to run garbage collection automatically, see [`Pool::set_gc_threshold`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GcThreshold {
+    /// Collect when the pool holds more than this many entries
+    Entries(usize),
+    /// Collect when the entries of the pool hold more than this many bytes
+    Bytes(usize),
+}
+
+impl GcThreshold {
+    #[inline]
+    fn limit(self) -> usize {
+        match self {
+            Self::Entries(v) | Self::Bytes(v) => v,
+        }
+    }
+}
+
+impl<T: Eq + Hash + ?Sized + Pointee, S: BuildHasher + Clone> Pool<T, S> {
+    /// Run [`collect_garbage`](Pool::collect_garbage) automatically when interning a new entry crosses `threshold`
+    ///
+    /// If most entries are still referenced, the next collection is postponed until the pool doubles in size,
+    /// so interning never degrades into collecting on every insertion
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::pool::{GcThreshold, Pool};
+    /// let pool = Pool::<str>::new();
+    /// pool.set_gc_threshold(GcThreshold::Entries(100));
+    /// for i in 0..1000 {
+    ///     pool.intern(i.to_string());
+    /// }
+    /// assert!(pool.len() <= 100);
+    /// ```
+    pub fn set_gc_threshold(&self, threshold: GcThreshold) {
+        *self.gc_threshold.write() = Some(threshold);
+        self.next_gc.store(threshold.limit(), Ordering::Relaxed);
+    }
+
+    /// Stop running garbage collection automatically
+    pub fn clear_gc_threshold(&self) {
+        *self.gc_threshold.write() = None;
+        self.next_gc.store(NO_GC, Ordering::Relaxed);
+    }
+
+    /// Get the threshold set with [`set_gc_threshold`](Pool::set_gc_threshold)
+    pub fn gc_threshold(&self) -> Option<GcThreshold> {
+        *self.gc_threshold.read()
+    }
+
+    /// Account for a entry added to the pool, the hooks are run by [`hook_insert`](Pool::hook_insert) once the shard is unlocked
+    #[inline]
+    pub(super) fn on_insert(&self, value: &T) {
+        self.counters.miss();
+        #[cfg(feature = "tracing")]
+        tracing::trace!(bytes = mem::size_of_val::<T>(value), "intern miss");
+        self.entries.fetch_add(1, Ordering::Relaxed);
+        self.bytes
+            .fetch_add(mem::size_of_val::<T>(value), Ordering::Relaxed);
+        self.counters.added(mem::size_of_val::<T>(value));
+    }
+
+    /// Account for a entry removed from the pool, the hooks are run by [`hook_remove`](Pool::hook_remove) once the shard is unlocked
+    #[inline]
+    pub(super) fn on_remove(&self, value: &T) {
+        self.entries.fetch_sub(1, Ordering::Relaxed);
+        self.bytes
+            .fetch_sub(mem::size_of_val::<T>(value), Ordering::Relaxed);
+        self.counters.removed(mem::size_of_val::<T>(value));
+    }
+
+    /// Check if hooks are set, so callers only collect the values to pass them if needed
+    #[inline]
+    pub(super) fn has_hooks(&self) -> bool {
+        self.hooks.read().is_some()
+    }
+
+    /// Run the insert hook, must not be called while holding a shard lock
+    #[inline]
+    pub(super) fn hook_insert(&self, value: &T) {
+        if let Some(hooks) = self.hooks.read().as_deref() {
+            hooks.on_insert(value, mem::size_of_val::<T>(value));
+        }
+    }
+
+    /// Run the remove hook, must not be called while holding a shard lock
+    #[inline]
+    pub(super) fn hook_remove(&self, value: &T) {
+        if let Some(hooks) = self.hooks.read().as_deref() {
+            hooks.on_remove(value, mem::size_of_val::<T>(value));
+        }
+    }
+
+    fn gc_level(&self, threshold: GcThreshold) -> usize {
+        match threshold {
+            GcThreshold::Entries(_) => self.entries.load(Ordering::Relaxed),
+            GcThreshold::Bytes(_) => self.bytes.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Collect garbage if the threshold was crossed, must not be called while holding a shard lock
+    #[inline]
+    pub(super) fn maybe_collect_garbage(&self) {
+        let next = self.next_gc.load(Ordering::Relaxed);
+        if next == NO_GC {
+            return;
+        }
+        self.collect_if_over(next);
+    }
+
+    #[cold]
+    fn collect_if_over(&self, next: usize) {
+        let threshold = match self.gc_threshold() {
+            Some(threshold) => threshold,
+            None => return,
+        };
+        if self.gc_level(threshold) <= next {
+            return;
+        }
+        // Claim this collection, other threads crossing the threshold at the same time skip it
+        if self
+            .next_gc
+            .compare_exchange(next, NO_GC, Ordering::Relaxed, Ordering::Relaxed)
+            .is_err()
+        {
+            return;
+        }
+        self.collect_garbage();
+        let next = threshold
+            .limit()
+            .max(self.gc_level(threshold).saturating_mul(2));
+        // A concurrent `clear_gc_threshold` or `set_gc_threshold` wins over this
+        let _ = self
+            .next_gc
+            .compare_exchange(NO_GC, next, Ordering::Relaxed, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gc_threshold() {
+        let pool = Pool::<str>::new();
+        pool.set_gc_threshold(GcThreshold::Bytes(1000));
+        let kept: Vec<_> = (0..100)
+            .map(|i| pool.intern(format!("kept{:06}", i)))
+            .collect();
+        assert_eq!(pool.len(), 100);
+        for i in 0..1000 {
+            pool.intern(format!("temp{:06}", i));
+        }
+        assert!(pool.len() < 500);
+        assert!(kept.iter().all(|v| pool.contains(v.get())));
+        assert_eq!(pool.bytes.load(Ordering::Relaxed), pool.stats().total_bytes);
+        assert_eq!(pool.entries.load(Ordering::Relaxed), pool.len());
+        pool.clear_gc_threshold();
+        assert_eq!(pool.gc_threshold(), None);
+    }
+}
@@ -0,0 +1,131 @@
+//! Callbacks on entries entering and leaving a pool
+
+use std::hash::{BuildHasher, Hash};
+
+use super::{Pointee, Pool};
+
+/// Callbacks run when a entry is added to or removed from a pool, see [`Pool::set_hooks`]  
+///
+/// Hooks run after the shard of the entry is unlocked, so they may use the same pool,
+/// but events of different threads can arrive in another order than they happened
+///
+/// # Example
+/// ```
+/// # use pstr::pool::{Pool, PoolHooks};
+/// # use std::sync::{atomic::{AtomicUsize, Ordering}, Arc};
+/// struct Audit(Arc<AtomicUsize>);
+///
+/// impl PoolHooks<str> for Audit {
+///     fn on_insert(&self, value: &str, _len: usize) {
+///         if value.starts_with("secret:") {
+///             self.0.fetch_add(1, Ordering::Relaxed);
+///         }
+///     }
+/// }
+///
+/// let secrets = Arc::new(AtomicUsize::new(0));
+/// let pool = Pool::<str>::new();
+/// pool.set_hooks(Audit(secrets.clone()));
+/// pool.intern("secret:hunter2");
+/// assert_eq!(secrets.load(Ordering::Relaxed), 1);
+/// ```
+pub trait PoolHooks<T: ?Sized>: Send + Sync {
+    /// Called after `value` of `len` bytes is inserted
+    #[inline]
+    fn on_insert(&self, value: &T, len: usize) {
+        let _ = (value, len);
+    }
+
+    /// Called when `value` of `len` bytes is removed, by garbage collection or by releasing it
+    #[inline]
+    fn on_remove(&self, value: &T, len: usize) {
+        let _ = (value, len);
+    }
+}
+
+impl<T: Eq + Hash + ?Sized + Pointee, S: BuildHasher + Clone> Pool<T, S> {
+    /// Set the callbacks run when entries are added or removed, replacing the previous ones
+    pub fn set_hooks(&self, hooks: impl PoolHooks<T> + 'static) {
+        *self.hooks.write() = Some(Box::new(hooks));
+    }
+
+    /// Remove the callbacks set with [`set_hooks`](Pool::set_hooks)
+    pub fn clear_hooks(&self) {
+        *self.hooks.write() = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct Log(Mutex<Vec<(String, bool)>>);
+
+    impl PoolHooks<str> for Arc<Log> {
+        fn on_insert(&self, value: &str, len: usize) {
+            assert_eq!(value.len(), len);
+            self.0.lock().unwrap().push((value.to_string(), true));
+        }
+
+        fn on_remove(&self, value: &str, _: usize) {
+            self.0.lock().unwrap().push((value.to_string(), false));
+        }
+    }
+
+    struct Echo;
+
+    thread_local! {
+        static ECHO_POOL: Pool<str> = Pool::new();
+    }
+
+    impl PoolHooks<str> for Echo {
+        fn on_insert(&self, value: &str, _: usize) {
+            if !value.ends_with('!') {
+                ECHO_POOL.with(|p| p.intern(format!("{}!", value)));
+            }
+        }
+
+        fn on_remove(&self, value: &str, _: usize) {
+            ECHO_POOL.with(|p| assert!(!p.contains(value)));
+        }
+    }
+
+    #[test]
+    fn test_hooks_use_pool() {
+        ECHO_POOL.with(|pool| {
+            pool.set_hooks(Echo);
+            let a = pool.intern("a");
+            pool.intern_many(&["b", "c"]);
+            assert!(pool.contains("a!") && pool.contains("b!") && pool.contains("c!"));
+            pool.collect_garbage();
+            assert!(pool.release(a));
+            assert!(pool.is_empty());
+        });
+    }
+
+    #[test]
+    fn test_hooks() {
+        let log = Arc::new(Log::default());
+        let pool = Pool::<str>::new();
+        pool.set_hooks(log.clone());
+        let a = pool.intern("a");
+        pool.intern("a");
+        pool.intern("b");
+        pool.collect_garbage();
+        assert!(pool.release(a));
+        pool.clear_hooks();
+        pool.intern("c");
+        let log = log.0.lock().unwrap();
+        assert_eq!(
+            *log,
+            [
+                ("a".to_string(), true),
+                ("b".to_string(), true),
+                ("b".to_string(), false),
+                ("a".to_string(), false),
+            ]
+        );
+    }
+}
@@ -0,0 +1,30 @@
+//! Normalizing values before they are interned
+
+/// Map a value to the form it is interned as, so values with the same normal form share one entry  
+///
+/// # Example
+/// ```
+/// # use pstr::pool::Pool;
+/// let pool = Pool::<str>::with_normalizer(|s: &str| {
+///     if s.bytes().any(|b| b.is_ascii_uppercase()) {
+///         Some(s.to_ascii_lowercase().into())
+///     } else {
+///         None
+///     }
+/// });
+/// let a = pool.intern("Content-Type");
+/// let b = pool.intern("content-type");
+/// assert_eq!(a, b);
+/// assert_eq!(a.get(), "content-type");
+/// ```
+pub trait Normalizer<T: ?Sized>: Send + Sync {
+    /// Return the normal form of `value`, or `None` if `value` is already normal
+    fn normalize(&self, value: &T) -> Option<Box<T>>;
+}
+
+impl<T: ?Sized, F: Fn(&T) -> Option<Box<T>> + Send + Sync> Normalizer<T> for F {
+    #[inline]
+    fn normalize(&self, value: &T) -> Option<Box<T>> {
+        self(value)
+    }
+}
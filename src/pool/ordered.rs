@@ -0,0 +1,241 @@
+//! The Ordered Intern Pool
+
+use std::{
+    collections::{hash_map::RandomState, BTreeSet},
+    fmt,
+    hash::{BuildHasher, Hash},
+    mem,
+    ops::{Bound, RangeBounds},
+    time::Instant,
+};
+
+use super::{EvictAll, EvictionPolicy, GcReport, Intern, Key, Pointee, PoolValue};
+use crate::sync::RwLock;
+
+/// The Ordered Intern Pool  
+///
+/// Keeps entries in sorted order, enabling range queries and ordered iteration at the cost of slower inserts
+///
+/// # Example
+/// ```
+/// # use pstr::pool::OrderedPool;
+/// let pool = OrderedPool::<str>::new();
+/// for s in ["b", "c", "a", "d"].iter() {
+///     pool.intern(*s);
+/// }
+/// let r: Vec<_> = pool.range("b".."d").iter().map(|s| s.get().to_string()).collect();
+/// assert_eq!(r, ["b", "c"]);
+/// ```
+pub struct OrderedPool<T: Ord + Hash + ?Sized + Pointee> {
+    /// Entries, each carrying its precomputed hash
+    pool: RwLock<BTreeSet<Key<T>>>,
+    hasher: RandomState,
+    policy: RwLock<Option<Box<dyn EvictionPolicy<T>>>>,
+}
+
+impl<T: Ord + Hash + ?Sized + Pointee> OrderedPool<T> {
+    /// New a empty ordered intern pool
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            pool: RwLock::new(BTreeSet::new()),
+            hasher: RandomState::new(),
+            policy: RwLock::new(None),
+        }
+    }
+}
+
+impl<T: Ord + Hash + ?Sized + Pointee + fmt::Debug> fmt::Debug for OrderedPool<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OrderedPool")
+            .field("pool", &self.pool)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Ord + Hash + ?Sized + Pointee> Default for OrderedPool<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Hash + ?Sized + Pointee> OrderedPool<T> {
+    /// Make a intern, see [`Pool::intern`](super::Pool::intern)
+    pub fn intern<A: AsRef<T>>(&self, a: A) -> Intern<T>
+    where
+        T: PoolValue,
+    {
+        if let Some(v) = self.pool.read().get(a.as_ref()) {
+            return v.0.clone();
+        }
+        let mut pool = self.pool.write();
+        match pool.get(a.as_ref()) {
+            Some(v) => v.0.clone(),
+            None => {
+                let hash = self.hasher.hash_one(a.as_ref());
+                let intern = Intern::new(a.as_ref(), hash);
+                pool.insert(Key(intern.clone()));
+                intern
+            }
+        }
+    }
+
+    /// Get the intern of `key` if it is in the pool, without inserting it, see [`Pool::get`](super::Pool::get)
+    #[inline]
+    pub fn get(&self, key: &T) -> Option<Intern<T>> {
+        self.pool.read().get(key).map(|v| v.0.clone())
+    }
+
+    /// Check if `key` is in the pool
+    #[inline]
+    pub fn contains(&self, key: &T) -> bool {
+        self.pool.read().contains(key)
+    }
+
+    /// Get all entries within `range`, in order
+    pub fn range<'a, R: RangeBounds<&'a T>>(&self, range: R) -> Vec<Intern<T>>
+    where
+        T: 'a,
+    {
+        let bounds: (Bound<&T>, Bound<&T>) = (
+            range.start_bound().map(|v| *v),
+            range.end_bound().map(|v| *v),
+        );
+        self.pool
+            .read()
+            .range::<T, _>(bounds)
+            .map(|v| v.0.clone())
+            .collect()
+    }
+
+    /// Get all entries, in order
+    pub fn to_vec(&self) -> Vec<Intern<T>> {
+        self.pool.read().iter().map(|v| v.0.clone()).collect()
+    }
+
+    /// Get the number of entries in the pool
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.pool.read().len()
+    }
+
+    /// Check if the pool is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.pool.read().is_empty()
+    }
+
+    /// Delete all interning string with reference count == 1 in the pool, see [`Pool::collect_garbage`](super::Pool::collect_garbage)  
+    /// Entries kept by the eviction policy set with [`set_eviction_policy`](OrderedPool::set_eviction_policy) are skipped
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::pool::OrderedPool;
+    /// let pool = OrderedPool::<str>::new();
+    /// let _a = pool.intern("kept");
+    /// pool.intern("garbage");
+    /// let report = pool.collect_garbage();
+    /// assert_eq!(report.removed, 1);
+    /// assert_eq!(report.remaining, 1);
+    /// ```
+    pub fn collect_garbage(&self) -> GcReport {
+        let policy = self.policy.read();
+        match policy.as_deref() {
+            Some(policy) => self.collect_garbage_with(policy),
+            None => self.collect_garbage_with(&EvictAll),
+        }
+    }
+
+    /// Delete all interning string with reference count == 1 in the pool which `policy` decides to evict
+    pub fn collect_garbage_with(&self, policy: &(impl EvictionPolicy<T> + ?Sized)) -> GcReport {
+        let start = Instant::now();
+        let mut report = GcReport::default();
+        let mut pool = self.pool.write();
+        pool.retain(|v| {
+            let keep = v.0.strong_count() > 1 || !policy.should_evict(v.0.get());
+            if !keep {
+                report.removed += 1;
+                report.bytes_freed += mem::size_of_val::<T>(v.0.get());
+            }
+            keep
+        });
+        report.remaining = pool.len();
+        drop(pool);
+        report.duration = start.elapsed();
+        report
+    }
+
+    /// Set the eviction policy used by [`collect_garbage`](OrderedPool::collect_garbage)
+    pub fn set_eviction_policy(&self, policy: impl EvictionPolicy<T> + 'static) {
+        *self.policy.write() = Some(Box::new(policy));
+    }
+
+    /// Restore the default policy which evicts every unreferenced entry
+    pub fn clear_eviction_policy(&self) {
+        *self.policy.write() = None;
+    }
+}
+
+impl OrderedPool<str> {
+    /// Get all entries starting with `prefix`, in order
+    pub fn prefix(&self, prefix: &str) -> Vec<Intern<str>> {
+        self.pool
+            .read()
+            .range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+            .take_while(|v| v.0.starts_with(prefix))
+            .map(|v| v.0.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range() {
+        let pool = OrderedPool::<str>::new();
+        let a = pool.intern("b");
+        let b = pool.intern("b");
+        assert_eq!(a, b);
+        pool.intern("a");
+        pool.intern("ab");
+        pool.intern("c");
+        let r: Vec<_> = pool
+            .range("ab"..="b")
+            .iter()
+            .map(|v| v.get().to_string())
+            .collect();
+        assert_eq!(r, ["ab", "b"]);
+        let r: Vec<_> = pool
+            .prefix("a")
+            .iter()
+            .map(|v| v.get().to_string())
+            .collect();
+        assert_eq!(r, ["a", "ab"]);
+        assert_eq!(pool.len(), 4);
+        assert_eq!(pool.collect_garbage().removed, 3);
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[test]
+    fn test_get_and_policy() {
+        let pool = OrderedPool::<str>::new();
+        pool.intern("keep");
+        pool.intern("drop");
+        assert_eq!(pool.get("keep").unwrap().get(), "keep");
+        assert!(pool.get("other").is_none());
+        assert!(pool.contains("drop"));
+        pool.set_eviction_policy(|s: &str| s != "keep");
+        let report = pool.collect_garbage();
+        assert_eq!(
+            (report.removed, report.bytes_freed, report.remaining),
+            (1, 4, 1)
+        );
+        assert!(pool.contains("keep"));
+        pool.clear_eviction_policy();
+        assert_eq!(pool.collect_garbage().removed, 1);
+        assert!(pool.is_empty());
+    }
+}
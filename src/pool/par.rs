@@ -0,0 +1,60 @@
+//! Parallel garbage collection
+
+use std::{
+    hash::{BuildHasher, Hash},
+    sync::atomic::Ordering,
+    time::Instant,
+};
+
+use rayon::prelude::*;
+
+use super::{EvictAll, EvictionPolicy, GcReport, Pointee, Pool};
+
+impl<T, S> Pool<T, S>
+where
+    T: Eq + Hash + Send + Sync + ?Sized + Pointee,
+    S: BuildHasher + Clone + Send + Sync,
+{
+    /// Like [`collect_garbage`](Pool::collect_garbage), but collects all shards in parallel on the rayon thread pool
+    ///
+    /// Each shard is still locked only while it is collected, interning waits for its own shard,
+    /// but several shards are locked at once, so more interning threads may wait at the same time
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::pool::Pool;
+    /// let pool = Pool::<str>::new();
+    /// for i in 0..100 {
+    ///     pool.intern(i.to_string());
+    /// }
+    /// assert_eq!(pool.par_collect_garbage().removed, 100);
+    /// ```
+    pub fn par_collect_garbage(&self) -> GcReport {
+        let policy = self.policy.read();
+        let policy: &dyn EvictionPolicy<T> = match policy.as_deref() {
+            Some(policy) => policy,
+            None => &EvictAll,
+        };
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("par_collect_garbage").entered();
+        let start = Instant::now();
+        let mut report = (0..self.pool.shard_count())
+            .into_par_iter()
+            .map(|shard| {
+                let mut report = GcReport::default();
+                self.collect_shard(shard, policy, &mut report);
+                report
+            })
+            .reduce(GcReport::default, |a, b| GcReport {
+                removed: a.removed + b.removed,
+                bytes_freed: a.bytes_freed + b.bytes_freed,
+                ..a
+            });
+        self.counters.gc_run();
+        report.remaining = self.entries.load(Ordering::Relaxed);
+        report.duration = start.elapsed();
+        #[cfg(feature = "tracing")]
+        report.trace();
+        report
+    }
+}
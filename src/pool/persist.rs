@@ -0,0 +1,117 @@
+//! Pool snapshots
+//!
+//! The format is a sequence of entries, each one a little-endian `u64` byte length followed by the bytes
+
+use std::{
+    hash::BuildHasher,
+    io::{self, Read, Write},
+};
+
+use super::Pool;
+
+fn dump_entries<'a>(
+    mut writer: impl Write,
+    entries: impl Iterator<Item = &'a [u8]>,
+) -> io::Result<()> {
+    for bytes in entries {
+        writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+        writer.write_all(bytes)?;
+    }
+    writer.flush()
+}
+
+fn load_entries(
+    mut reader: impl Read,
+    mut f: impl FnMut(Vec<u8>) -> io::Result<()>,
+) -> io::Result<usize> {
+    let mut count = 0;
+    loop {
+        let mut len = [0; 8];
+        match reader.read(&mut len[..1]) {
+            Ok(0) => return Ok(count),
+            Ok(_) => reader.read_exact(&mut len[1..])?,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+        let len = u64::from_le_bytes(len);
+        let mut bytes = Vec::new();
+        reader.by_ref().take(len).read_to_end(&mut bytes)?;
+        if bytes.len() as u64 != len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        f(bytes)?;
+        count += 1;
+    }
+}
+
+impl<S: BuildHasher + Clone> Pool<str, S> {
+    /// Write every entry of the pool to `writer`
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::pool::Pool;
+    /// let pool = Pool::<str>::new();
+    /// pool.intern("hello");
+    /// let mut buf = Vec::new();
+    /// pool.dump(&mut buf).unwrap();
+    ///
+    /// let warm = Pool::<str>::new();
+    /// assert_eq!(warm.load(&buf[..]).unwrap(), 1);
+    /// ```
+    pub fn dump(&self, writer: impl Write) -> io::Result<()> {
+        let entries = self.to_vec();
+        dump_entries(writer, entries.iter().map(|v| v.as_bytes()))
+    }
+
+    /// Intern every entry written by [`Pool::dump`], returns the number of entries read
+    ///
+    /// Loaded entries are not referenced by anything, so they are removed by the next garbage collection unless used before
+    pub fn load(&self, reader: impl Read) -> io::Result<usize> {
+        load_entries(reader, |bytes| {
+            let s = String::from_utf8(bytes)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            self.intern(s);
+            Ok(())
+        })
+    }
+}
+
+impl<S: BuildHasher + Clone> Pool<[u8], S> {
+    /// Write every entry of the pool to `writer`
+    pub fn dump(&self, writer: impl Write) -> io::Result<()> {
+        let entries = self.to_vec();
+        dump_entries(writer, entries.iter().map(|v| &v[..]))
+    }
+
+    /// Intern every entry written by [`Pool::dump`], returns the number of entries read
+    ///
+    /// Loaded entries are not referenced by anything, so they are removed by the next garbage collection unless used before
+    pub fn load(&self, reader: impl Read) -> io::Result<usize> {
+        load_entries(reader, |bytes| {
+            self.intern(bytes);
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dump_load() {
+        let pool = Pool::<str>::new();
+        for s in ["", "a", "hello", "世界"].iter() {
+            pool.intern(*s);
+        }
+        let mut buf = Vec::new();
+        pool.dump(&mut buf).unwrap();
+
+        let loaded = Pool::<str>::new();
+        assert_eq!(loaded.load(&buf[..]).unwrap(), 4);
+        assert!(loaded.contains("世界"));
+        assert!(loaded.contains(""));
+
+        assert!(loaded.load(&buf[..buf.len() - 1]).is_err());
+    }
+}
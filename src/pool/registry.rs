@@ -0,0 +1,150 @@
+//! Named pools shared across the program
+
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt,
+    hash::Hash,
+};
+
+#[cfg(not(feature = "single-thread"))]
+use once_cell::sync::Lazy;
+
+use super::{config, GlobalHasher, Pointee, Pool};
+use crate::sync::RwLock;
+
+/// A pool of some value type, leaked to live as long as the program
+#[cfg(not(feature = "single-thread"))]
+type AnyPool = &'static (dyn Any + Send + Sync);
+/// A pool of some value type, leaked to live as long as the program
+#[cfg(feature = "single-thread")]
+type AnyPool = &'static dyn Any;
+
+#[cfg(not(feature = "single-thread"))]
+static REGISTRY: Lazy<Registry> = Lazy::new(Registry::new);
+
+#[cfg(feature = "single-thread")]
+thread_local! {
+    static REGISTRY: &'static Registry = Box::leak(Box::new(Registry::new()));
+}
+
+/// Get the registry of named pools
+///
+/// # Example
+/// ```
+/// # use pstr::pool::registry;
+/// let symbols = registry().get_or_create::<str>("ast-symbols");
+/// let a = symbols.intern("fn");
+/// assert_eq!(registry().get_or_create::<str>("ast-symbols").get("fn"), Some(a));
+/// assert!(registry().get::<str>("other").is_none());
+/// ```
+#[inline]
+pub fn registry() -> &'static Registry {
+    #[cfg(not(feature = "single-thread"))]
+    return &REGISTRY;
+    #[cfg(feature = "single-thread")]
+    return REGISTRY.with(|registry| *registry);
+}
+
+/// Pools looked up by name and value type, see [`registry`]
+///
+/// Each subsystem can keep its interned values in its own namespace,
+/// the pools live as long as the program like the global pools and are configured the same way.
+/// With the `single-thread` feature each thread has its own registry like the global pools
+pub struct Registry {
+    /// Pools of each value type by name
+    pools: RwLock<HashMap<TypeId, HashMap<Box<str>, AnyPool>>>,
+    /// The unnamed global pool of each value type
+    globals: RwLock<HashMap<TypeId, AnyPool>>,
+}
+
+impl Registry {
+    fn new() -> Self {
+        Self {
+            pools: RwLock::new(HashMap::new()),
+            globals: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Get the pool of `T` named `name`, creating it if it doesn't exist
+    pub fn get_or_create<T>(&self, name: &str) -> &'static Pool<T, GlobalHasher>
+    where
+        T: Eq + Hash + Send + Sync + ?Sized + Pointee + 'static,
+    {
+        if let Some(pool) = self.get(name) {
+            return pool;
+        }
+        let pool = *self
+            .pools
+            .write()
+            .entry(TypeId::of::<T>())
+            .or_default()
+            .entry(name.into())
+            .or_insert_with(|| Box::leak(Box::new(config::global_pool::<T>(Some(name)))));
+        pool.downcast_ref().unwrap()
+    }
+
+    /// Get the pool of `T` named `name` if it was created
+    pub fn get<T>(&self, name: &str) -> Option<&'static Pool<T, GlobalHasher>>
+    where
+        T: Eq + Hash + Send + Sync + ?Sized + Pointee + 'static,
+    {
+        let pool = *self.pools.read().get(&TypeId::of::<T>())?.get(name)?;
+        pool.downcast_ref()
+    }
+
+    /// Get the global pool of `T`, creating it if it doesn't exist, see [`IRef`](crate::IRef)
+    pub(crate) fn global<T>(&self) -> &'static Pool<T, GlobalHasher>
+    where
+        T: Eq + Hash + Send + Sync + ?Sized + Pointee + 'static,
+    {
+        let id = TypeId::of::<T>();
+        if let Some(pool) = self.globals.read().get(&id) {
+            return pool.downcast_ref().unwrap();
+        }
+        let pool = *self.globals.write().entry(id).or_insert_with(|| {
+            Box::leak(Box::new(config::global_pool::<T>(Some(
+                std::any::type_name::<T>(),
+            ))))
+        });
+        pool.downcast_ref().unwrap()
+    }
+
+    /// Get the names of all pools of `T`
+    pub fn names<T: ?Sized + 'static>(&self) -> Vec<String> {
+        match self.pools.read().get(&TypeId::of::<T>()) {
+            Some(pools) => pools.keys().map(|name| name.to_string()).collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+impl fmt::Debug for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Registry")
+            .field(
+                "pools",
+                &self.pools.read().values().map(HashMap::len).sum::<usize>(),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        let a = registry().get_or_create::<str>("test_registry");
+        let b = registry().get_or_create::<str>("test_registry");
+        assert!(std::ptr::eq(a, b));
+        let bytes = registry().get_or_create::<[u8]>("test_registry");
+        a.intern("x");
+        assert!(a.contains("x"));
+        assert!(bytes.is_empty());
+        assert!(registry()
+            .names::<str>()
+            .contains(&"test_registry".to_string()));
+    }
+}
@@ -0,0 +1,72 @@
+//! Similarity search over interned strings
+
+use std::hash::BuildHasher;
+
+use super::{Intern, Pool};
+
+impl<S: BuildHasher + Clone> Pool<str, S> {
+    /// Find entries within edit distance `max_distance` of `query`  
+    ///
+    /// Results are sorted by distance, then by string, useful for "did you mean" suggestions
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::pool::Pool;
+    /// let pool = Pool::<str>::new();
+    /// let _a = pool.intern("foo_bar");
+    /// let _b = pool.intern("baz");
+    /// let r = pool.nearest("foo_baz", 2);
+    /// assert_eq!(r.len(), 1);
+    /// assert_eq!(r[0].0.get(), "foo_bar");
+    /// assert_eq!(r[0].1, 1);
+    /// ```
+    pub fn nearest(&self, query: &str, max_distance: usize) -> Vec<(Intern<str>, usize)> {
+        let query: Vec<char> = query.chars().collect();
+        let mut r = Vec::new();
+        self.pool.for_each(|k| {
+            if let Some(d) = bounded_distance(&query, k.0.get(), max_distance) {
+                r.push((k.0.clone(), d));
+            }
+        });
+        r.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.get().cmp(b.0.get())));
+        r
+    }
+}
+
+/// Levenshtein distance between `a` and `b`, or `None` if it exceeds `max`
+fn bounded_distance(a: &[char], b: &str, max: usize) -> Option<usize> {
+    let b_len = b.chars().count();
+    if a.len().max(b_len) - a.len().min(b_len) > max {
+        return None;
+    }
+    let mut prev: Vec<usize> = (0..=a.len()).collect();
+    let mut cur = vec![0; a.len() + 1];
+    for (i, cb) in b.chars().enumerate() {
+        cur[0] = i + 1;
+        let mut row_min = cur[0];
+        for (j, ca) in a.iter().enumerate() {
+            let cost = if *ca == cb { 0 } else { 1 };
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+            row_min = row_min.min(cur[j + 1]);
+        }
+        if row_min > max {
+            return None;
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    Some(prev[a.len()]).filter(|d| *d <= max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance() {
+        let a: Vec<char> = "kitten".chars().collect();
+        assert_eq!(bounded_distance(&a, "sitting", 3), Some(3));
+        assert_eq!(bounded_distance(&a, "sitting", 2), None);
+        assert_eq!(bounded_distance(&a, "kitten", 0), Some(0));
+        assert_eq!(bounded_distance(&[], "abc", 3), Some(3));
+    }
+}
@@ -0,0 +1,125 @@
+//! Pool statistics
+
+use std::{
+    fmt,
+    hash::{BuildHasher, Hash},
+    io, mem,
+};
+
+use super::{Intern, Key, Pointee, Pool};
+
+/// A snapshot of pool statistics, see [`Pool::stats`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Number of entries in the pool
+    pub entries: usize,
+    /// Sum of the byte sizes of all entries
+    pub total_bytes: usize,
+    /// Number of entries referenced by exactly one handle outside the pool
+    pub unique_refs: usize,
+    /// Number of entries not referenced outside the pool, which garbage collection would remove
+    pub garbage_candidates: usize,
+}
+
+impl<T: Eq + Hash + ?Sized + Pointee, S: BuildHasher + Clone> Pool<T, S> {
+    /// Walk the pool and collect statistics  
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::pool::Pool;
+    /// let pool = Pool::<str>::new();
+    /// let _a = pool.intern("hello");
+    /// pool.intern("world!");
+    /// let stats = pool.stats();
+    /// assert_eq!(stats.entries, 2);
+    /// assert_eq!(stats.total_bytes, 11);
+    /// assert_eq!(stats.unique_refs, 1);
+    /// assert_eq!(stats.garbage_candidates, 1);
+    /// ```
+    pub fn stats(&self) -> PoolStats {
+        let mut stats = PoolStats::default();
+        self.pool.for_each(|k| {
+            let intern = &k.0;
+            stats.entries += 1;
+            stats.total_bytes += mem::size_of_val::<T>(intern.get());
+            match intern.strong_count() {
+                1 => stats.garbage_candidates += 1,
+                2 => stats.unique_refs += 1,
+                _ => {}
+            }
+        });
+        stats
+    }
+
+    /// Estimate the heap memory used by the pool in bytes  
+    ///
+    /// Counts every entry's allocation, which holds its bytes and header, and the slots of the underlying table including unused capacity
+    pub fn heap_bytes(&self) -> usize {
+        let table = self.pool.capacity() * (mem::size_of::<Key<T>>() + 1);
+        let mut entries = 0;
+        self.pool.for_each(|k| entries += k.0.heap_size());
+        table + entries
+    }
+
+    /// Write every entry to `w`, one per line as the number of handles outside the pool and the `Debug` form of the value,
+    /// sorted by value  
+    /// Values longer than `max_len` chars are cut and end with `…`
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::pool::Pool;
+    /// let pool = Pool::<str>::new();
+    /// let _a = pool.intern("b");
+    /// pool.intern("a".repeat(100));
+    /// let mut out = Vec::new();
+    /// pool.debug_dump(&mut out, Some(4)).unwrap();
+    /// assert_eq!(String::from_utf8(out).unwrap(), "0\t\"aaa…\n1\t\"b\"\n");
+    /// ```
+    pub fn debug_dump(&self, mut w: impl io::Write, max_len: Option<usize>) -> io::Result<()>
+    where
+        T: fmt::Debug + Ord,
+    {
+        let mut entries: Vec<(Intern<T>, usize)> = Vec::with_capacity(self.len());
+        self.pool.for_each(|k| {
+            let refs = k.0.strong_count() - 1;
+            entries.push((k.0.clone(), refs));
+        });
+        entries.sort_unstable_by(|a, b| a.0.get().cmp(b.0.get()));
+        for (entry, refs) in entries {
+            let mut value = format!("{:?}", entry.get());
+            if let Some(max_len) = max_len {
+                if let Some((i, _)) = value.char_indices().nth(max_len) {
+                    value.truncate(i);
+                    value.push('…');
+                }
+            }
+            writeln!(w, "{}\t{}", refs, value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heap_bytes() {
+        let pool = Pool::<str>::new();
+        let empty = pool.heap_bytes();
+        pool.intern("a".repeat(1000));
+        // At least the reference count and the hash in front of the bytes
+        let header = mem::size_of::<usize>() + mem::size_of::<u64>();
+        assert!(pool.heap_bytes() >= empty + 1000 + header);
+    }
+
+    #[test]
+    fn test_debug_dump_order() {
+        let pool = Pool::<str>::new();
+        pool.intern("B");
+        pool.intern("\n");
+        let mut out = Vec::new();
+        pool.debug_dump(&mut out, None).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "0\t\"\\n\"\n0\t\"B\"\n");
+    }
+}
@@ -0,0 +1,264 @@
+//! The table holding the entries of a pool
+//!
+//! A sharded [`DashMap`](dashmap::DashMap) by default, with the `single-thread` feature a single map in a `RefCell`,
+//! which needs no atomics and leaves dashmap out of the binary.
+//! Built with `--cfg loom` the shards are behind loom locks, dashmap's own locks are invisible to loom
+
+use std::{
+    fmt,
+    hash::{BuildHasher, Hash},
+};
+
+use dashmap::SharedValue;
+
+use super::{Key, Pointee};
+
+#[cfg(all(loom, not(feature = "single-thread")))]
+pub(super) use self::checked::Table;
+#[cfg(not(any(feature = "single-thread", loom)))]
+pub(super) use self::sharded::Table;
+#[cfg(feature = "single-thread")]
+pub(super) use self::single::Table;
+
+/// One shard of the table
+pub(super) type Map<T, S> = hashbrown::HashMap<Key<T>, SharedValue<()>, S>;
+
+/// The value of every entry, the table is used as a set
+#[inline]
+pub(super) fn value() -> SharedValue<()> {
+    SharedValue::new(())
+}
+
+#[cfg(not(any(feature = "single-thread", loom)))]
+mod sharded {
+    use std::hash::{BuildHasher, Hash};
+
+    use dashmap::{DashMap, RwLockReadGuard, RwLockWriteGuard};
+
+    use super::{Key, Map, Pointee};
+
+    pub(in crate::pool) type ReadGuard<'a, T, S> = RwLockReadGuard<'a, Map<T, S>>;
+    pub(in crate::pool) type WriteGuard<'a, T, S> = RwLockWriteGuard<'a, Map<T, S>>;
+
+    pub(in crate::pool) struct Table<T: ?Sized + Pointee, S>(DashMap<Key<T>, (), S>);
+
+    impl<T: Eq + Hash + ?Sized + Pointee, S: BuildHasher + Clone> Table<T, S> {
+        pub fn new(capacity: usize, shard_amount: Option<usize>, hasher: S) -> Self {
+            Self(match shard_amount {
+                Some(shard_amount) => DashMap::with_capacity_and_hasher_and_shard_amount(
+                    capacity,
+                    hasher,
+                    shard_amount,
+                ),
+                None => DashMap::with_capacity_and_hasher(capacity, hasher),
+            })
+        }
+
+        #[inline]
+        pub fn shard_count(&self) -> usize {
+            self.0.shards().len()
+        }
+
+        /// Get the shard of a entry whose hash is `hash`
+        #[inline]
+        pub fn shard_of(&self, hash: u64) -> usize {
+            self.0.determine_shard(hash as usize)
+        }
+
+        #[inline]
+        pub fn read(&self, shard: usize) -> ReadGuard<'_, T, S> {
+            self.0.shards()[shard].read()
+        }
+
+        #[inline]
+        pub fn try_read(&self, shard: usize) -> Option<ReadGuard<'_, T, S>> {
+            self.0.shards()[shard].try_read()
+        }
+
+        #[inline]
+        pub fn write(&self, shard: usize) -> WriteGuard<'_, T, S> {
+            self.0.shards()[shard].write()
+        }
+
+        #[inline]
+        pub fn try_write(&self, shard: usize) -> Option<WriteGuard<'_, T, S>> {
+            self.0.shards()[shard].try_write()
+        }
+    }
+}
+
+#[cfg(feature = "single-thread")]
+mod single {
+    use std::{
+        cell::{Ref, RefCell, RefMut},
+        hash::{BuildHasher, Hash},
+    };
+
+    use super::{Map, Pointee};
+
+    pub(in crate::pool) type ReadGuard<'a, T, S> = Ref<'a, Map<T, S>>;
+    pub(in crate::pool) type WriteGuard<'a, T, S> = RefMut<'a, Map<T, S>>;
+
+    pub(in crate::pool) struct Table<T: ?Sized + Pointee, S>(RefCell<Map<T, S>>);
+
+    impl<T: Eq + Hash + ?Sized + Pointee, S: BuildHasher + Clone> Table<T, S> {
+        pub fn new(capacity: usize, shard_amount: Option<usize>, hasher: S) -> Self {
+            if let Some(shard_amount) = shard_amount {
+                assert!(shard_amount > 1 && shard_amount.is_power_of_two());
+            }
+            Self(RefCell::new(Map::with_capacity_and_hasher(
+                capacity, hasher,
+            )))
+        }
+
+        #[inline]
+        pub fn shard_count(&self) -> usize {
+            1
+        }
+
+        /// Get the shard of a entry whose hash is `hash`
+        #[inline]
+        pub fn shard_of(&self, _hash: u64) -> usize {
+            0
+        }
+
+        #[inline]
+        pub fn read(&self, _shard: usize) -> ReadGuard<'_, T, S> {
+            self.0.borrow()
+        }
+
+        #[inline]
+        pub fn try_read(&self, _shard: usize) -> Option<ReadGuard<'_, T, S>> {
+            self.0.try_borrow().ok()
+        }
+
+        #[inline]
+        pub fn write(&self, _shard: usize) -> WriteGuard<'_, T, S> {
+            self.0.borrow_mut()
+        }
+
+        #[inline]
+        pub fn try_write(&self, _shard: usize) -> Option<WriteGuard<'_, T, S>> {
+            self.0.try_borrow_mut().ok()
+        }
+    }
+}
+
+#[cfg(all(loom, not(feature = "single-thread")))]
+mod checked {
+    use std::hash::{BuildHasher, Hash};
+
+    use loom::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+    use super::{Map, Pointee};
+
+    pub(in crate::pool) type ReadGuard<'a, T, S> = RwLockReadGuard<'a, Map<T, S>>;
+    pub(in crate::pool) type WriteGuard<'a, T, S> = RwLockWriteGuard<'a, Map<T, S>>;
+
+    /// Shards when no amount is given, few to keep the models small
+    const DEFAULT_SHARD_AMOUNT: usize = 2;
+
+    pub(in crate::pool) struct Table<T: ?Sized + Pointee, S>(Box<[RwLock<Map<T, S>>]>);
+
+    impl<T: Eq + Hash + ?Sized + Pointee, S: BuildHasher + Clone> Table<T, S> {
+        pub fn new(capacity: usize, shard_amount: Option<usize>, hasher: S) -> Self {
+            let shard_amount = shard_amount.unwrap_or(DEFAULT_SHARD_AMOUNT);
+            assert!(shard_amount > 1 && shard_amount.is_power_of_two());
+            let capacity = capacity.div_ceil(shard_amount);
+            Self(
+                (0..shard_amount)
+                    .map(|_| RwLock::new(Map::with_capacity_and_hasher(capacity, hasher.clone())))
+                    .collect(),
+            )
+        }
+
+        #[inline]
+        pub fn shard_count(&self) -> usize {
+            self.0.len()
+        }
+
+        /// Get the shard of a entry whose hash is `hash`
+        #[inline]
+        pub fn shard_of(&self, hash: u64) -> usize {
+            hash as usize & (self.0.len() - 1)
+        }
+
+        #[inline]
+        pub fn read(&self, shard: usize) -> ReadGuard<'_, T, S> {
+            self.0[shard].read().unwrap()
+        }
+
+        #[inline]
+        pub fn try_read(&self, shard: usize) -> Option<ReadGuard<'_, T, S>> {
+            self.0[shard].try_read().ok()
+        }
+
+        #[inline]
+        pub fn write(&self, shard: usize) -> WriteGuard<'_, T, S> {
+            self.0[shard].write().unwrap()
+        }
+
+        #[inline]
+        pub fn try_write(&self, shard: usize) -> Option<WriteGuard<'_, T, S>> {
+            self.0[shard].try_write().ok()
+        }
+    }
+}
+
+impl<T: Eq + Hash + ?Sized + Pointee, S: BuildHasher + Clone> Table<T, S> {
+    /// Get the number of entries in all shards
+    pub fn len(&self) -> usize {
+        (0..self.shard_count()).map(|i| self.read(i).len()).sum()
+    }
+
+    /// Get the number of entries all shards can hold without reallocating
+    pub fn capacity(&self) -> usize {
+        (0..self.shard_count())
+            .map(|i| self.read(i).capacity())
+            .sum()
+    }
+
+    /// Call `f` on every key, one shard locked at a time
+    pub fn for_each(&self, mut f: impl FnMut(&Key<T>)) {
+        for i in 0..self.shard_count() {
+            self.read(i).keys().for_each(&mut f);
+        }
+    }
+
+    /// Find the entry of `value` whose hash is `hash`
+    pub fn find<R>(&self, value: &T, hash: u64, f: impl FnOnce(&Key<T>) -> R) -> Option<R> {
+        let shard = self.read(self.shard_of(hash));
+        let found = shard.raw_entry().from_hash(hash, |k| {
+            k.0.precomputed_hash() == hash && k.0.get() == value
+        });
+        found.map(|(k, _)| f(k))
+    }
+
+    /// Remove the entry of `value` whose hash is `hash` if `f` returns `true` for it
+    pub fn remove_if(
+        &self,
+        value: &T,
+        hash: u64,
+        f: impl FnOnce(&Key<T>) -> bool,
+    ) -> Option<Key<T>> {
+        let mut shard = self.write(self.shard_of(hash));
+        match shard.raw_entry_mut().from_hash(hash, |k| {
+            k.0.precomputed_hash() == hash && k.0.get() == value
+        }) {
+            hashbrown::hash_map::RawEntryMut::Occupied(e) if f(e.key()) => Some(e.remove_entry().0),
+            _ => None,
+        }
+    }
+}
+
+impl<T: Eq + Hash + ?Sized + Pointee + fmt::Debug, S: BuildHasher + Clone> fmt::Debug
+    for Table<T, S>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut set = f.debug_set();
+        self.for_each(|k| {
+            set.entry(k);
+        });
+        set.finish()
+    }
+}
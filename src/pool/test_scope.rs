@@ -0,0 +1,204 @@
+//! Per-thread replacement of the global string pool for tests
+
+use std::{
+    cell::RefCell,
+    fmt,
+    marker::PhantomData,
+    ops::Deref,
+    rc::Rc,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use super::{config, GlobalHasher, Intern, Pool, STR_POOL};
+
+/// Number of live scopes on all threads, so threads without one skip the thread-local lookup
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// Pools of the live scopes of this thread, the innermost last
+    static SCOPED: RefCell<Vec<Rc<Pool<str, GlobalHasher>>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Replace the global string pool with a fresh pool on the current thread until the guard is dropped
+///
+/// `IStr`s made on this thread meanwhile go into the fresh pool, so tests asserting on the pool don't see each other.
+/// Scopes nest, the pool of the innermost live scope is in place.
+/// Dropping a guard removes its pool even if inner scopes are still alive, they stay in place.
+/// Dropped handles are released to the pool holding their entry, wherever they were made
+///
+/// # Equality
+/// **Handles of different pools are never equal.**
+/// Pooled `IStr`s compare by pointer, so a `IStr` made in the scope and a equal string made outside of it,
+/// or in another scope, compare `!=` while `Ord` says [`Equal`](std::cmp::Ordering::Equal) and they hash the same.
+/// Don't mix them in sets, maps or `==` assertions, compare [`as_str`](crate::IStr::as_str) instead.
+/// Strings short enough to be stored inline never touch a pool and still compare equal
+///
+/// ```
+/// # use pstr::{IStr, pool::test_scope};
+/// let outside = IStr::new("made outside of the scope");
+/// let _scope = test_scope();
+/// let inside = IStr::new("made outside of the scope");
+/// assert_ne!(inside, outside);
+/// assert_eq!(inside.cmp(&outside), std::cmp::Ordering::Equal);
+/// assert_eq!(inside.as_str(), outside.as_str());
+/// ```
+///
+/// # Example
+/// ```
+/// # use pstr::{IStr, pool::test_scope};
+/// let pool = test_scope();
+/// let s = IStr::new("only in this test");
+/// assert_eq!(pool.len(), 1);
+/// drop(s);
+/// assert_eq!(pool.collect_garbage().removed, 1);
+/// ```
+pub fn test_scope() -> TestScope {
+    let pool = Rc::new(config::global_pool(None));
+    SCOPED.with(|s| s.borrow_mut().push(pool.clone()));
+    ACTIVE.fetch_add(1, Ordering::Relaxed);
+    TestScope {
+        pool,
+        _marker: PhantomData,
+    }
+}
+
+/// Guard of a pool replacing the global string pool on the current thread, see [`test_scope`]
+///
+/// Derefs to the pool in place while it lives
+pub struct TestScope {
+    pool: Rc<Pool<str, GlobalHasher>>,
+    /// Only restores the pool of the thread it was made on
+    _marker: PhantomData<*const ()>,
+}
+
+impl Deref for TestScope {
+    type Target = Pool<str, GlobalHasher>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.pool
+    }
+}
+
+impl fmt::Debug for TestScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TestScope").field(&self.pool).finish()
+    }
+}
+
+impl Drop for TestScope {
+    fn drop(&mut self) {
+        // The stack is already gone if the thread is tearing down its thread locals
+        let old = SCOPED.try_with(|s| {
+            let mut s = s.borrow_mut();
+            let i = s.iter().rposition(|p| Rc::ptr_eq(p, &self.pool))?;
+            Some(s.remove(i))
+        });
+        ACTIVE.fetch_sub(1, Ordering::Relaxed);
+        // Dropped outside the borrow, in case the pool drops handles
+        drop(old);
+    }
+}
+
+/// Run `f` on the string pool of the current thread, the pool of the innermost test scope or [`STR_POOL`]  
+/// Handles dropped while the thread tears down its thread locals use [`STR_POOL`]
+#[inline]
+pub(crate) fn with_str_pool<R>(f: impl FnOnce(&Pool<str, GlobalHasher>) -> R) -> R {
+    if ACTIVE.load(Ordering::Relaxed) == 0 {
+        return f(&STR_POOL);
+    }
+    match SCOPED.try_with(|s| s.borrow().last().cloned()) {
+        Ok(Some(pool)) => f(&pool),
+        _ => f(&STR_POOL),
+    }
+}
+
+/// Run `f` on the string pool holding the entry of `intern`, the pool of the innermost test scope holding it or [`STR_POOL`]  
+/// Used to release dropped handles, which may outlive the scope they were made in or come from outside of it
+#[inline]
+pub(crate) fn with_owning_str_pool<R>(
+    intern: &Intern<str>,
+    f: impl FnOnce(&Pool<str, GlobalHasher>) -> R,
+) -> R {
+    if ACTIVE.load(Ordering::Relaxed) == 0 {
+        return f(&STR_POOL);
+    }
+    match SCOPED.try_with(|s| s.borrow().iter().rev().find(|p| p.owns(intern)).cloned()) {
+        Ok(Some(pool)) => f(&pool),
+        _ => f(&STR_POOL),
+    }
+}
+
+/// Check if the current thread is inside a test scope
+#[inline]
+pub(crate) fn in_test_scope() -> bool {
+    ACTIVE.load(Ordering::Relaxed) != 0
+        && SCOPED.try_with(|s| !s.borrow().is_empty()).unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IStr;
+
+    #[test]
+    fn test_test_scope() {
+        let outer = test_scope();
+        let a = IStr::new("test_scope_outer");
+        {
+            let inner = test_scope();
+            let b = IStr::new("test_scope_inner");
+            assert_eq!(inner.len(), 1);
+            assert!(!outer.contains("test_scope_inner"));
+            drop(b);
+        }
+        assert_eq!(outer.len(), 1);
+        assert!(outer.contains("test_scope_outer"));
+        assert!(!STR_POOL.contains("test_scope_outer"));
+        drop(a);
+        drop(outer);
+        assert!(!in_test_scope());
+    }
+
+    #[test]
+    fn test_out_of_order() {
+        let outer = test_scope();
+        let inner = test_scope();
+        drop(outer);
+        let a = IStr::new("test_scope_out_of_order");
+        assert!(inner.contains("test_scope_out_of_order"));
+        drop(inner);
+        assert!(!in_test_scope());
+        drop(a);
+    }
+
+    #[test]
+    fn test_drop_routes_to_owner() {
+        let outer = test_scope();
+        outer.set_release_on_drop(true);
+        let a = IStr::new("test_scope_drop_routes_to_owner");
+        let inner = test_scope();
+        inner.set_release_on_drop(true);
+        let b = IStr::new("test_scope_drop_routes_to_owner");
+        drop(a);
+        assert!(!outer.contains("test_scope_drop_routes_to_owner"));
+        assert!(inner.contains("test_scope_drop_routes_to_owner"));
+        drop(b);
+        assert!(inner.is_empty());
+    }
+
+    #[test]
+    fn test_drop_in_thread_local() {
+        thread_local! {
+            static LATE: RefCell<Option<(TestScope, IStr)>> = const { RefCell::new(None) };
+        }
+        std::thread::spawn(|| {
+            // Dropped with the thread locals, maybe after `SCOPED`
+            let scope = test_scope();
+            let s = IStr::new("test_scope_thread_local");
+            LATE.with(|l| *l.borrow_mut() = Some((scope, s)));
+        })
+        .join()
+        .unwrap();
+    }
+}
@@ -0,0 +1,90 @@
+//! `ToSql` and `FromSql` of [postgres-types](https://crates.io/crates/postgres-types), as used by `tokio-postgres` and `postgres`
+//!
+//! `IStr` and `MowStr` accept the same types as `&str`, decoding interns the text borrowed from the row
+
+use std::error::Error;
+
+use ::bytes::BytesMut;
+use ::postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+use crate::{IStr, MowStr};
+
+impl ToSql for IStr {
+    #[inline]
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        <&str as ToSql>::to_sql(&&**self, ty, out)
+    }
+
+    #[inline]
+    fn accepts(ty: &Type) -> bool {
+        <&str as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for IStr {
+    #[inline]
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        <&str as FromSql>::from_sql(ty, raw).map(IStr::new)
+    }
+
+    #[inline]
+    fn accepts(ty: &Type) -> bool {
+        <&str as FromSql>::accepts(ty)
+    }
+}
+
+impl ToSql for MowStr {
+    #[inline]
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        <&str as ToSql>::to_sql(&&**self, ty, out)
+    }
+
+    #[inline]
+    fn accepts(ty: &Type) -> bool {
+        <&str as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for MowStr {
+    /// Decode as a interned string
+    #[inline]
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        <&str as FromSql>::from_sql(ty, raw).map(MowStr::new)
+    }
+
+    #[inline]
+    fn accepts(ty: &Type) -> bool {
+        <&str as FromSql>::accepts(ty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_postgres() {
+        let s = IStr::new("postgres text column value");
+        let mut buf = BytesMut::new();
+        s.to_sql_checked(&Type::TEXT, &mut buf).unwrap();
+        assert!(s.to_sql_checked(&Type::INT4, &mut buf).is_err());
+        let back = IStr::from_sql(&Type::TEXT, &buf).unwrap();
+        assert_eq!(back.as_ptr(), s.as_ptr());
+        let m = MowStr::from_sql(&Type::VARCHAR, &buf).unwrap();
+        assert!(m.is_interned());
+        assert_eq!(m, *s);
+        assert!(!<IStr as FromSql>::accepts(&Type::BYTEA));
+    }
+}
@@ -0,0 +1,490 @@
+//! Pool reference counting
+//!
+//! A `Prc` is a thin pointer to a single allocation holding a header followed by the value,
+//! the header stores the reference count, the hash precomputed by the pool and the length of the value.
+//! The value starts at the first offset after the header aligned for it, so the pointer to it is rebuilt from
+//! the address of the header and the length, see [`Pointee`].
+//! A `'static` value can be borrowed instead, then only the header and the address of the value are allocated  
+//! The count is atomic like `Arc`, with the `single-thread` feature it is a plain `Cell` like `Rc`
+
+use std::{
+    alloc::{self, Layout},
+    ffi::{CStr, OsStr},
+    marker::PhantomData,
+    mem,
+    path::Path,
+    ptr::{self, NonNull},
+};
+
+#[cfg(not(feature = "single-thread"))]
+use crate::sync::atomic::{self, AtomicUsize, Ordering};
+#[cfg(feature = "single-thread")]
+use std::cell::Cell;
+
+/// Values which can be copied into the allocation of a pool entry
+///
+/// Implemented for every `Clone` type and for `str`, `[T: Copy]`, `OsStr`, `CStr` and `Path`
+///
+/// # Safety
+/// [`write_to`](PoolValue::write_to) must initialize `dst` with a valid copy of `self`
+pub unsafe trait PoolValue {
+    /// Write a copy of `self` to `dst`
+    ///
+    /// # Safety
+    /// `dst` must be valid for writes of `size_of_val(self)` bytes and aligned like `self`
+    unsafe fn write_to(&self, dst: *mut u8);
+}
+
+unsafe impl<T: Clone> PoolValue for T {
+    #[inline]
+    unsafe fn write_to(&self, dst: *mut u8) {
+        ptr::write(dst as *mut T, self.clone())
+    }
+}
+
+unsafe impl<T: Copy> PoolValue for [T] {
+    #[inline]
+    unsafe fn write_to(&self, dst: *mut u8) {
+        ptr::copy_nonoverlapping(self.as_ptr(), dst as *mut T, self.len())
+    }
+}
+
+/// Implement `PoolValue` for a unsized type which is plain bytes
+macro_rules! impl_bytes {
+    ($($t:ty),*) => {
+        $(
+            unsafe impl PoolValue for $t {
+                #[inline]
+                unsafe fn write_to(&self, dst: *mut u8) {
+                    ptr::copy_nonoverlapping(self as *const $t as *const u8, dst, mem::size_of_val(self))
+                }
+            }
+        )*
+    };
+}
+
+impl_bytes!(str, OsStr, CStr, Path);
+
+/// Types whose pointer a pool entry rebuilds from the address of the value and its length
+///
+/// Implemented for every sized type and for `str`, `[T]`, `OsStr`, `CStr` and `Path`
+///
+/// # Safety
+/// [`from_raw_parts`](Pointee::from_raw_parts) must return a pointer to the value at `data`
+/// whose [`metadata`](Pointee::metadata) is `metadata`, and [`ALIGN`](Pointee::ALIGN) must be the alignment of every value
+pub unsafe trait Pointee {
+    /// Alignment of the values
+    const ALIGN: usize;
+
+    /// Get the length of `value`, 0 for sized types  
+    /// A associated function instead of a method, so it doesn't clash with methods of the same name on every type
+    fn metadata(value: &Self) -> usize;
+
+    /// Make a pointer to the value at `data` whose length is `metadata`
+    fn from_raw_parts(data: *const u8, metadata: usize) -> *const Self;
+}
+
+unsafe impl<T> Pointee for T {
+    const ALIGN: usize = mem::align_of::<T>();
+
+    #[inline]
+    fn metadata(_: &Self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn from_raw_parts(data: *const u8, _: usize) -> *const Self {
+        data as *const T
+    }
+}
+
+unsafe impl<T> Pointee for [T] {
+    const ALIGN: usize = mem::align_of::<T>();
+
+    #[inline]
+    fn metadata(value: &Self) -> usize {
+        value.len()
+    }
+
+    #[inline]
+    fn from_raw_parts(data: *const u8, metadata: usize) -> *const Self {
+        ptr::slice_from_raw_parts(data as *const T, metadata)
+    }
+}
+
+/// Implement `Pointee` for a unsized type which is plain bytes, its length is its size
+macro_rules! impl_bytes_pointee {
+    ($($t:ty),*) => {
+        $(
+            unsafe impl Pointee for $t {
+                const ALIGN: usize = 1;
+
+                #[inline]
+                fn metadata(value: &Self) -> usize {
+                    mem::size_of_val(value)
+                }
+
+                #[inline]
+                fn from_raw_parts(data: *const u8, metadata: usize) -> *const Self {
+                    ptr::slice_from_raw_parts(data, metadata) as *const $t
+                }
+            }
+        )*
+    };
+}
+
+impl_bytes_pointee!(str, OsStr, CStr, Path);
+
+/// Reference count of a allocation
+#[cfg(not(feature = "single-thread"))]
+struct Count(AtomicUsize);
+
+#[cfg(not(feature = "single-thread"))]
+impl Count {
+    #[inline]
+    fn new() -> Self {
+        Self(AtomicUsize::new(1))
+    }
+
+    #[inline]
+    fn get(&self) -> usize {
+        self.0.load(Ordering::Acquire)
+    }
+
+    /// Add one and return the previous count
+    #[inline]
+    fn increment(&self) -> usize {
+        // Like `Arc`, a new reference only needs the existing one to be alive
+        self.0.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Subtract one and return `true` if it was the last reference
+    #[inline]
+    fn decrement(&self) -> bool {
+        if self.0.fetch_sub(1, Ordering::Release) != 1 {
+            return false;
+        }
+        atomic::fence(Ordering::Acquire);
+        true
+    }
+}
+
+/// Reference count of a allocation
+#[cfg(feature = "single-thread")]
+struct Count(Cell<usize>);
+
+#[cfg(feature = "single-thread")]
+impl Count {
+    #[inline]
+    fn new() -> Self {
+        Self(Cell::new(1))
+    }
+
+    #[inline]
+    fn get(&self) -> usize {
+        self.0.get()
+    }
+
+    /// Add one and return the previous count
+    #[inline]
+    fn increment(&self) -> usize {
+        let count = self.0.get();
+        self.0.set(count + 1);
+        count
+    }
+
+    /// Subtract one and return `true` if it was the last reference
+    #[inline]
+    fn decrement(&self) -> bool {
+        let count = self.0.get() - 1;
+        self.0.set(count);
+        count == 0
+    }
+}
+
+/// Set in `Header::len` if the value is borrowed from a `'static`
+const BORROWED: usize = 1 << (usize::BITS - 1);
+
+#[repr(C)]
+struct Header {
+    count: Count,
+    hash: u64,
+    /// Metadata of the value, with [`BORROWED`] set if the value isn't in the allocation,
+    /// then it is neither dropped nor deallocated and its address follows the header
+    len: usize,
+}
+
+/// The allocation of a entry borrowing a `'static` value
+#[repr(C)]
+struct Borrowed {
+    header: Header,
+    data: *const u8,
+}
+
+/// A thin reference counted pointer to a header block followed by the value
+pub(crate) struct Prc<T: ?Sized + Pointee> {
+    ptr: NonNull<Header>,
+    _marker: PhantomData<T>,
+}
+
+// The count isn't atomic with the `single-thread` feature, so `Prc` is neither `Send` nor `Sync` then like `Rc`
+#[cfg(not(feature = "single-thread"))]
+unsafe impl<T: ?Sized + Pointee + Send + Sync> Send for Prc<T> {}
+#[cfg(not(feature = "single-thread"))]
+unsafe impl<T: ?Sized + Pointee + Send + Sync> Sync for Prc<T> {}
+
+impl<T: ?Sized + Pointee> Prc<T> {
+    /// Copy `value` into a new allocation with a reference count of 1
+    #[inline]
+    pub fn new(value: &T, hash: u64) -> Self
+    where
+        T: PoolValue,
+    {
+        unsafe { Self::new_with(value, hash, |dst| value.write_to(dst)) }
+    }
+
+    /// Allocate room for a value like `value` with a reference count of 1, and let `write` fill it
+    ///
+    /// # Safety
+    /// `write` must initialize its argument with a valid value of the size and metadata of `value`
+    pub unsafe fn new_with(value: &T, hash: u64, write: impl FnOnce(*mut u8)) -> Self {
+        let (layout, offset) = Self::layout(value);
+        debug_assert_eq!(offset, Self::data_offset());
+        unsafe {
+            let base = alloc::alloc(layout);
+            if base.is_null() {
+                alloc::handle_alloc_error(layout)
+            }
+            write(base.add(offset));
+            let header = base as *mut Header;
+            ptr::write(
+                header,
+                Header {
+                    count: Count::new(),
+                    hash,
+                    len: T::metadata(value),
+                },
+            );
+            Self {
+                ptr: NonNull::new_unchecked(header),
+                _marker: PhantomData,
+            }
+        }
+    }
+
+    /// Point a new header at `value` without copying it, with a reference count of 1  
+    /// Only the header and the address of `value` are allocated and freed
+    pub fn from_static(value: &'static T, hash: u64) -> Self {
+        let layout = Layout::new::<Borrowed>();
+        unsafe {
+            let borrowed = alloc::alloc(layout) as *mut Borrowed;
+            if borrowed.is_null() {
+                alloc::handle_alloc_error(layout)
+            }
+            ptr::write(
+                borrowed,
+                Borrowed {
+                    header: Header {
+                        count: Count::new(),
+                        hash,
+                        len: T::metadata(value) | BORROWED,
+                    },
+                    data: value as *const T as *const u8,
+                },
+            );
+            Self {
+                ptr: NonNull::new_unchecked(borrowed as *mut Header),
+                _marker: PhantomData,
+            }
+        }
+    }
+
+    /// Layout of the allocation holding `value`, and the offset of the value in it
+    #[inline]
+    fn layout(value: &T) -> (Layout, usize) {
+        let (layout, offset) = Layout::new::<Header>()
+            .extend(Layout::for_value(value))
+            .expect("capacity overflow");
+        (layout.pad_to_align(), offset)
+    }
+
+    /// Offset of the value in the allocation, the size of the header rounded up to the alignment of the value
+    #[inline]
+    fn data_offset() -> usize {
+        (mem::size_of::<Header>() + T::ALIGN - 1) & !(T::ALIGN - 1)
+    }
+
+    #[inline]
+    fn header(&self) -> &Header {
+        unsafe { self.ptr.as_ref() }
+    }
+
+    /// Pointer to the value, in the allocation or borrowed
+    #[inline]
+    fn value(&self) -> *const T {
+        let len = self.header().len;
+        let data = if len & BORROWED != 0 {
+            unsafe { (*(self.ptr.as_ptr() as *const Borrowed)).data }
+        } else {
+            unsafe { (self.ptr.as_ptr() as *const u8).add(Self::data_offset()) }
+        };
+        T::from_raw_parts(data, len & !BORROWED)
+    }
+
+    #[inline]
+    pub fn get(&self) -> &T {
+        unsafe { &*self.value() }
+    }
+
+    /// Get the hash stored in the header
+    #[inline]
+    pub fn hash(&self) -> u64 {
+        self.header().hash
+    }
+
+    #[inline]
+    pub fn strong_count(&self) -> usize {
+        self.header().count.get()
+    }
+
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.ptr == other.ptr
+    }
+
+    /// Check if the value is borrowed from a `'static` instead of stored in the allocation
+    #[inline]
+    pub fn is_borrowed(&self) -> bool {
+        self.header().len & BORROWED != 0
+    }
+
+    /// Layout of this allocation
+    #[inline]
+    fn alloc_layout(&self) -> Layout {
+        if self.is_borrowed() {
+            Layout::new::<Borrowed>()
+        } else {
+            Self::layout(self.get()).0
+        }
+    }
+
+    /// Get the size of the allocation in bytes
+    #[inline]
+    pub fn heap_size(&self) -> usize {
+        self.alloc_layout().size()
+    }
+
+    /// Turn into a thin pointer to the header without changing the reference count
+    #[inline]
+    pub fn into_raw(self) -> *const () {
+        let ptr = self.ptr.as_ptr() as *const ();
+        mem::forget(self);
+        ptr
+    }
+
+    /// Rebuild from a pointer returned by [`into_raw`](Prc::into_raw)
+    ///
+    /// # Safety
+    /// `ptr` must come from `into_raw` of a `Prc<T>` and is consumed by this call
+    #[inline]
+    pub unsafe fn from_raw(ptr: *const ()) -> Self {
+        Self {
+            ptr: NonNull::new_unchecked(ptr as *mut Header),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized + Pointee> Clone for Prc<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        if self.header().count.increment() > isize::MAX as usize {
+            std::process::abort();
+        }
+        Self {
+            ptr: self.ptr,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized + Pointee> Drop for Prc<T> {
+    #[inline]
+    fn drop(&mut self) {
+        if !self.header().count.decrement() {
+            return;
+        }
+        let layout = self.alloc_layout();
+        unsafe {
+            if !self.is_borrowed() {
+                ptr::drop_in_place(self.value() as *mut T);
+            }
+            // Nothing to drop for std atomics, loom tracks its atomics until they are dropped
+            ptr::drop_in_place(ptr::addr_of_mut!((*self.ptr.as_ptr()).count));
+            alloc::dealloc(self.ptr.as_ptr() as *mut u8, layout);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prc() {
+        let a = Prc::<str>::new("hello", 42);
+        let b = a.clone();
+        assert!(a.ptr_eq(&b));
+        assert_eq!(b.get(), "hello");
+        assert_eq!(b.hash(), 42);
+        assert_eq!(a.strong_count(), 2);
+        drop(a);
+        assert_eq!(b.strong_count(), 1);
+        let b = unsafe { Prc::<str>::from_raw(b.into_raw()) };
+        assert_eq!(b.get(), "hello");
+        assert_eq!(mem::size_of::<Header>(), 2 * mem::size_of::<usize>() + 8);
+        assert!(b.heap_size() >= mem::size_of::<Header>() + 5);
+
+        let v = Prc::<Vec<String>>::new(&vec!["a".to_string()], 0);
+        assert_eq!(v.get()[0], "a");
+        assert_eq!(Prc::<[u16]>::new(&[1, 2, 3], 0).get(), [1, 2, 3]);
+        assert_eq!(Prc::<[u64]>::new(&[1, 2, 3], 0).get(), [1, 2, 3]);
+        assert_eq!(Prc::<u128>::new(&7, 0).get(), &7);
+        assert_eq!(
+            Prc::<Path>::new(Path::new("a/b"), 0).get(),
+            Path::new("a/b")
+        );
+    }
+
+    #[test]
+    fn test_prc_static() {
+        static VALUE: &str = "borrowed from a static";
+        let a = Prc::<str>::from_static(VALUE, 7);
+        let b = a.clone();
+        assert!(b.is_borrowed());
+        assert!(!Prc::<str>::new(VALUE, 7).is_borrowed());
+        assert_eq!(b.get().as_ptr(), VALUE.as_ptr());
+        assert_eq!(b.hash(), 7);
+        assert_eq!(b.heap_size(), mem::size_of::<Borrowed>());
+        drop(a);
+        drop(b);
+    }
+
+    #[test]
+    #[cfg(loom)]
+    fn test_loom_prc() {
+        loom::model(|| {
+            let a = Prc::<str>::new("loom", 0);
+            let b = a.clone();
+            let t = loom::thread::spawn(move || {
+                let c = b.clone();
+                drop(b);
+                c.strong_count()
+            });
+            let count = a.strong_count();
+            assert!((1..=3).contains(&count));
+            assert!(t.join().unwrap() >= 1);
+            assert_eq!(a.strong_count(), 1);
+        });
+    }
+}
@@ -0,0 +1,84 @@
+//! `ToRedisArgs` and `FromRedisValue` of [redis](https://crates.io/crates/redis)
+//!
+//! Strings read from replies are interned from the borrowed reply without a intermediate `String`
+
+use std::str;
+
+use ::redis::{FromRedisValue, ParsingError, RedisWrite, ToRedisArgs, ToSingleRedisArg, Value};
+
+use crate::{IStr, MowStr};
+
+/// Intern a string or bulk string reply, other replies are converted like a `String`
+fn intern_value(v: &Value) -> Result<IStr, ParsingError> {
+    match v {
+        Value::BulkString(bytes) => Ok(IStr::new(str::from_utf8(bytes)?)),
+        Value::SimpleString(s) => Ok(IStr::new(s)),
+        v => String::from_redis_value_ref(v).map(IStr::from_string),
+    }
+}
+
+impl ToRedisArgs for IStr {
+    #[inline]
+    fn write_redis_args<W: ?Sized + RedisWrite>(&self, out: &mut W) {
+        out.write_arg(self.as_bytes())
+    }
+}
+
+impl ToSingleRedisArg for IStr {}
+
+impl FromRedisValue for IStr {
+    #[inline]
+    fn from_redis_value_ref(v: &Value) -> Result<Self, ParsingError> {
+        intern_value(v)
+    }
+
+    #[inline]
+    fn from_redis_value(v: Value) -> Result<Self, ParsingError> {
+        intern_value(&v)
+    }
+}
+
+impl ToRedisArgs for MowStr {
+    #[inline]
+    fn write_redis_args<W: ?Sized + RedisWrite>(&self, out: &mut W) {
+        out.write_arg(self.as_bytes())
+    }
+}
+
+impl ToSingleRedisArg for MowStr {}
+
+impl FromRedisValue for MowStr {
+    /// Convert to a interned string
+    #[inline]
+    fn from_redis_value_ref(v: &Value) -> Result<Self, ParsingError> {
+        intern_value(v).map(MowStr::from)
+    }
+
+    #[inline]
+    fn from_redis_value(v: Value) -> Result<Self, ParsingError> {
+        intern_value(&v).map(MowStr::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redis() {
+        let key = IStr::new("session:0123456789abcdef");
+        assert_eq!(key.to_redis_args(), vec![key.as_bytes().to_vec()]);
+        let reply = Value::Array(vec![
+            Value::BulkString(key.as_bytes().to_vec()),
+            Value::SimpleString(key.to_string()),
+            Value::Int(42),
+        ]);
+        let values: Vec<IStr> = ::redis::from_redis_value(reply).unwrap();
+        assert_eq!(values[0].as_ptr(), key.as_ptr());
+        assert_eq!(values[1].as_ptr(), key.as_ptr());
+        assert_eq!(values[2], IStr::new("42"));
+        let m: MowStr = ::redis::from_redis_value_ref(&Value::Okay).unwrap();
+        assert_eq!(m, "OK");
+        assert!(IStr::from_redis_value(Value::BulkString(vec![0xff])).is_err());
+    }
+}
@@ -0,0 +1,71 @@
+//! Batch release of transient interning
+
+use std::{cell::RefCell, mem};
+
+use crate::IStr;
+
+/// Create a new [`InternScope`]
+///
+/// # Example
+/// ```
+/// # use pstr::IStr;
+/// let scope = pstr::scope();
+/// let s = scope.intern("per-request value");
+/// assert_eq!(s, "per-request value");
+/// drop(s);
+/// drop(scope); // entry removed from the pool here
+/// ```
+#[inline]
+pub fn scope() -> InternScope {
+    InternScope::new()
+}
+
+/// A scope guard tracking everything interned through it  
+///
+/// When the scope ends, every tracked entry that is not referenced elsewhere is removed from the pool,
+/// so per-request interning doesn't require a global garbage collection to clean up
+#[derive(Debug, Default)]
+pub struct InternScope {
+    interned: RefCell<Vec<IStr>>,
+}
+
+impl InternScope {
+    /// New a empty scope
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make a `IStr` tracked by this scope
+    #[inline]
+    pub fn intern(&self, s: impl AsRef<str>) -> IStr {
+        self.track(IStr::new(s))
+    }
+
+    /// Track a existing `IStr` by this scope
+    #[inline]
+    pub fn track(&self, s: IStr) -> IStr {
+        self.interned.borrow_mut().push(s.clone());
+        s
+    }
+
+    /// Get the number of tracked handles
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.interned.borrow().len()
+    }
+
+    /// Check if nothing is tracked
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.interned.borrow().is_empty()
+    }
+}
+
+impl Drop for InternScope {
+    fn drop(&mut self) {
+        for s in mem::take(self.interned.get_mut()) {
+            s.release();
+        }
+    }
+}
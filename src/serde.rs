@@ -0,0 +1,509 @@
+//! Serde support
+//!
+//! `IStr` and `MowStr` serialize as plain strings, and deserializing a `IStr` interns it.
+//! [`InterningDeserializer`] interns every string of a document while it is deserialized,
+//! see [`deserialize_interned`].
+//! [`interned_keys`] is for `#[serde(with)]` on maps keyed by `IStr`
+
+use std::{cell::RefCell, fmt, marker::PhantomData};
+
+use ::serde::{
+    de::{self, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{
+    pool::{self, Intern},
+    IStr, MowStr,
+};
+
+pub mod interned_keys;
+
+impl Serialize for IStr {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self)
+    }
+}
+
+impl Serialize for MowStr {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self)
+    }
+}
+
+struct IStrVisitor;
+
+impl<'de> Visitor<'de> for IStrVisitor {
+    type Value = IStr;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a string")
+    }
+
+    #[inline]
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<IStr, E> {
+        Ok(IStr::new(v))
+    }
+
+    #[inline]
+    fn visit_string<E: de::Error>(self, v: String) -> Result<IStr, E> {
+        Ok(IStr::from_string(v))
+    }
+}
+
+impl<'de> Deserialize<'de> for IStr {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(IStrVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for MowStr {
+    /// Deserialize as a interned string
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        IStr::deserialize(deserializer).map(MowStr::from)
+    }
+}
+
+/// Strings interned by a [`InterningDeserializer`], kept alive so the deserialized value can borrow them
+///
+/// Every string is in the global string pool, so equal strings borrowed from the arena share one allocation
+#[derive(Debug, Default)]
+pub struct InternArena {
+    strs: RefCell<Vec<Intern<str>>>,
+}
+
+impl InternArena {
+    /// New a empty arena
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Intern `s` and borrow it for as long as the arena lives
+    pub fn intern(&self, s: &str) -> &str {
+        let intern = pool::intern_str(s);
+        let s: *const str = intern.get();
+        self.strs.borrow_mut().push(intern);
+        // SAFETY: a entry never moves and the handle pushed above keeps it alive until the arena is dropped
+        unsafe { &*s }
+    }
+
+    /// Get the number of strings interned through the arena, counting repeats
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.strs.borrow().len()
+    }
+
+    /// Check if nothing was interned through the arena
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.strs.borrow().is_empty()
+    }
+}
+
+impl Drop for InternArena {
+    fn drop(&mut self) {
+        for intern in self.strs.get_mut().iter() {
+            pool::with_owning_str_pool(intern, |p| p.on_drop(intern))
+        }
+    }
+}
+
+/// Deserialize a `T` from `deserializer`, interning every string on the way
+///
+/// Strings are handed to `T` borrowed from `arena`,
+/// so `&str` and `#[serde(borrow)] Cow<str>` fields of equal strings point to the same pooled bytes
+/// and `IStr` fields find their entry without hashing a copy.
+/// `String` fields still copy out of the pool, they can't share memory
+///
+/// # Example
+/// ```
+/// # use pstr::serde::{deserialize_interned, InternArena};
+/// let json = r#"[{"status": "active"}, {"status": "active"}]"#;
+/// #[derive(serde::Deserialize)]
+/// struct Row<'a> {
+///     status: &'a str,
+/// }
+///
+/// let arena = InternArena::new();
+/// let mut de = serde_json::Deserializer::from_str(json);
+/// let rows: Vec<Row> = deserialize_interned(&mut de, &arena).unwrap();
+/// assert_eq!(rows[0].status.as_ptr(), rows[1].status.as_ptr());
+/// ```
+#[inline]
+pub fn deserialize_interned<'a, 'de, T, D>(
+    deserializer: D,
+    arena: &'a InternArena,
+) -> Result<T, D::Error>
+where
+    T: Deserialize<'a>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(InterningDeserializer::new(deserializer, arena))
+}
+
+/// A `DeserializeSeed` deserializing a `T` with [`deserialize_interned`]
+pub struct InterningSeed<'a, T> {
+    arena: &'a InternArena,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> InterningSeed<'a, T> {
+    /// New a seed interning into `arena`
+    #[inline]
+    pub fn new(arena: &'a InternArena) -> Self {
+        Self {
+            arena,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'de, T: Deserialize<'a>> DeserializeSeed<'de> for InterningSeed<'a, T> {
+    type Value = T;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<T, D::Error> {
+        deserialize_interned(deserializer, self.arena)
+    }
+}
+
+/// A `Deserializer` wrapper interning every string the wrapped deserializer produces into a [`InternArena`]
+pub struct InterningDeserializer<'a, 'de, D> {
+    inner: D,
+    arena: &'a InternArena,
+    _marker: PhantomData<&'de ()>,
+}
+
+impl<'a, 'de, D: Deserializer<'de>> InterningDeserializer<'a, 'de, D> {
+    /// Wrap `deserializer`, interning into `arena`
+    #[inline]
+    pub fn new(deserializer: D, arena: &'a InternArena) -> Self {
+        Self {
+            inner: deserializer,
+            arena,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Wraps the visitors, seeds and accessors passed between the two deserializers
+struct Wrap<'a, 'de, X> {
+    inner: X,
+    arena: &'a InternArena,
+    _marker: PhantomData<&'de ()>,
+}
+
+impl<'a, 'de, X> Wrap<'a, 'de, X> {
+    #[inline]
+    fn new(inner: X, arena: &'a InternArena) -> Self {
+        Self {
+            inner,
+            arena,
+            _marker: PhantomData,
+        }
+    }
+}
+
+macro_rules! forward_deserialize {
+    ($($method:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            #[inline]
+            fn $method<V: Visitor<'a>>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, Self::Error> {
+                self.inner.$method($($arg,)* Wrap::new(visitor, self.arena))
+            }
+        )*
+    };
+}
+
+impl<'a, 'de, D: Deserializer<'de>> Deserializer<'a> for InterningDeserializer<'a, 'de, D> {
+    type Error = D::Error;
+
+    forward_deserialize! {
+        deserialize_any();
+        deserialize_bool();
+        deserialize_i8();
+        deserialize_i16();
+        deserialize_i32();
+        deserialize_i64();
+        deserialize_i128();
+        deserialize_u8();
+        deserialize_u16();
+        deserialize_u32();
+        deserialize_u64();
+        deserialize_u128();
+        deserialize_f32();
+        deserialize_f64();
+        deserialize_char();
+        deserialize_str();
+        deserialize_string();
+        deserialize_bytes();
+        deserialize_byte_buf();
+        deserialize_option();
+        deserialize_unit();
+        deserialize_unit_struct(name: &'static str);
+        deserialize_newtype_struct(name: &'static str);
+        deserialize_seq();
+        deserialize_tuple(len: usize);
+        deserialize_tuple_struct(name: &'static str, len: usize);
+        deserialize_map();
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]);
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]);
+        deserialize_identifier();
+        deserialize_ignored_any();
+    }
+
+    #[inline]
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+macro_rules! forward_visit {
+    ($($method:ident($ty:ty);)*) => {
+        $(
+            #[inline]
+            fn $method<E: de::Error>(self, v: $ty) -> Result<Self::Value, E> {
+                self.inner.$method(v)
+            }
+        )*
+    };
+}
+
+impl<'a, 'de, V: Visitor<'a>> Visitor<'de> for Wrap<'a, 'de, V> {
+    type Value = V::Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.expecting(f)
+    }
+
+    forward_visit! {
+        visit_bool(bool);
+        visit_i8(i8);
+        visit_i16(i16);
+        visit_i32(i32);
+        visit_i64(i64);
+        visit_i128(i128);
+        visit_u8(u8);
+        visit_u16(u16);
+        visit_u32(u32);
+        visit_u64(u64);
+        visit_u128(u128);
+        visit_f32(f32);
+        visit_f64(f64);
+        visit_char(char);
+        visit_bytes(&[u8]);
+        visit_byte_buf(Vec<u8>);
+    }
+
+    #[inline]
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        self.inner.visit_borrowed_str(self.arena.intern(v))
+    }
+
+    #[inline]
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        self.visit_str(v)
+    }
+
+    #[inline]
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        self.visit_str(&v)
+    }
+
+    #[inline]
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.inner.visit_none()
+    }
+
+    #[inline]
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.inner
+            .visit_some(InterningDeserializer::new(deserializer, self.arena))
+    }
+
+    #[inline]
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.inner.visit_unit()
+    }
+
+    #[inline]
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        self.inner
+            .visit_newtype_struct(InterningDeserializer::new(deserializer, self.arena))
+    }
+
+    #[inline]
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        self.inner.visit_seq(Wrap::new(seq, self.arena))
+    }
+
+    #[inline]
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        self.inner.visit_map(Wrap::new(map, self.arena))
+    }
+
+    #[inline]
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        self.inner.visit_enum(Wrap::new(data, self.arena))
+    }
+}
+
+impl<'a, 'de, S: DeserializeSeed<'a>> DeserializeSeed<'de> for Wrap<'a, 'de, S> {
+    type Value = S::Value;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.inner
+            .deserialize(InterningDeserializer::new(deserializer, self.arena))
+    }
+}
+
+impl<'a, 'de, A: SeqAccess<'de>> SeqAccess<'a> for Wrap<'a, 'de, A> {
+    type Error = A::Error;
+
+    #[inline]
+    fn next_element_seed<T: DeserializeSeed<'a>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        self.inner.next_element_seed(Wrap::new(seed, self.arena))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, 'de, A: MapAccess<'de>> MapAccess<'a> for Wrap<'a, 'de, A> {
+    type Error = A::Error;
+
+    #[inline]
+    fn next_key_seed<K: DeserializeSeed<'a>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        self.inner.next_key_seed(Wrap::new(seed, self.arena))
+    }
+
+    #[inline]
+    fn next_value_seed<V: DeserializeSeed<'a>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.inner.next_value_seed(Wrap::new(seed, self.arena))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, 'de, A: EnumAccess<'de>> EnumAccess<'a> for Wrap<'a, 'de, A> {
+    type Error = A::Error;
+    type Variant = Wrap<'a, 'de, A::Variant>;
+
+    #[inline]
+    fn variant_seed<V: DeserializeSeed<'a>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let arena = self.arena;
+        let (value, variant) = self.inner.variant_seed(Wrap::new(seed, arena))?;
+        Ok((value, Wrap::new(variant, arena)))
+    }
+}
+
+impl<'a, 'de, A: VariantAccess<'de>> VariantAccess<'a> for Wrap<'a, 'de, A> {
+    type Error = A::Error;
+
+    #[inline]
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        self.inner.unit_variant()
+    }
+
+    #[inline]
+    fn newtype_variant_seed<T: DeserializeSeed<'a>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        self.inner.newtype_variant_seed(Wrap::new(seed, self.arena))
+    }
+
+    #[inline]
+    fn tuple_variant<V: Visitor<'a>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.inner
+            .tuple_variant(len, Wrap::new(visitor, self.arena))
+    }
+
+    #[inline]
+    fn struct_variant<V: Visitor<'a>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.inner
+            .struct_variant(fields, Wrap::new(visitor, self.arena))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{borrow::Cow, collections::HashMap};
+
+    #[test]
+    fn test_serde() {
+        let s: IStr = serde_json::from_str("\"serde istr\"").unwrap();
+        assert_eq!(s, IStr::new("serde istr"));
+        assert_eq!(serde_json::to_string(&s).unwrap(), "\"serde istr\"");
+        let m: MowStr = serde_json::from_str("\"serde mowstr\"").unwrap();
+        assert!(m.is_interned());
+        assert_eq!(serde_json::to_string(&m).unwrap(), "\"serde mowstr\"");
+    }
+
+    #[test]
+    fn test_deserialize_interned() {
+        #[derive(Deserialize)]
+        enum Kind<'a> {
+            Named {
+                #[serde(borrow)]
+                name: Cow<'a, str>,
+            },
+            Plain(String),
+        }
+
+        let json = r#"{
+            "a": [{"Named": {"name": "deserialize interned"}}, {"Plain": "x"}],
+            "b": [{"Named": {"name": "deserialize interned"}}, null]
+        }"#;
+        let arena = InternArena::new();
+        let seed = InterningSeed::<HashMap<&str, Vec<Option<Kind>>>>::new(&arena);
+        let v = seed
+            .deserialize(&mut serde_json::Deserializer::from_str(json))
+            .unwrap();
+        let name = |k: &str| match &v[k][0] {
+            Some(Kind::Named {
+                name: Cow::Borrowed(name),
+            }) => name.as_ptr(),
+            _ => unreachable!(),
+        };
+        assert_eq!(name("a"), name("b"));
+        assert!(matches!(&v["a"][1], Some(Kind::Plain(s)) if s == "x"));
+        assert!(v["b"][1].is_none());
+        assert!(arena.len() >= 5);
+    }
+}
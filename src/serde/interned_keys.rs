@@ -0,0 +1,100 @@
+//! `#[serde(with = "pstr::serde::interned_keys")]` for maps keyed by `IStr`
+//!
+//! Works with any map that iterates as `(&IStr, &V)` and can be built from `(IStr, V)`,
+//! such as `HashMap<IStr, V>` and `BTreeMap<IStr, V>`.
+//! The keys are interned as they are read
+//!
+//! # Example
+//! ```
+//! # use pstr::IStr;
+//! # use std::collections::HashMap;
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Counts {
+//!     #[serde(with = "pstr::serde::interned_keys")]
+//!     by_status: HashMap<IStr, u32>,
+//! }
+//!
+//! let c: Counts = serde_json::from_str(r#"{"by_status": {"active": 3}}"#).unwrap();
+//! assert_eq!(c.by_status[&IStr::new("active")], 3);
+//! assert_eq!(serde_json::to_string(&c).unwrap(), r#"{"by_status":{"active":3}}"#);
+//! ```
+
+use std::{fmt, marker::PhantomData};
+
+use ::serde::{
+    de::{MapAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::IStr;
+
+/// Serialize `map` as a map of strings
+#[inline]
+pub fn serialize<'a, M, V, S>(map: &'a M, serializer: S) -> Result<S::Ok, S::Error>
+where
+    &'a M: IntoIterator<Item = (&'a IStr, &'a V)>,
+    V: Serialize + 'a,
+    S: Serializer,
+{
+    serializer.collect_map(map)
+}
+
+/// Deserialize a map of strings, interning the keys
+#[inline]
+pub fn deserialize<'de, M, V, D>(deserializer: D) -> Result<M, D::Error>
+where
+    M: Default + Extend<(IStr, V)>,
+    V: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_map(KeysVisitor(PhantomData))
+}
+
+struct KeysVisitor<M, V>(PhantomData<fn() -> (M, V)>);
+
+impl<'de, M, V> Visitor<'de> for KeysVisitor<M, V>
+where
+    M: Default + Extend<(IStr, V)>,
+    V: Deserialize<'de>,
+{
+    type Value = M;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a map with string keys")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<M, A::Error> {
+        let mut map = M::default();
+        while let Some((k, v)) = access.next_entry::<IStr, V>()? {
+            map.extend(Some((k, v)));
+        }
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use ::serde::{Deserialize, Serialize};
+
+    use crate::IStr;
+
+    #[test]
+    fn test_interned_keys() {
+        #[derive(Serialize, Deserialize)]
+        struct Maps {
+            #[serde(with = "super")]
+            hash: HashMap<IStr, u8>,
+            #[serde(with = "super")]
+            btree: BTreeMap<IStr, Vec<u8>>,
+        }
+
+        let json = r#"{"hash":{"interned_keys":1},"btree":{"a":[],"b":[2]}}"#;
+        let maps: Maps = serde_json::from_str(json).unwrap();
+        let key = maps.hash.keys().next().unwrap();
+        assert_eq!(key.as_ptr(), IStr::new("interned_keys").as_ptr());
+        assert_eq!(maps.btree[&IStr::new("b")], [2]);
+        assert_eq!(serde_json::to_string(&maps).unwrap(), json);
+    }
+}
@@ -0,0 +1,117 @@
+//! Conversions with the small string types of [smol_str](https://crates.io/crates/smol_str) and [compact_str](https://crates.io/crates/compact_str)
+//!
+//! Strings coming in are interned, going out they are copied into the small string
+
+#[cfg(feature = "compact_str")]
+use ::compact_str::CompactString;
+#[cfg(feature = "smol_str")]
+use ::smol_str::SmolStr;
+
+use crate::{IStr, MowStr};
+
+#[cfg(feature = "smol_str")]
+impl From<&SmolStr> for IStr {
+    #[inline]
+    fn from(s: &SmolStr) -> Self {
+        IStr::new(s)
+    }
+}
+
+#[cfg(feature = "smol_str")]
+impl From<SmolStr> for IStr {
+    #[inline]
+    fn from(s: SmolStr) -> Self {
+        IStr::new(s)
+    }
+}
+
+#[cfg(feature = "smol_str")]
+impl From<SmolStr> for MowStr {
+    #[inline]
+    fn from(s: SmolStr) -> Self {
+        MowStr::new(s)
+    }
+}
+
+#[cfg(feature = "smol_str")]
+impl From<IStr> for SmolStr {
+    #[inline]
+    fn from(s: IStr) -> Self {
+        SmolStr::new(s)
+    }
+}
+
+#[cfg(feature = "smol_str")]
+impl From<MowStr> for SmolStr {
+    #[inline]
+    fn from(s: MowStr) -> Self {
+        SmolStr::new(s)
+    }
+}
+
+#[cfg(feature = "compact_str")]
+impl From<&CompactString> for IStr {
+    #[inline]
+    fn from(s: &CompactString) -> Self {
+        IStr::new(s)
+    }
+}
+
+#[cfg(feature = "compact_str")]
+impl From<CompactString> for IStr {
+    #[inline]
+    fn from(s: CompactString) -> Self {
+        IStr::new(s)
+    }
+}
+
+#[cfg(feature = "compact_str")]
+impl From<CompactString> for MowStr {
+    #[inline]
+    fn from(s: CompactString) -> Self {
+        MowStr::new(s)
+    }
+}
+
+#[cfg(feature = "compact_str")]
+impl From<IStr> for CompactString {
+    #[inline]
+    fn from(s: IStr) -> Self {
+        CompactString::new(s)
+    }
+}
+
+#[cfg(feature = "compact_str")]
+impl From<MowStr> for CompactString {
+    #[inline]
+    fn from(s: MowStr) -> Self {
+        CompactString::new(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "smol_str")]
+    fn test_smol_str() {
+        let s = SmolStr::new("smol_str to be interned");
+        let a = IStr::from(&s);
+        assert_eq!(IStr::from(s.clone()).as_ptr(), a.as_ptr());
+        assert!(MowStr::from(s.clone()).is_interned());
+        assert_eq!(SmolStr::from(a), s);
+        assert_eq!(SmolStr::from(MowStr::new("m")), "m");
+    }
+
+    #[test]
+    #[cfg(feature = "compact_str")]
+    fn test_compact_str() {
+        let s = CompactString::new("compact_str to be interned");
+        let a = IStr::from(&s);
+        assert_eq!(IStr::from(s.clone()).as_ptr(), a.as_ptr());
+        assert!(MowStr::from(s.clone()).is_interned());
+        assert_eq!(CompactString::from(a), s);
+        assert_eq!(CompactString::from(MowStr::new("m")), "m");
+    }
+}
@@ -0,0 +1,120 @@
+//! Database types for [sqlx](https://crates.io/crates/sqlx)
+//!
+//! `IStr` and `MowStr` are text columns of any database whose `&str` is one,
+//! so it works with the Postgres, MySQL and SQLite drivers.
+//! Decoding interns the text borrowed from the row, so low-cardinality columns share their entries
+
+use ::sqlx::{encode::IsNull, error::BoxDynError, Database, Decode, Encode, Type};
+
+use crate::{IStr, MowStr};
+
+impl<DB: Database> Type<DB> for IStr
+where
+    str: Type<DB>,
+{
+    #[inline]
+    fn type_info() -> DB::TypeInfo {
+        <str as Type<DB>>::type_info()
+    }
+
+    #[inline]
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <str as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for IStr
+where
+    for<'a> &'a str: Encode<'q, DB>,
+{
+    #[inline]
+    fn encode_by_ref(&self, buf: &mut DB::ArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        <&str as Encode<'q, DB>>::encode(self, buf)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> usize {
+        <&str as Encode<'q, DB>>::size_hint(&&**self)
+    }
+}
+
+impl<'r, DB: Database> Decode<'r, DB> for IStr
+where
+    &'r str: Decode<'r, DB>,
+{
+    #[inline]
+    fn decode(value: DB::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        <&str as Decode<'r, DB>>::decode(value).map(IStr::new)
+    }
+}
+
+impl<DB: Database> Type<DB> for MowStr
+where
+    str: Type<DB>,
+{
+    #[inline]
+    fn type_info() -> DB::TypeInfo {
+        <str as Type<DB>>::type_info()
+    }
+
+    #[inline]
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <str as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for MowStr
+where
+    for<'a> &'a str: Encode<'q, DB>,
+{
+    #[inline]
+    fn encode_by_ref(&self, buf: &mut DB::ArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        <&str as Encode<'q, DB>>::encode(self, buf)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> usize {
+        <&str as Encode<'q, DB>>::size_hint(&&**self)
+    }
+}
+
+impl<'r, DB: Database> Decode<'r, DB> for MowStr
+where
+    &'r str: Decode<'r, DB>,
+{
+    /// Decode as a interned string
+    #[inline]
+    fn decode(value: DB::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        <&str as Decode<'r, DB>>::decode(value).map(MowStr::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::sqlx::{Connection, Row, SqliteConnection};
+
+    #[test]
+    fn test_sqlx() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+            // Decoded from the rows, `query_as` needs `Send` outputs which the `single-thread` feature doesn't have
+            let rows: Vec<(IStr, MowStr)> = ::sqlx::query("SELECT ?1, ?1 UNION ALL SELECT ?2, ?1")
+                .bind(IStr::new("active, too long to be inlined"))
+                .bind(MowStr::new("closed"))
+                .fetch_all(&mut conn)
+                .await
+                .unwrap()
+                .iter()
+                .map(|row| (row.get(0), row.get(1)))
+                .collect();
+            assert_eq!(rows[0].0, IStr::new("active, too long to be inlined"));
+            assert_eq!(rows[0].0.as_ptr(), rows[1].1.as_ptr());
+            assert!(rows[1].1.is_interned());
+            assert_eq!(rows[1].0, "closed");
+        });
+    }
+}
@@ -0,0 +1,82 @@
+//! Interning streamed data
+
+use std::{
+    io::{self, Read},
+    str,
+};
+
+use crate::{builder::with_scratch_bytes, IStr};
+
+/// Collect data into the thread's scratch buffer and intern it
+fn with_scratch(fill: impl FnOnce(&mut Vec<u8>) -> io::Result<()>) -> io::Result<IStr> {
+    with_scratch_bytes(|buf| {
+        fill(buf)?;
+        str::from_utf8(buf)
+            .map(IStr::new)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    })
+}
+
+impl IStr {
+    /// Read `reader` to the end and intern the content  
+    ///
+    /// The data is read into a scratch buffer reused by every call on the same thread,
+    /// so content already in the pool is never copied into a owned allocation.
+    /// Return a `InvalidData` error if the data is not valid UTF-8
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::IStr;
+    /// let a = IStr::read_from("payload".as_bytes()).unwrap();
+    /// assert_eq!(a, IStr::new("payload"));
+    /// ```
+    pub fn read_from(mut reader: impl Read) -> io::Result<Self> {
+        with_scratch(|buf| reader.read_to_end(buf).map(drop))
+    }
+
+    /// Concatenate `chunks` and intern the result, see [`read_from`](IStr::read_from)
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::IStr;
+    /// let a = IStr::from_chunks(vec!["pay", "load"]).unwrap();
+    /// assert_eq!(a, "payload");
+    /// ```
+    pub fn from_chunks<I>(chunks: I) -> io::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        with_scratch(|buf| {
+            for chunk in chunks {
+                buf.extend_from_slice(chunk.as_ref());
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::MAX_KEPT_SCRATCH;
+
+    #[test]
+    fn test_from_chunks() {
+        let big = "x".repeat(MAX_KEPT_SCRATCH + 1);
+        let a = IStr::from_chunks(big.as_bytes().chunks(4096)).unwrap();
+        assert_eq!(a.len(), big.len());
+        assert_eq!(with_scratch_bytes(|buf| buf.capacity()), 0);
+        assert!(IStr::from_chunks(vec![&[0xffu8][..]]).is_err());
+    }
+
+    #[test]
+    fn test_reentrant() {
+        let chunks = (0..3).map(|i| {
+            let inner = IStr::from_chunks([format!("inner{}", i)]).unwrap();
+            format!("{},", inner)
+        });
+        let a = IStr::from_chunks(chunks).unwrap();
+        assert_eq!(a, "inner0,inner1,inner2,");
+    }
+}
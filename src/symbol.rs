@@ -0,0 +1,175 @@
+//! Dense copyable handles for interned strings
+
+use std::{convert::TryFrom, fmt};
+
+#[cfg(not(feature = "single-thread"))]
+use once_cell::sync::Lazy;
+
+use crate::{sync::RwLock, IStr, IStrKey, IStrMap};
+
+#[cfg(not(feature = "single-thread"))]
+static SYMBOLS: Lazy<RwLock<SymbolTable>> = Lazy::new(Default::default);
+
+#[cfg(feature = "single-thread")]
+thread_local! {
+    static SYMBOLS: &'static RwLock<SymbolTable> = Box::leak(Default::default());
+}
+
+/// Get the global symbol table, each thread has its own with the `single-thread` feature
+#[inline]
+fn symbols() -> &'static RwLock<SymbolTable> {
+    #[cfg(not(feature = "single-thread"))]
+    return &SYMBOLS;
+    #[cfg(feature = "single-thread")]
+    return SYMBOLS.with(|table| *table);
+}
+
+#[derive(Debug, Default)]
+struct SymbolTable {
+    ids: IStrMap<u32>,
+    names: Vec<IStr>,
+}
+
+/// A 4 byte `Copy` handle to a `IStr`
+///
+/// Symbols are allocated in order from a global table which keeps its strings alive,
+/// so a symbol always resolves back to the same string.  
+/// With the `single-thread` feature each thread has its own table, a symbol only resolves on the thread that made it
+///
+/// # Example
+/// ```
+/// # use pstr::{IStr, Symbol};
+/// let a = IStr::new("ident").symbol();
+/// let b = Symbol::new("ident");
+/// assert_eq!(a, b);
+/// assert_eq!(a.resolve(), "ident");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// Get the symbol of a string, interning it if needed
+    #[inline]
+    pub fn new(s: impl AsRef<str>) -> Self {
+        IStr::new(s).symbol()
+    }
+
+    /// Get the interned string of this symbol
+    #[inline]
+    pub fn resolve(self) -> IStr {
+        symbols().read().names[self.0 as usize].clone()
+    }
+
+    /// Get the raw index
+    #[inline]
+    pub fn as_u32(self) -> u32 {
+        self.0
+    }
+
+    /// Register `table` as the first symbols, so `table[i]` always gets the symbol with index `i`  
+    ///
+    /// Return `false` if other symbols were already allocated at those indexes,
+    /// registering the same table again is a no-op
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::Symbol;
+    /// static KEYWORDS: [&str; 2] = ["fn", "let"];
+    /// assert!(Symbol::preseed(&KEYWORDS));
+    /// assert_eq!(Symbol::new("let").as_u32(), 1);
+    /// ```
+    pub fn preseed(table: &[&'static str]) -> bool {
+        let mut symbols = symbols().write();
+        let known = symbols.names.len().min(table.len());
+        if symbols.names[..known]
+            .iter()
+            .zip(table)
+            .any(|(a, b)| a != b)
+        {
+            return false;
+        }
+        for s in &table[known..] {
+            let s = IStr::from_static(s);
+            if symbols.ids.contains_key(IStrKey::from_ref(&s)) {
+                return false;
+            }
+            let id = u32::try_from(symbols.names.len()).expect("symbol table overflow");
+            symbols.names.push(s.clone());
+            symbols.ids.insert(s.into(), id);
+        }
+        true
+    }
+
+    /// Create a symbol from a raw index, only valid for preseeded tables
+    #[doc(hidden)]
+    #[inline]
+    pub const fn __from_u32(id: u32) -> Self {
+        Self(id)
+    }
+}
+
+impl IStr {
+    /// Get the [`Symbol`] of this string
+    ///
+    /// The first call for a string adds it to the global symbol table, it will never be collected afterwards
+    ///
+    /// # Panics
+    /// Panics if the table already holds `u32::MAX + 1` symbols
+    pub fn symbol(&self) -> Symbol {
+        if let Some(id) = symbols().read().ids.get(IStrKey::from_ref(self)) {
+            return Symbol(*id);
+        }
+        let mut table = symbols().write();
+        if let Some(id) = table.ids.get(IStrKey::from_ref(self)) {
+            return Symbol(*id);
+        }
+        let id = u32::try_from(table.names.len()).expect("symbol table overflow");
+        table.names.push(self.clone());
+        table.ids.insert(self.clone().into(), id);
+        Symbol(id)
+    }
+}
+
+impl From<IStr> for Symbol {
+    #[inline]
+    fn from(v: IStr) -> Self {
+        v.symbol()
+    }
+}
+
+impl From<&'_ str> for Symbol {
+    #[inline]
+    fn from(v: &'_ str) -> Self {
+        Self::new(v)
+    }
+}
+
+impl From<Symbol> for IStr {
+    #[inline]
+    fn from(v: Symbol) -> Self {
+        v.resolve()
+    }
+}
+
+impl fmt::Display for Symbol {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.resolve(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symbol() {
+        assert_eq!(std::mem::size_of::<Symbol>(), 4);
+        let a = Symbol::new("symbol_a");
+        let b = Symbol::new("symbol_b");
+        assert_ne!(a, b);
+        assert_eq!(a, IStr::new("symbol_a").symbol());
+        assert_eq!(b.resolve(), "symbol_b");
+        assert_eq!(b.to_string(), "symbol_b");
+    }
+}
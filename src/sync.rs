@@ -0,0 +1,157 @@
+//! Locks used inside the crate, from `parking_lot` with the `parking_lot` feature, otherwise from std  
+//! With the `single-thread` feature they are `RefCell`s
+//!
+//! Built with `--cfg loom` the locks of the pools and the atomics of the pools and the reference counts come from
+//! [loom](https://crates.io/crates/loom), so their interleavings can be model checked.
+//! The global pools live across models, only pools made inside `loom::model` can be used then
+
+#[cfg(all(feature = "parking_lot", not(any(feature = "single-thread", loom))))]
+pub(crate) use parking_lot::RwLock;
+
+#[cfg(feature = "single-thread")]
+pub(crate) use self::cell_lock::RwLock;
+#[cfg(all(loom, not(feature = "single-thread")))]
+pub(crate) use self::loom_lock::RwLock;
+#[cfg(not(any(feature = "parking_lot", feature = "single-thread", loom)))]
+pub(crate) use self::std_lock::RwLock;
+
+/// Atomics of the pools and the reference counts
+pub(crate) mod atomic {
+    #[cfg(loom)]
+    pub(crate) use loom::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    #[cfg(not(loom))]
+    pub(crate) use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    /// Bits of the bloom filter, a `Cell` with the `single-thread` feature
+    #[cfg(feature = "single-thread")]
+    pub(crate) use super::cell_atomic::AtomicU64;
+    #[cfg(all(loom, not(feature = "single-thread")))]
+    pub(crate) use loom::sync::atomic::AtomicU64;
+    #[cfg(not(any(loom, feature = "single-thread")))]
+    pub(crate) use std::sync::atomic::AtomicU64;
+
+    /// Only the reference counts need a fence, they aren't atomic with the `single-thread` feature
+    #[cfg(all(loom, not(feature = "single-thread")))]
+    pub(crate) use loom::sync::atomic::fence;
+    #[cfg(not(any(loom, feature = "single-thread")))]
+    pub(crate) use std::sync::atomic::fence;
+}
+
+#[cfg(not(any(feature = "parking_lot", feature = "single-thread", loom)))]
+mod std_lock {
+    use std::sync::{self, PoisonError, RwLockReadGuard, RwLockWriteGuard};
+
+    /// `std::sync::RwLock` with the api of `parking_lot`, a poisoned lock is used as is
+    #[derive(Debug, Default)]
+    pub(crate) struct RwLock<T: ?Sized>(sync::RwLock<T>);
+
+    impl<T> RwLock<T> {
+        #[inline]
+        pub const fn new(value: T) -> Self {
+            Self(sync::RwLock::new(value))
+        }
+    }
+
+    impl<T: ?Sized> RwLock<T> {
+        #[inline]
+        pub fn read(&self) -> RwLockReadGuard<'_, T> {
+            self.0.read().unwrap_or_else(PoisonError::into_inner)
+        }
+
+        #[inline]
+        pub fn write(&self) -> RwLockWriteGuard<'_, T> {
+            self.0.write().unwrap_or_else(PoisonError::into_inner)
+        }
+    }
+}
+
+#[cfg(feature = "single-thread")]
+mod cell_lock {
+    use std::cell::{Ref, RefCell, RefMut};
+
+    /// `RefCell` with the api of `parking_lot::RwLock`, locking it twice for writing panics instead of deadlocking
+    #[derive(Debug, Default)]
+    pub(crate) struct RwLock<T: ?Sized>(RefCell<T>);
+
+    impl<T> RwLock<T> {
+        #[inline]
+        pub const fn new(value: T) -> Self {
+            Self(RefCell::new(value))
+        }
+    }
+
+    impl<T: ?Sized> RwLock<T> {
+        #[inline]
+        pub fn read(&self) -> Ref<'_, T> {
+            self.0.borrow()
+        }
+
+        #[inline]
+        pub fn write(&self) -> RefMut<'_, T> {
+            self.0.borrow_mut()
+        }
+    }
+}
+
+#[cfg(feature = "single-thread")]
+mod cell_atomic {
+    use std::cell::Cell;
+
+    use super::atomic::Ordering;
+
+    /// `Cell<u64>` with the api of `AtomicU64`, the orderings are ignored
+    #[derive(Debug, Default)]
+    pub(crate) struct AtomicU64(Cell<u64>);
+
+    impl AtomicU64 {
+        #[inline]
+        pub const fn new(value: u64) -> Self {
+            Self(Cell::new(value))
+        }
+
+        #[inline]
+        pub fn load(&self, _: Ordering) -> u64 {
+            self.0.get()
+        }
+
+        #[inline]
+        pub fn fetch_or(&self, value: u64, _: Ordering) -> u64 {
+            let prev = self.0.get();
+            self.0.set(prev | value);
+            prev
+        }
+    }
+}
+
+#[cfg(all(loom, not(feature = "single-thread")))]
+mod loom_lock {
+    use loom::sync::{self, RwLockReadGuard, RwLockWriteGuard};
+
+    /// `loom::sync::RwLock` with the api of `parking_lot`
+    #[derive(Debug)]
+    pub(crate) struct RwLock<T>(sync::RwLock<T>);
+
+    impl<T> RwLock<T> {
+        #[inline]
+        pub fn new(value: T) -> Self {
+            Self(sync::RwLock::new(value))
+        }
+
+        #[inline]
+        pub fn read(&self) -> RwLockReadGuard<'_, T> {
+            self.0.read().unwrap()
+        }
+
+        #[inline]
+        pub fn write(&self) -> RwLockWriteGuard<'_, T> {
+            self.0.write().unwrap()
+        }
+    }
+
+    impl<T: Default> Default for RwLock<T> {
+        #[inline]
+        fn default() -> Self {
+            Self::new(T::default())
+        }
+    }
+}
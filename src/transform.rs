@@ -0,0 +1,160 @@
+//! Interned string transforms
+
+use crate::{builder::with_scratch, IStr};
+
+/// Whether `f` maps every char of `s` to itself
+fn unchanged<I: Iterator<Item = char>>(s: &str, f: impl Fn(char) -> I) -> bool {
+    s.chars().all(|c| {
+        let mut m = f(c);
+        m.next() == Some(c) && m.next().is_none()
+    })
+}
+
+impl IStr {
+    /// Lowercase like [`str::to_lowercase`] and intern the result  
+    ///
+    /// Return a clone of `self` if nothing changes,
+    /// otherwise the result is built in a scratch buffer so no allocation happens if it is already in the pool
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::IStr;
+    /// let a = IStr::new("Content-Type");
+    /// assert_eq!(a.to_lowercase_interned(), IStr::new("content-type"));
+    /// ```
+    pub fn to_lowercase_interned(&self) -> IStr {
+        if unchanged(self, char::to_lowercase) {
+            return self.clone();
+        }
+        with_scratch(|buf| {
+            if self.is_ascii() {
+                buf.extend(self.chars().map(|c| c.to_ascii_lowercase()))
+            } else {
+                // Final sigma depends on context, leave it to std
+                buf.push_str(&self.to_lowercase())
+            }
+        })
+    }
+
+    /// Uppercase like [`str::to_uppercase`] and intern the result, see [`to_lowercase_interned`](IStr::to_lowercase_interned)
+    pub fn to_uppercase_interned(&self) -> IStr {
+        if unchanged(self, char::to_uppercase) {
+            return self.clone();
+        }
+        with_scratch(|buf| buf.extend(self.chars().flat_map(char::to_uppercase)))
+    }
+
+    /// Lowercase ASCII letters like [`str::to_ascii_lowercase`] and intern the result, see [`to_lowercase_interned`](IStr::to_lowercase_interned)
+    pub fn to_ascii_lowercase_interned(&self) -> IStr {
+        if !self.bytes().any(|b| b.is_ascii_uppercase()) {
+            return self.clone();
+        }
+        with_scratch(|buf| buf.extend(self.chars().map(|c| c.to_ascii_lowercase())))
+    }
+
+    /// Trim leading and trailing whitespace like [`str::trim`] and intern the result  
+    ///
+    /// Return a clone of `self` if there is nothing to trim
+    pub fn trim_interned(&self) -> IStr {
+        let trimmed = self.trim();
+        if trimmed.len() == self.len() {
+            return self.clone();
+        }
+        IStr::new(trimmed)
+    }
+
+    /// Replace all matches of `from` with `to` like [`str::replace`] and intern the result  
+    ///
+    /// Return a clone of `self` without allocating if `from` doesn't occur,
+    /// see [`to_lowercase_interned`](IStr::to_lowercase_interned)
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::IStr;
+    /// let a = IStr::new("x-forwarded-for");
+    /// assert_eq!(a.replace_interned("-", "_"), "x_forwarded_for");
+    /// assert_eq!(a.replace_interned("?", "_"), a);
+    /// ```
+    #[inline]
+    pub fn replace_interned(&self, from: &str, to: &str) -> IStr {
+        self.replacen_interned(from, to, usize::MAX)
+    }
+
+    /// Replace the first `count` matches of `from` with `to` like [`str::replacen`] and intern the result,
+    /// see [`replace_interned`](IStr::replace_interned)
+    pub fn replacen_interned(&self, from: &str, to: &str, count: usize) -> IStr {
+        let mut matches = self.match_indices(from).take(count).peekable();
+        if matches.peek().is_none() {
+            return self.clone();
+        }
+        with_scratch(|buf| {
+            let mut last = 0;
+            for (start, part) in matches {
+                buf.push_str(&self[last..start]);
+                buf.push_str(to);
+                last = start + part.len();
+            }
+            buf.push_str(&self[last..]);
+        })
+    }
+
+    /// Repeat `n` times like [`str::repeat`] and intern the result,
+    /// see [`to_lowercase_interned`](IStr::to_lowercase_interned)
+    ///
+    /// # Example
+    /// ```
+    /// # use pstr::IStr;
+    /// let indent = IStr::new("  ").repeat_interned(3);
+    /// assert_eq!(indent, "      ");
+    /// ```
+    pub fn repeat_interned(&self, n: usize) -> IStr {
+        if n == 1 {
+            return self.clone();
+        }
+        let len = self.len().checked_mul(n).expect("capacity overflow");
+        with_scratch(|buf| {
+            buf.reserve(len);
+            for _ in 0..n {
+                buf.push_str(self);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case() {
+        let a = IStr::new("transform_case_pooled");
+        assert_eq!(a.to_lowercase_interned().as_ptr(), a.as_ptr());
+        assert_eq!(a.to_ascii_lowercase_interned().as_ptr(), a.as_ptr());
+        assert_eq!(a.to_uppercase_interned(), "TRANSFORM_CASE_POOLED");
+        assert_eq!(IStr::new("ΟΔΟΣ").to_lowercase_interned(), "οδος");
+        assert_eq!(IStr::new("straße").to_uppercase_interned(), "STRASSE");
+        assert_eq!(IStr::new("ÀB").to_ascii_lowercase_interned(), "Àb");
+        assert_eq!(IStr::new("  x ").trim_interned(), IStr::new("x"));
+        assert_eq!(a.trim_interned().as_ptr(), a.as_ptr());
+    }
+
+    #[test]
+    fn test_replace() {
+        let a = IStr::new("a.b.c.pooled.path");
+        assert_eq!(a.replace_interned(".", "::"), "a::b::c::pooled::path");
+        assert_eq!(a.replacen_interned(".", "", 1), "ab.c.pooled.path");
+        assert_eq!(a.replacen_interned(".", "", 0).as_ptr(), a.as_ptr());
+        assert_eq!(
+            IStr::new("aaa").replace_interned("", "-"),
+            "aaa".replace("", "-")
+        );
+    }
+
+    #[test]
+    fn test_repeat() {
+        let a = IStr::new("repeat_pooled_str_");
+        assert_eq!(a.repeat_interned(0), "");
+        assert_eq!(a.repeat_interned(1).as_ptr(), a.as_ptr());
+        assert_eq!(a.repeat_interned(2), "repeat_pooled_str_repeat_pooled_str_");
+    }
+}